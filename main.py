@@ -3,10 +3,14 @@ import argparse
 import logging
 import time
 
+import python.certify_box.main_certify_box
 import python.custom.main_custom
 import python.data.main_data
 import python.figure.main_figure
 import python.nu.main_nu
+import python.path.main_path
+import python.roots.main_roots
+import python.spectral_abscissa.main_spectral_abscissa
 import python.utils.log_helper
 
 
@@ -33,7 +37,8 @@ def parse_cli_arguments():
         metavar='command',
         type=str,
         help='command to run',
-        choices=['data', 'figure', 'nu', 'custom'],
+        choices=['data', 'figure', 'nu', 'roots', 'spectral-abscissa', 'crossing-curves', 'gu2005', 'path',
+                 'certify-box', 'custom'],
     )
 
     arg_parser.add_argument(
@@ -92,6 +97,17 @@ def main():
         python.figure.main_figure.main(args)
     elif args.command == 'nu':
         python.nu.main_nu.main(args)
+    elif args.command == 'roots':
+        python.roots.main_roots.main(args)
+    elif args.command in ['crossing-curves', 'gu2005']:
+        # Curves are stored in data files, and drawn by the region figure
+        python.data.main_data.main(args)
+    elif args.command == 'spectral-abscissa':
+        python.spectral_abscissa.main_spectral_abscissa.main(args)
+    elif args.command == 'path':
+        python.path.main_path.main(args)
+    elif args.command == 'certify-box':
+        python.certify_box.main_certify_box.main(args)
     else:
         raise argparse.ArgumentError(f'Unknown command: {args.command}')
 
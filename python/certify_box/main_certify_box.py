@@ -0,0 +1,82 @@
+"""This module contains facilities for certifying stability over a box or a convex polygon.
+
+The rust subsystem recursively subdivides the given polygon and covers the obtained cells
+with pregions of the region algorithm.
+The polygon is certified if all cells get covered; otherwise, the uncovered cells are reported.
+The results are visualized on a matplotlib figure, which is stored in the output directory.
+"""
+import logging
+import subprocess
+import contextlib
+from types import SimpleNamespace
+import json
+
+import matplotlib.pyplot as plt
+import matplotlib.patches as patches
+
+import python.utils.rust_adapter as rust_utils
+import python.utils.storage as storage
+from python.figure.main_figure import get_pregion_boundary, get_pregion_q
+
+
+logger = logging.getLogger(__name__)
+
+
+def certify_box(args):
+    """Invoke rust subsystem to certify the polygon of given configuration."""
+
+    rust_args = rust_utils.build_rust_command(args)
+    logger.info(f'Invoking Rust subsystem for certify-box')
+
+    result = None
+    with contextlib.redirect_stdout(logging.getLogger(__name__)):
+        result = subprocess.run(rust_args, cwd='./rust', stdout=None, stderr=None)
+
+    if result.returncode != 0:
+        raise Exception(f'Rust subsystem for certify-box exited with {result.returncode}')
+
+    logger.info(f'Rust subsystem for certify-box complete')
+
+
+def create_figure(args):
+    """Visualize the polygon, its covering pregions and uncovered cells on a new figure."""
+
+    box_results = None
+
+    with open(f'output/certify_box/temp_data/{args.configuration}.boxdata', 'r') as read_file:
+        box_results = json.load(read_file, object_hook=lambda d: SimpleNamespace(**d))
+
+    if box_results == None:
+        raise Exception(f'Error reading certify-box results from file')
+
+    fig, ax = plt.subplots()
+
+    # Configure axes
+    ax.set_xlim(box_results.limits.p1_min, box_results.limits.p1_max)
+    ax.set_ylim(box_results.limits.p2_min, box_results.limits.p2_max)
+    ax.set_xlabel(f'${box_results.parameters[0]}$')
+    ax.set_ylabel(f'${box_results.parameters[1]}$')
+
+    for pregion in box_results.pregions:
+        boundary = get_pregion_boundary(pregion, N=100, p=get_pregion_q(pregion))
+        ax.plot(boundary[0, :], boundary[1, :], color='lightsteelblue', linewidth=0.5)
+
+    for cell in box_results.uncovered_cells:
+        width = cell.p_max[0] - cell.p_min[0]
+        height = cell.p_max[1] - cell.p_min[1]
+        ax.add_patch(patches.Rectangle(cell.p_min, width, height, facecolor='lightcoral'))
+
+    polygon = patches.Polygon(box_results.polygon, closed=True, fill=False, edgecolor='k')
+    ax.add_patch(polygon)
+
+    status = 'certified' if box_results.certified else 'not certified'
+    ax.set_title(f'$\\nu={box_results.nu}$ ({status})')
+    return fig
+
+
+def main(args):
+    """Certify, visualize and store the polygon for given configuration."""
+    certify_box(args)
+    fig = create_figure(args)
+    extension = 'pdf'
+    storage.save_figure(args, fig, 'certify_box', 'figure', extension)
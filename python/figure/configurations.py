@@ -124,6 +124,20 @@ def retarded1_custom_func(ax, legend_handles, language='english'):
     return ax, legend_handles
 
 
+def retarded2_custom_func(ax, legend_handles, language='english'):
+    """Add results from Gu2005 (calculated by the rust subsystem) to given axes."""
+    SCS_LABELS = {'english': 'SCS', 'serbian': 'SCS'}
+    if gu2005.add_gu2005_rust_curves(ax, 'retarded2'):
+        legend_handle = Line2D(
+            [0], [0],
+            color='black',
+            linestyle='--',
+            label=SCS_LABELS[language],
+        )
+        legend_handles.append(legend_handle)
+    return ax, legend_handles
+
+
 def distributed_delay1_narrow_custom_func(ax, legend_handles, language='english'):
     """Cut off portion of axes in order to hide uninteresting areas."""
     ax.set_xlim(0, 20.0)
@@ -356,6 +370,46 @@ REGION_CONFIGURATIONS = {
         custom_func=retarded1_custom_func,
     ),
 
+    'retarded1_auto' : RegionConfiguration(
+        rust_configuration='retarded1_auto',
+        width=COMMON_WIDTHS['single_column'],
+        height=COMMON_WIDTHS['single_column'] * W2H_RATIO,
+        ticks=TickConfiguration(1, 1, 0.25, 0.25),
+        ncol=3,
+        bbox=(0, -0.17, 1, 0.1),
+        custom_func=retarded1_custom_func,
+    ),
+
+    'retarded1_coverage' : RegionConfiguration(
+        rust_configuration='retarded1_coverage',
+        width=COMMON_WIDTHS['single_column'],
+        height=COMMON_WIDTHS['single_column'] * W2H_RATIO,
+        ticks=TickConfiguration(1, 1, 0.25, 0.25),
+        ncol=3,
+        bbox=(0, -0.17, 1, 0.1),
+        custom_func=retarded1_custom_func,
+    ),
+
+    'retarded1_shared' : RegionConfiguration(
+        rust_configuration='retarded1_shared',
+        width=COMMON_WIDTHS['single_column'],
+        height=COMMON_WIDTHS['single_column'] * W2H_RATIO,
+        ticks=TickConfiguration(1, 1, 0.25, 0.25),
+        ncol=3,
+        bbox=(0, -0.17, 1, 0.1),
+        custom_func=retarded1_custom_func,
+    ),
+
+    'retarded1_infinity_norm' : RegionConfiguration(
+        rust_configuration='retarded1_infinity_norm',
+        width=COMMON_WIDTHS['single_column'],
+        height=COMMON_WIDTHS['single_column'] * W2H_RATIO,
+        ticks=TickConfiguration(1, 1, 0.25, 0.25),
+        ncol=3,
+        bbox=(0, -0.17, 1, 0.1),
+        custom_func=retarded1_custom_func,
+    ),
+
     'retarded2' : RegionConfiguration(
         rust_configuration='retarded2',
         width=COMMON_WIDTHS['single_column'],
@@ -363,6 +417,7 @@ REGION_CONFIGURATIONS = {
         ticks=TickConfiguration(1, 1, 0.25, 0.25),
         ncol=2,
         bbox=(0, -0.17, 1, 0.1),
+        custom_func=retarded2_custom_func,
     ),
 
 
@@ -386,6 +441,15 @@ REGION_CONFIGURATIONS = {
         bbox=(0, -0.17, 1, 0.1),
     ),
 
+    'distributed_delay1_scaled' : RegionConfiguration(
+        rust_configuration='distributed_delay1_scaled',
+        width=COMMON_WIDTHS['double_column'],
+        height=COMMON_WIDTHS['single_column'],
+        ticks=TickConfiguration(1, 0.5, 0.25, 0.25),
+        ncol=3,
+        bbox=(0, -0.17, 1, 0.1),
+    ),
+
     'distributed_delay1_narrow' : RegionConfiguration(
         rust_configuration='distributed_delay1',
         width=COMMON_WIDTHS['single_column'],
@@ -1,5 +1,6 @@
 """This module contains facilities for plotting fsals results on matplotlib figures."""
 import logging
+from pathlib import Path
 
 import matplotlib.pyplot as plt
 import matplotlib as mpl
@@ -19,6 +20,8 @@ ORIGIN_MARKERCOLOR = 'black'
 ORIGIN_MARKERSIZE = 4
 ORIGIN_LABEL_LINE = {'english': 'Origins', 'serbian': 'Početne tačke'}
 ORIGIN_LABEL_REGION = 'Origins'
+CROSSING_CURVE_COLOR = 'black'
+CROSSING_CURVE_LABEL = {'english': 'Crossing curves', 'serbian': 'Krive prelaska'}
 COLORS = {
      0: 'g',
      1: 'pink',
@@ -131,7 +134,8 @@ def get_drawable_canvas(width, height):
 def add_origins_to_ax(ax, regions):
     """Draw region origins on given axes."""
     for region in regions:
-        add_origin_to_ax(ax, region.origin)
+        for origin in region.origins:
+            add_origin_to_ax(ax, origin)
 
 
 def add_origin_to_ax(ax, origin):
@@ -140,12 +144,24 @@ def add_origin_to_ax(ax, origin):
             clip_on=False, markersize=ORIGIN_MARKERSIZE)
 
 
+def get_pregion_q(pregion):
+    """Get Hölder exponent of given pregion (infinity is stored as null)."""
+    q = getattr(pregion, 'q', 2)
+    return np.inf if q is None else q
+
+
+def get_pregion_scaling(pregion):
+    """Get per-axis scaling of given pregion."""
+    return np.array(getattr(pregion, 'scaling', (1, 1)))
+
+
 def get_corners(pregion):
     """Get corners of rectangle surrounding given pregion."""
-    upper_left_p1 = pregion.origin[0] - pregion.radius
-    upper_left_p2 = pregion.origin[1] - pregion.radius
-    lower_right_p1 = pregion.origin[0] + pregion.radius
-    lower_right_p2 = pregion.origin[1] + pregion.radius
+    a1, a2 = get_pregion_scaling(pregion)
+    upper_left_p1 = pregion.origin[0] - pregion.radius * a1
+    upper_left_p2 = pregion.origin[1] - pregion.radius * a2
+    lower_right_p1 = pregion.origin[0] + pregion.radius * a1
+    lower_right_p2 = pregion.origin[1] + pregion.radius * a2
 
     upper_left = np.array([upper_left_p1, upper_left_p2])
     lower_right = np.array([lower_right_p1, lower_right_p2])
@@ -183,10 +199,16 @@ def add_regions_to_ax(fig, ax, data, colors):
         color = tuple([round(c*255) for c in color])
 
         for pregion in region.pregions:
-            corners = get_corners(pregion)
-            upper_np, lower_np = corners2pixels(corners, spans, pixel_dimensions, mins)
-            upper, lower = (upper_np[0], upper_np[1]), (lower_np[0], lower_np[1])
-            canvas.ellipse([upper, lower], fill=color)
+            q = get_pregion_q(pregion)
+            if q == 2:
+                corners = get_corners(pregion)
+                upper_np, lower_np = corners2pixels(corners, spans, pixel_dimensions, mins)
+                upper, lower = (upper_np[0], upper_np[1]), (lower_np[0], lower_np[1])
+                canvas.ellipse([upper, lower], fill=color)
+            else:
+                boundary = get_pregion_boundary(pregion, N=64, p=q).T
+                pixels = np.rint((boundary - mins) / spans * pixel_dimensions).astype(int)
+                canvas.polygon([tuple(pixel) for pixel in pixels], fill=color)
 
     box = [data.limits.p1_min, data.limits.p1_max, data.limits.p2_min, data.limits.p2_max]
     ax.imshow(image, extent=box, aspect='auto', origin='lower')
@@ -202,14 +224,14 @@ def get_pregion_boundary(pregion, N=1000, p=2):
         x2 = np.power(np.power(pregion.radius, p) - np.power(np.abs(x1), p), 1/p)
     x1_total = np.concatenate((x1, np.flip(x1.copy())))
     x2_total = np.concatenate((x2, np.flip(-x2.copy())))
-    unshifted_boundary = np.vstack((x1_total, x2_total))
+    unshifted_boundary = np.vstack((x1_total, x2_total)) * get_pregion_scaling(pregion)[:, np.newaxis]
     shifted_boundary = unshifted_boundary + np.array(pregion.origin, ndmin=2).T
     return shifted_boundary
 
 
 def add_pregion_to_ax(ax, pregion, color, fill=True):
     """Draw pregion object to given axes."""
-    region_boundary = get_pregion_boundary(pregion)
+    region_boundary = get_pregion_boundary(pregion, p=get_pregion_q(pregion))
     add_polygon(ax, region_boundary, color, fill)
 
 
@@ -221,6 +243,21 @@ def add_polygon(ax, poly_boundary, style_string='g', fill=True):
         ax.plot(poly_boundary[0,:], poly_boundary[1,:], style_string)
 
 
+def add_crossing_curves_to_ax(ax, cfg):
+    """Draw crossing curves (if they were calculated) to given axes."""
+    path = f'output/data/region/{cfg.rust_configuration}.curves'
+    if not Path(path).is_file():
+        return False
+
+    data = storage.read_data_from_path(path)
+    for curve in data.curves:
+        p1 = [point.p[0] for point in curve.points]
+        p2 = [point.p[1] for point in curve.points]
+        ax.plot(p1, p2, color=CROSSING_CURVE_COLOR, linewidth=0.8, linestyle='--')
+
+    return True
+
+
 def create_figure_line(args):
     """Visualize fsals line results from file on a figure."""
     cfg = LINE_CONFIGURATIONS[args.configuration]
@@ -308,12 +345,18 @@ def create_figure_region(args):
     if cfg.draw_origins:
         add_origins_to_ax(ax, data.regions)
 
+    curves_drawn = add_crossing_curves_to_ax(ax, cfg)
+
     # Prepare regular legend labels
     nus = sorted(list(nus))
     legend_handles = [
         Line2D([0], [0], color=COLORS[nu], label=f'$NU_f$ = {nu}') for nu in nus
     ]
 
+    if curves_drawn:
+        legend_handles.append(Line2D([0], [0], color=CROSSING_CURVE_COLOR, linestyle='--',
+            label=CROSSING_CURVE_LABEL[cfg.language]))
+
     # Add origin label, if necessary
     if cfg.draw_origins:
         origin_handle = Line2D([0], [0], color='black', linestyle='None',
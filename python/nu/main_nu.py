@@ -16,6 +16,7 @@ from types import SimpleNamespace
 import json
 
 import matplotlib.pyplot as plt
+import matplotlib.patches as patches
 
 import python.utils.rust_adapter as rust_utils
 import python.utils.storage as storage
@@ -63,6 +64,28 @@ def nu2color(nu):
     return color
 
 
+def nu2cell_color(nu):
+    """Determine the color of a quadtree cell with given nu (None for boundary cells)."""
+
+    if nu is None:
+        return 'lightgray'
+    elif nu > 0:
+        return nu2color(nu)
+    else:
+        return 'g'
+
+
+def add_cells(ax, cells):
+    """Draw leaf cells of the adaptive nu quadtree."""
+
+    for cell in cells:
+        width = cell.p_max[0] - cell.p_min[0]
+        height = cell.p_max[1] - cell.p_min[1]
+        rectangle = patches.Rectangle(cell.p_min, width, height, linewidth=0.1,
+                                      edgecolor='k', facecolor=nu2cell_color(cell.nu))
+        ax.add_patch(rectangle)
+
+
 def create_figure(args):
     """Visualize nu data on a new figure."""
 
@@ -82,13 +105,19 @@ def create_figure(args):
     ax.set_xlabel(f'${nu_results.parameters[0]}$')
     ax.set_ylabel(f'${nu_results.parameters[1]}$')
 
-    # Add points
+    # Adaptive results are drawn as cells, since they are too dense for labels
+    if len(nu_results.cells) > 0:
+        add_cells(ax, nu_results.cells)
+        return fig
+
+    # Add points (unreliable ones are suffixed with a question mark)
     for point in nu_results.point_results:
         if point.nu > 0:
             color = nu2color(point.nu)
         else:
             color = 'g'
-        ax.text(point.p[0], point.p[1], str(int(point.nu)), color=color)
+        label = str(int(point.nu)) if point.reliable else f'{int(point.nu)}?'
+        ax.text(point.p[0], point.p[1], label, color=color)
         ax.plot(point.p[0], point.p[1], 'bo', markersize=1)
 
     return fig
@@ -0,0 +1,81 @@
+"""This module contains facilities for certifying stability along a parametric path.
+
+The rust subsystem certifies the given piecewise linear path segment by segment,
+using the line algorithm, and reports the first point at which certification fails.
+The path is visualized on a matplotlib figure, with the certified part highlighted,
+which is stored in the output directory.
+"""
+import logging
+import subprocess
+import contextlib
+from types import SimpleNamespace
+import json
+
+import matplotlib.pyplot as plt
+
+import python.utils.rust_adapter as rust_utils
+import python.utils.storage as storage
+
+
+logger = logging.getLogger(__name__)
+
+
+def certify_path(args):
+    """Invoke rust subsystem to certify the path of given configuration."""
+
+    rust_args = rust_utils.build_rust_command(args)
+    logger.info(f'Invoking Rust subsystem for path')
+
+    result = None
+    with contextlib.redirect_stdout(logging.getLogger(__name__)):
+        result = subprocess.run(rust_args, cwd='./rust', stdout=None, stderr=None)
+
+    if result.returncode != 0:
+        raise Exception(f'Rust subsystem for path exited with {result.returncode}')
+
+    logger.info(f'Rust subsystem for path complete')
+
+
+def create_figure(args):
+    """Visualize the path along with its certified part on a new figure."""
+
+    path_results = None
+
+    with open(f'output/path/temp_data/{args.configuration}.pathdata', 'r') as read_file:
+        path_results = json.load(read_file, object_hook=lambda d: SimpleNamespace(**d))
+
+    if path_results == None:
+        raise Exception(f'Error reading path results from file')
+
+    fig, ax = plt.subplots()
+
+    # Configure axes
+    ax.set_xlim(path_results.limits.p1_min, path_results.limits.p1_max)
+    ax.set_ylim(path_results.limits.p2_min, path_results.limits.p2_max)
+    ax.set_xlabel(f'${path_results.parameters[0]}$')
+    ax.set_ylabel(f'${path_results.parameters[1]}$')
+
+    # Whole path is dashed, and its certified part is drawn over it
+    p1 = [vertex[0] for vertex in path_results.vertices]
+    p2 = [vertex[1] for vertex in path_results.vertices]
+    ax.plot(p1, p2, 'k--', linewidth=0.8)
+
+    for segment in path_results.segments:
+        start = getattr(segment, 'from')  # 'from' is a python keyword
+        ratio = segment.certified_length / segment.length
+        end = [p_start + ratio * (p_to - p_start) for p_start, p_to in zip(start, segment.to)]
+        ax.plot([start[0], end[0]], [start[1], end[1]], 'g-')
+
+    if path_results.failure is not None:
+        ax.plot(path_results.failure[0], path_results.failure[1], 'rx')
+
+    ax.set_title(f'$\\nu={path_results.nu}$')
+    return fig
+
+
+def main(args):
+    """Certify, visualize and store the path for given configuration."""
+    certify_path(args)
+    fig = create_figure(args)
+    extension = 'pdf'
+    storage.save_figure(args, fig, 'path', 'figure', extension)
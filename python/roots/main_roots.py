@@ -0,0 +1,76 @@
+"""This module contains facilities for locating unstable zeros of the characteristic function.
+
+For each parametric point of the given configuration, the rust subsystem locates zeros
+of the characteristic function inside a search box in the right half-plane.
+The zeros of all points are visualized on a matplotlib figure in the complex plane,
+which is stored in the output directory.
+"""
+import logging
+import subprocess
+import contextlib
+from types import SimpleNamespace
+import json
+
+import matplotlib.pyplot as plt
+
+import python.utils.rust_adapter as rust_utils
+import python.utils.storage as storage
+
+
+logger = logging.getLogger(__name__)
+
+
+def calculate_roots(args):
+    """Invoke rust subsystem to locate zeros for given configuration."""
+
+    rust_args = rust_utils.build_rust_command(args)
+    logger.info(f'Invoking Rust subsystem for roots')
+
+    result = None
+    with contextlib.redirect_stdout(logging.getLogger(__name__)):
+        result = subprocess.run(rust_args, cwd='./rust', stdout=None, stderr=None)
+
+    if result.returncode != 0:
+        raise Exception(f'Rust subsystem for roots exited with {result.returncode}')
+
+    logger.info(f'Rust subsystem for roots complete')
+
+
+def create_figure(args):
+    """Visualize located zeros of each parametric point on a new figure."""
+
+    roots_results = None
+
+    with open(f'output/roots/temp_data/{args.configuration}.rootsdata', 'r') as read_file:
+        roots_results = json.load(read_file, object_hook=lambda d: SimpleNamespace(**d))
+
+    if roots_results == None:
+        raise Exception(f'Error reading roots results from file')
+
+    fig, ax = plt.subplots()
+    ax.set_xlabel(r'$\Re(s)$')
+    ax.set_ylabel(r'$\Im(s)$')
+    ax.axvline(0, color='k', linewidth=0.5)
+
+    # Each point gets its own marker color; multiplicities are written next to the zeros
+    for point in roots_results.point_results:
+        label = f'$({roots_results.parameters[0]}, {roots_results.parameters[1]})$ = {tuple(point.p)}'
+        if not point.reliable:
+            label += '?'
+        re = [root.re for root in point.roots]
+        im = [root.im for root in point.roots]
+        lines = ax.plot(re, im, 'x', label=label)
+        for root in point.roots:
+            if root.multiplicity > 1:
+                ax.text(root.re, root.im, str(root.multiplicity), color=lines[0].get_color())
+
+    ax.legend()
+    return fig
+
+
+def main(args):
+    """Locate, visualize and store zeros for given configuration."""
+    calculate_roots(args)
+    fig = create_figure(args)
+    extension = 'pdf'
+    storage.save_figure(args, fig, 'roots', 'figure', extension)
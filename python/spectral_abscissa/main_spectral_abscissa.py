@@ -0,0 +1,86 @@
+"""This module contains facilities for visualizing the spectral abscissa of a system.
+
+The spectral abscissa is the real part of the rightmost characteristic root.
+Unlike nu, it is a continuous measure of stability: its zero level set is the
+stability boundary, while its negative levels show decay rates.
+The rust subsystem calculates it on an equidistant parametric grid, and the results
+are visualized on a matplotlib contour figure, which is stored in the output directory.
+"""
+import logging
+import subprocess
+import contextlib
+from types import SimpleNamespace
+import json
+
+import numpy as np
+import matplotlib.pyplot as plt
+
+import python.utils.rust_adapter as rust_utils
+import python.utils.storage as storage
+
+
+logger = logging.getLogger(__name__)
+
+
+def calculate_spectral_abscissa(args):
+    """Invoke rust subsystem to calculate the spectral abscissa for given configuration."""
+
+    rust_args = rust_utils.build_rust_command(args)
+    logger.info(f'Invoking Rust subsystem for spectral abscissa')
+
+    result = None
+    with contextlib.redirect_stdout(logging.getLogger(__name__)):
+        result = subprocess.run(rust_args, cwd='./rust', stdout=None, stderr=None)
+
+    if result.returncode != 0:
+        raise Exception(f'Rust subsystem for spectral abscissa exited with {result.returncode}')
+
+    logger.info(f'Rust subsystem for spectral abscissa complete')
+
+
+def create_figure(args):
+    """Visualize spectral abscissa data on a new contour figure."""
+
+    results = None
+
+    path = f'output/spectral_abscissa/temp_data/{args.configuration}.sadata'
+    with open(path, 'r') as read_file:
+        results = json.load(read_file, object_hook=lambda d: SimpleNamespace(**d))
+
+    if results == None:
+        raise Exception(f'Error reading spectral abscissa results from file')
+
+    # Points are stored row by row; points without zeros in the search box lie left of it
+    p1 = sorted(set(point.p[0] for point in results.point_results))
+    p2 = sorted(set(point.p[1] for point in results.point_results))
+    abscissa = [
+        point.abscissa if point.abscissa is not None else results.search_box.re_min
+        for point in results.point_results
+    ]
+    abscissa = np.array(abscissa).reshape(len(p2), len(p1))
+
+    fig, ax = plt.subplots()
+
+    ax.set_xlim(results.limits.p1_min, results.limits.p1_max)
+    ax.set_ylim(results.limits.p2_min, results.limits.p2_max)
+    ax.set_xlabel(f'${results.parameters[0]}$')
+    ax.set_ylabel(f'${results.parameters[1]}$')
+
+    filled = ax.contourf(p1, p2, abscissa, levels=20, cmap='RdYlGn_r')
+    fig.colorbar(filled, ax=ax, label='spectral abscissa')
+    ax.contour(p1, p2, abscissa, levels=[0.0], colors='k', linewidths=2)
+
+    # Mark points whose zero counts could not be trusted
+    for point in results.point_results:
+        if not point.reliable:
+            ax.text(point.p[0], point.p[1], '?', color='k')
+
+    return fig
+
+
+def main(args):
+    """Calculate, visualize and store the spectral abscissa for given configuration."""
+    calculate_spectral_abscissa(args)
+    fig = create_figure(args)
+    extension = 'pdf'
+    storage.save_figure(args, fig, 'spectral_abscissa', 'figure', extension)
@@ -1,22 +1,48 @@
 """Calculate the stability of system retarded1 using the Gu2005 method.
 
 For more information about the method, see https://doi.org/10.1016/j.jmaa.2005.02.034.
+The method is also implemented in the rust subsystem (`gu2005` command); curves
+produced by it are preferred over the ones calculated here, when available.
 """
 import logging
+from pathlib import Path
 
 import numpy as np
 
+import python.utils.storage as storage
+
 
 def add_SCS_curves(ax, curves, color='black', linestyle='-'):
     for curve in curves:
         ax.plot(curve[0,:], curve[1,:], color=color, linestyle=linestyle)
 
 
+def read_SCS_curves(rust_configuration):
+    """Read SCS curves calculated by the rust subsystem, if there are any."""
+    path = f'output/comparison/gu2005/{rust_configuration}.scs'
+    if not Path(path).is_file():
+        return None
+
+    data = storage.read_data_from_path(path)
+    return [np.array(curve).T for curve in data.curves]
+
+
 def add_gu2005_example1(ax):
-    def a1(s): return 2/s
-    def a2(s): return 1/np.power(s,2)
-    SCS_curves = get_SCS_curves(a1, a2)
+    SCS_curves = read_SCS_curves('retarded1')
+    if SCS_curves is None:
+        def a1(s): return 2/s
+        def a2(s): return 1/np.power(s,2)
+        SCS_curves = get_SCS_curves(a1, a2)
+    add_SCS_curves(ax, SCS_curves, color='black', linestyle='--')
+
+
+def add_gu2005_rust_curves(ax, rust_configuration):
+    """Draw SCS curves calculated by the rust subsystem; return whether there were any."""
+    SCS_curves = read_SCS_curves(rust_configuration)
+    if SCS_curves is None:
+        return False
     add_SCS_curves(ax, SCS_curves, color='black', linestyle='--')
+    return True
 
 
 def get_OMEGA(a1, a2, N=100000):
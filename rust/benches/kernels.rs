@@ -0,0 +1,218 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use fsals::data::origins::Origins;
+use fsals::data::region::{self, Delta, RegionConfiguration};
+use fsals::nu::{self, ContourConfiguration, NuConfiguration, StabilityBoundary};
+use fsals::systems::retarded1;
+use fsals::types::{Comp, Limits, Par};
+use fsals::utils::batch::KernelTable;
+use fsals::utils::optimization::{BatchFraction, BrentSearch, MinimizationStrategy};
+
+
+/* One nu configuration per registered system, providing representative inputs */
+const SYSTEM_CONFIGURATIONS: [&str; 16] = [
+    "retarded1",
+    "retarded2",
+    "distributed_delay1",
+    "semi_infinite_rod",
+    "finite_rod",
+    "pde_complex_k_sigma",
+    "pde_complex_tau_sigma",
+    "pde_complex_beta_sigma",
+    "telegrapher_x_k",
+    "telegrapher_alpha_gamma",
+    "telegrapher_standard",
+    "ln_system1",
+    "dopid1",
+    "dopid2",
+    "dopid3",
+    "test_configuration",
+];
+
+const LOG_SPACE_STEPS: usize = 1000;
+const LIN_STEPS: usize = 1000;
+const EPS: f64 = 1e-2;
+
+
+fn get_configurations() -> Vec<&'static NuConfiguration> {
+    SYSTEM_CONFIGURATIONS
+        .iter()
+        .map(|name| nu::CONFIGURATONS.get(name).expect("Unknown configuration"))
+        .collect()
+}
+
+
+fn get_center(limits: &Limits) -> Par {
+    ((limits.p1_min + limits.p1_max) / 2.0, (limits.p2_min + limits.p2_max) / 2.0)
+}
+
+
+fn get_log_space(conf: &NuConfiguration) -> Vec<f64> {
+    iter_num_tools::log_space(1e-3..=conf.contour_conf.w_max, LOG_SPACE_STEPS).collect()
+}
+
+
+fn bench_f_complex(c: &mut Criterion) {
+    let mut group = c.benchmark_group("f_complex");
+    for conf in get_configurations() {
+        let p = get_center(&conf.limits);
+        let log_space = get_log_space(conf);
+        group.bench_function(conf.system.name, |b| b.iter(|| {
+            log_space
+                .iter()
+                .map(|w| (conf.system.f_complex)(Comp::new(0.0, *w), black_box(p)).norm())
+                .sum::<f64>()
+        }));
+    }
+    group.finish();
+}
+
+
+fn bench_region_fraction(c: &mut Criterion) {
+    let mut group = c.benchmark_group("region_fraction");
+    for conf in get_configurations() {
+        let Some(region_fraction) = conf.system.region_fraction else { continue };
+        let p = get_center(&conf.limits);
+        let log_space = get_log_space(conf);
+        let mut out = vec![0.0; log_space.len()];
+        group.bench_function(conf.system.name, |b| b.iter(|| {
+            region_fraction(&log_space, black_box(p), EPS, &mut out);
+            out.iter().sum::<f64>()
+        }));
+    }
+    group.finish();
+}
+
+
+fn bench_region_fraction_precalculated_numerator(c: &mut Criterion) {
+    let mut group = c.benchmark_group("region_fraction_precalculated_numerator");
+    for conf in get_configurations() {
+        let Some(region_fraction) = conf.system.region_fraction_precalculated_numerator else {
+            continue
+        };
+        let p = get_center(&conf.limits);
+        let log_space = get_log_space(conf);
+        let numerator: Vec<f64> = log_space
+            .iter()
+            .map(|w| (conf.system.f_complex)(Comp::new(0.0, *w), p).norm())
+            .collect();
+        let kernels = KernelTable::new(conf.system.frequency_kernels, &log_space);
+        let mut out = vec![0.0; log_space.len()];
+        group.bench_function(conf.system.name, |b| b.iter(|| {
+            region_fraction(&numerator, &log_space, &kernels, black_box(p), EPS, &mut out);
+            out.iter().sum::<f64>()
+        }));
+    }
+    group.finish();
+}
+
+
+fn bench_line_denominator(c: &mut Criterion) {
+    const ANGLE: f64 = 0.3;
+    let mut group = c.benchmark_group("line_denominator");
+    for conf in get_configurations() {
+        let Some(line_denominator) = conf.system.line_denominator else { continue };
+        let p = get_center(&conf.limits);
+        let log_space = get_log_space(conf);
+        group.bench_function(conf.system.name, |b| b.iter(|| {
+            log_space
+                .iter()
+                .map(|w| line_denominator(*w, black_box(p), ANGLE, 0.0, EPS))
+                .sum::<f64>()
+        }));
+    }
+    group.finish();
+}
+
+
+fn bench_brent_search(c: &mut Criterion) {
+    let mut group = c.benchmark_group("brent_search");
+    for conf in get_configurations() {
+        let (Some(precalculated), Some(fraction)) = (
+            conf.system.region_fraction_precalculated_numerator,
+            conf.system.region_fraction) else { continue };
+        let p = get_center(&conf.limits);
+        let log_space = get_log_space(conf);
+        let numerator: Vec<f64> = log_space
+            .iter()
+            .map(|w| (conf.system.f_complex)(Comp::new(0.0, *w), p).norm())
+            .collect();
+        let kernels = KernelTable::new(conf.system.frequency_kernels, &log_space);
+        group.bench_function(conf.system.name, |b| b.iter(|| {
+            let p = black_box(p);
+            let problem = BatchFraction {
+                log_space: &log_space,
+                logspace_fraction_function: &|w: &[f64], out: &mut [f64]| {
+                    precalculated(&numerator, w, &kernels, p, EPS, out)
+                },
+                fraction_function: &|w: &[f64], out: &mut [f64]| fraction(w, p, EPS, out),
+            };
+            BrentSearch { lin_steps: LIN_STEPS }.minimize(&problem)
+        }));
+    }
+    group.finish();
+}
+
+
+fn bench_calculate_nu_single(c: &mut Criterion) {
+    let mut group = c.benchmark_group("calculate_nu_single");
+    group.sample_size(10);
+    for conf in get_configurations() {
+        let p = get_center(&conf.limits);
+        group.bench_function(conf.name, |b| b.iter(|| {
+            nu::calculate_nu_single(&conf.contour_conf, &conf.system, black_box(p))
+        }));
+    }
+    group.finish();
+}
+
+
+fn bench_get_region(c: &mut Criterion) {
+    /* Coarse region around a single origin, small enough to be timed repeatedly */
+    let conf = RegionConfiguration {
+        name: "retarded1_benchmark",
+        system: retarded1::SYSTEM,
+        limits: Limits { p1_min: 0.0, p1_max: 2.6, p2_min: 0.0, p2_max: 3.3 },
+        safeguard: 0.98,
+        check_obsoletion: true,
+        origins: Origins::Manual(vec![(0.25, 1.00)]),
+        contour_conf: ContourConfiguration {
+            indentation_radius: 1e-3,
+            singular_points: vec![],
+            w_max: 1e5,
+            steps: 10_000,
+            boundary: StabilityBoundary::ImaginaryAxis,
+            },
+        delta: Delta::Abs(5e-2),
+        spawn_count: 16,
+        enforce_limits: false,
+        lin_steps: LIN_STEPS,
+        log_space_minw: 1e-3,
+        log_space_maxw: 1e5,
+        log_space_steps: LOG_SPACE_STEPS,
+        certified_minimum: false,
+        max_iter: Option::None,
+        nu_modifier: 0,
+        q: 2.0,
+        scaling: (1.0, 1.0),
+        coverage: Option::None,
+    };
+
+    let mut group = c.benchmark_group("get_region");
+    group.sample_size(10);
+    group.bench_function(conf.name, |b| b.iter(|| region::get_regions(&conf, &[(0.25, 1.00)])));
+    group.finish();
+}
+
+
+criterion_group!(
+    benches,
+    bench_f_complex,
+    bench_region_fraction,
+    bench_region_fraction_precalculated_numerator,
+    bench_line_denominator,
+    bench_brent_search,
+    bench_calculate_nu_single,
+    bench_get_region,
+);
+criterion_main!(benches);
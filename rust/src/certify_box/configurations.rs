@@ -0,0 +1,49 @@
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+
+use crate::types::Par;
+
+
+pub struct CertifyBoxConfiguration {
+    pub name: &'static str,                  // Config name
+    pub region_configuration: &'static str,  // Region configuration providing system and settings
+    pub polygon: Vec<Par>,                   // Convex polygon (e.g. the 4 corners of a box)
+    pub max_depth: u32,                      // Maximal number of subdivisions of the bounding box
+}
+
+
+lazy_static! {
+    pub static ref CONFIGURATIONS: HashMap<&'static str, CertifyBoxConfiguration> = {
+        let mut configs = HashMap::new();
+
+        configs.insert("retarded1", CertifyBoxConfiguration {
+            name: "retarded1",
+            region_configuration: "retarded1",
+            polygon: vec![(0.1, 0.6), (0.6, 0.6), (0.6, 1.4), (0.1, 1.4)],
+            max_depth: 6,
+        });
+
+        configs.insert("retarded1_triangle", CertifyBoxConfiguration {
+            name: "retarded1_triangle",
+            region_configuration: "retarded1",
+            polygon: vec![(1.2, 0.6), (2.4, 0.8), (1.6, 1.8)],
+            max_depth: 6,
+        });
+
+        configs.insert("retarded1_unstable", CertifyBoxConfiguration {
+            name: "retarded1_unstable",
+            region_configuration: "retarded1",
+            polygon: vec![(0.2, 1.0), (1.2, 1.0), (1.2, 2.0), (0.2, 2.0)],
+            max_depth: 5,
+        });
+
+        configs.insert("distributed_delay1", CertifyBoxConfiguration {
+            name: "distributed_delay1",
+            region_configuration: "distributed_delay1",
+            polygon: vec![(0.8, 0.1), (1.2, 0.1), (1.2, 0.3), (0.8, 0.3)],
+            max_depth: 6,
+        });
+
+        configs
+    };
+}
@@ -0,0 +1,165 @@
+mod configurations;
+
+use log::{debug, info, warn};
+use rayon::prelude::*;
+
+use crate::Args;
+use crate::data::region::{self, PRegion, RegionConfiguration};
+use crate::nu;
+use crate::types::{Limits, Par};
+use crate::utils::{geometry, storage};
+pub use configurations::{CertifyBoxConfiguration, CONFIGURATIONS};
+
+
+/* Axis-aligned cell of the subdivision */
+#[derive(Debug, Clone, Copy, serde::Serialize)]
+pub struct Cell {
+    p_min: Par,
+    p_max: Par,
+    depth: u32,
+}
+
+
+#[derive(Debug, serde::Serialize)]
+pub struct CertifyBoxResult {
+    polygon: Vec<Par>,
+    nu: i32,
+    certified: bool,             // Polygon is covered by pregions, so nu is constant over it
+    pregions: Vec<PRegion>,
+    uncovered_cells: Vec<Cell>,  // Cells of maximal depth which no pregion covers
+    limits: &'static Limits,
+    parameters: (&'static str, &'static str),
+}
+
+
+impl Cell {
+    fn corners(&self) -> [Par; 4] {
+        [self.p_min, (self.p_max.0, self.p_min.1), self.p_max, (self.p_min.0, self.p_max.1)]
+    }
+
+    fn center(&self) -> Par {
+        (0.5 * (self.p_min.0 + self.p_max.0), 0.5 * (self.p_min.1 + self.p_max.1))
+    }
+
+    fn children(&self) -> [Cell; 4] {
+        let (x0, y0) = self.p_min;
+        let (x2, y2) = self.p_max;
+        let (x1, y1) = self.center();
+        let depth = self.depth + 1;
+
+        [
+            Cell { p_min: (x0, y0), p_max: (x1, y1), depth },
+            Cell { p_min: (x1, y0), p_max: (x2, y1), depth },
+            Cell { p_min: (x0, y1), p_max: (x1, y2), depth },
+            Cell { p_min: (x1, y1), p_max: (x2, y2), depth },
+        ]
+    }
+
+    /* Pregions are convex, so containing the corners means containing the cell */
+    fn is_covered_by(&self, pregion: &PRegion) -> bool {
+        self.corners().iter().all(|corner| pregion.is_point_inside(*corner))
+    }
+}
+
+
+fn get_bounding_cell(polygon: &[Par]) -> Cell {
+    let p1_min = polygon.iter().map(|p| p.0).fold(f64::INFINITY, f64::min);
+    let p1_max = polygon.iter().map(|p| p.0).fold(f64::NEG_INFINITY, f64::max);
+    let p2_min = polygon.iter().map(|p| p.1).fold(f64::INFINITY, f64::min);
+    let p2_max = polygon.iter().map(|p| p.1).fold(f64::NEG_INFINITY, f64::max);
+
+    Cell { p_min: (p1_min, p2_min), p_max: (p1_max, p2_max), depth: 0 }
+}
+
+
+fn get_centroid(polygon: &[Par]) -> Par {
+    let count = polygon.len() as f64;
+    let p1 = polygon.iter().map(|p| p.0).sum::<f64>() / count;
+    let p2 = polygon.iter().map(|p| p.1).sum::<f64>() / count;
+
+    (p1, p2)
+}
+
+
+/* Subdivide the bounding box of the polygon level by level, until each cell intersecting the
+ * polygon lies within a pregion; nu is constant within each pregion, and the polygon is
+ * connected, so nu is constant over the whole covered polygon */
+fn certify_box(conf: &CertifyBoxConfiguration, region_conf: &'static RegionConfiguration) -> CertifyBoxResult {
+    if !geometry::is_convex_polygon(&conf.polygon) {
+        panic!("Polygon must be convex, with at least 3 vertices");
+    }
+
+    let grid = region_conf.get_frequency_grid();
+    let delta = region::absolutize_delta(&region_conf.delta, &region_conf.limits, region_conf.scaling);
+    let centroid = get_centroid(&conf.polygon);
+    let nu = nu::calculate_nu_single(&region_conf.contour_conf, &region_conf.system, centroid);
+    let nu = nu + region_conf.nu_modifier;
+
+    let mut pregions: Vec<PRegion> = Vec::new();
+    let mut uncovered_cells: Vec<Cell> = Vec::new();
+    let mut cells = vec![get_bounding_cell(&conf.polygon)];
+
+    while !cells.is_empty() {
+        /* Cells outside of the polygon, or within already found pregions, need no attention */
+        let open_cells: Vec<Cell> = cells
+            .into_iter()
+            .filter(|cell| geometry::does_rectangle_intersect_polygon(cell.p_min, cell.p_max, &conf.polygon))
+            .filter(|cell| !pregions.iter().any(|pregion| cell.is_covered_by(pregion)))
+            .collect();
+        debug!("Evaluating pregions for {} cells", open_cells.len());
+
+        let new_pregions: Vec<PRegion> = open_cells
+            .par_iter()
+            .map(|cell| {
+                region::get_pregion(region_conf, cell.center(), false, delta, &grid, cell.depth, Option::None)
+            })
+            .collect();
+
+        let mut next_cells = Vec::new();
+        for (cell, pregion) in open_cells.iter().zip(new_pregions.iter()) {
+            if cell.is_covered_by(pregion) {
+                continue;
+            }
+            match cell.depth < conf.max_depth {
+                true => next_cells.extend(cell.children()),
+                false => uncovered_cells.push(*cell),
+            }
+        }
+
+        pregions.extend(new_pregions);
+        cells = next_cells;
+    }
+
+    let certified = uncovered_cells.is_empty();
+    match certified {
+        true => info!("Polygon certified to have nu={} with {} pregions", nu, pregions.len()),
+        false => warn!("Polygon not certified; {} cells remain uncovered", uncovered_cells.len()),
+    }
+
+    CertifyBoxResult {
+        polygon: conf.polygon.clone(),
+        nu,
+        certified,
+        pregions,
+        uncovered_cells,
+        limits: &region_conf.limits,
+        parameters: region_conf.system.parameters,
+    }
+}
+
+
+pub fn run(args: &Args) {
+    /* Certify the polygon */
+    let config_name = args.configuration
+        .as_ref()
+        .expect("certify-box requires configuration to be specified");
+    let config = CONFIGURATIONS.get(config_name.as_str()).expect("Unknown configuration");
+    let region_conf = region::CONFIGURATONS
+        .get(config.region_configuration)
+        .expect("Unknown region configuration");
+    let results = certify_box(config, region_conf);
+
+    /* Store results in file */
+    let filename = storage::get_filepath("certify_box", "temp_data", "boxdata", config_name);
+    storage::store_results(results, &filename);
+}
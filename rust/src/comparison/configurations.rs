@@ -0,0 +1,54 @@
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+
+use crate::systems::retarded1;
+use crate::systems::retarded2;
+use crate::types::{Comp, System};
+
+
+/* Two-delay system written as 1 + a1(s)e^(-s*tau1) + a2(s)e^(-s*tau2) */
+pub struct Gu2005Configuration {
+    pub name: &'static str,
+    pub system: System,
+    pub a1: fn(Comp) -> Comp,
+    pub a2: fn(Comp) -> Comp,
+    pub w_min: f64,                          // Lower end of the searched crossing frequencies
+    pub w_max: f64,                          // Upper end of the searched crossing frequencies
+    pub omega_steps: usize,                  // Granularity of the search for crossing frequencies
+    pub curve_steps: usize,                  // Number of points of each crossing curve
+    pub draw_depth: i32,                     // Number of considered branches per delay
+}
+
+
+/* Global collection of all Gu (2005) configurations */
+lazy_static! {
+    pub static ref GU2005_CONFIGURATIONS: HashMap<&'static str, Gu2005Configuration> = {
+        let mut configs = HashMap::new();
+
+        configs.insert("retarded1", Gu2005Configuration {
+            name: "retarded1",
+            system: retarded1::SYSTEM,
+            a1: retarded1::gu2005_a1,
+            a2: retarded1::gu2005_a2,
+            w_min: 1e-10,
+            w_max: 10.0,
+            omega_steps: 100_000,
+            curve_steps: 1_000,
+            draw_depth: 10,
+        });
+
+        configs.insert("retarded2", Gu2005Configuration {
+            name: "retarded2",
+            system: retarded2::SYSTEM,
+            a1: retarded2::gu2005_a1,
+            a2: retarded2::gu2005_a2,
+            w_min: 1e-10,
+            w_max: 10.0,
+            omega_steps: 100_000,
+            curve_steps: 1_000,
+            draw_depth: 10,
+        });
+
+        configs
+    };
+}
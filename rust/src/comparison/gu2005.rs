@@ -0,0 +1,215 @@
+/* Stability crossing curves of two-delay systems 1 + a1(s)e^(-s*tau1) + a2(s)e^(-s*tau2),
+ * by Gu, Niculescu and Chen (https://doi.org/10.1016/j.jmaa.2005.02.034) */
+use log::{debug, warn};
+use iter_num_tools::lin_space;
+use std::f64::consts::PI;
+
+use crate::types::{Comp, Par};
+use super::configurations::Gu2005Configuration;
+
+
+/* A crossing curve, as a sequence of (tau1, tau2) points */
+pub type ScsCurve = Vec<Par>;
+
+
+/* Frequencies at which a triangle with sides 1, |a1(jw)| and |a2(jw)| exists */
+fn is_in_omega(a1_norm: f64, a2_norm: f64) -> bool {
+    a1_norm + a2_norm >= 1.0 && (a1_norm - a2_norm).abs() <= 1.0
+}
+
+
+/* Frequency intervals of crossing (the set OMEGA) */
+pub fn get_omega(conf: &Gu2005Configuration) -> Vec<(f64, f64)> {
+    let w_values: Vec<f64> = lin_space(conf.w_min..=conf.w_max, conf.omega_steps).collect();
+    let mut intervals = Vec::new();
+    let mut interval_start: Option<f64> = Option::None;
+
+    for (i, w) in w_values.iter().enumerate() {
+        let s = Comp::new(0.0, *w);
+        let inside = is_in_omega((conf.a1)(s).norm(), (conf.a2)(s).norm());
+
+        match (interval_start, inside) {
+            (None, true) => interval_start = Some(*w),
+            (Some(start), false) => {
+                let end = w_values[i-1];
+                if start == end {
+                    warn!("Interval start and end points are the same for w={}!", end);
+                }
+                intervals.push((start, end));
+                interval_start = Option::None;
+            },
+            _ => (),
+        }
+    }
+
+    /* The last interval may reach the end of the searched frequencies */
+    if let Some(start) = interval_start {
+        intervals.push((start, conf.w_max));
+    }
+
+    intervals
+}
+
+
+/* Inner angles of the triangle opposite to |a2| and |a1|, respectively */
+fn get_thetas(a1_norm: f64, a2_norm: f64) -> (f64, f64) {
+    let cos1 = (1.0 + a1_norm.powi(2) - a2_norm.powi(2)) / (2.0 * a1_norm);
+    let cos2 = (1.0 + a2_norm.powi(2) - a1_norm.powi(2)) / (2.0 * a2_norm);
+
+    /* Interval ends may fall slightly outside of the triangle inequality */
+    (cos1.clamp(-1.0, 1.0).acos(), cos2.clamp(-1.0, 1.0).acos())
+}
+
+
+/* Smallest integer branch index k for which (angle + (2k-1)pi) is positive */
+fn get_first_positive_branch(angle: f64) -> i32 {
+    ((PI - angle) / (2.0 * PI)).floor() as i32 + 1
+}
+
+
+/* Crossing curve quantities at a single frequency */
+struct CrossingPoint {
+    w: f64,
+    a1_angle: f64,
+    a2_angle: f64,
+    theta1: f64,
+    theta2: f64,
+}
+
+
+impl CrossingPoint {
+    fn new(conf: &Gu2005Configuration, w: f64) -> CrossingPoint {
+        let s = Comp::new(0.0, w);
+        let (a1, a2) = ((conf.a1)(s), (conf.a2)(s));
+        let (theta1, theta2) = get_thetas(a1.norm(), a2.norm());
+
+        CrossingPoint { w, a1_angle: a1.arg(), a2_angle: a2.arg(), theta1, theta2 }
+    }
+
+    /* Delays on the "+" and "-" curves for branch indices u and v */
+    fn get_delays(&self, u: i32, v: i32) -> (Par, Par) {
+        let branch1 = self.a1_angle + (2.0 * u as f64 - 1.0) * PI;
+        let branch2 = self.a2_angle + (2.0 * v as f64 - 1.0) * PI;
+
+        let plus = ((branch1 + self.theta1) / self.w, (branch2 - self.theta2) / self.w);
+        let minus = ((branch1 - self.theta1) / self.w, (branch2 + self.theta2) / self.w);
+        (plus, minus)
+    }
+}
+
+
+/* Split a curve into segments with positive delays */
+fn filter_curve(curve: &[Par]) -> Vec<ScsCurve> {
+    let mut curves = Vec::new();
+    let mut current: ScsCurve = Vec::new();
+
+    for point in curve {
+        if point.0 > 0.0 && point.1 > 0.0 {
+            current.push(*point);
+        } else if !current.is_empty() {
+            curves.push(current);
+            current = Vec::new();
+        }
+    }
+    if !current.is_empty() {
+        curves.push(current);
+    }
+
+    curves
+}
+
+
+fn get_interval_curves(conf: &Gu2005Configuration, interval: (f64, f64)) -> Vec<ScsCurve> {
+    let points: Vec<CrossingPoint> = lin_space(interval.0..=interval.1, conf.curve_steps)
+        .map(|w| CrossingPoint::new(conf, w))
+        .collect();
+
+    /* Lowest branch indices yielding positive delays anywhere on the interval */
+    let u_min = points
+        .iter()
+        .flat_map(|point| [point.a1_angle + point.theta1, point.a1_angle - point.theta1])
+        .map(get_first_positive_branch)
+        .min()
+        .unwrap_or(0);
+    let v_min = points
+        .iter()
+        .flat_map(|point| [point.a2_angle - point.theta2, point.a2_angle + point.theta2])
+        .map(get_first_positive_branch)
+        .min()
+        .unwrap_or(0);
+
+    let mut curves = Vec::new();
+    for i in 0..conf.draw_depth {
+        for k in 0..conf.draw_depth {
+            let (plus, minus): (Vec<Par>, Vec<Par>) = points
+                .iter()
+                .map(|point| point.get_delays(u_min + i, v_min + k))
+                .unzip();
+            curves.extend(filter_curve(&plus));
+            curves.extend(filter_curve(&minus));
+        }
+    }
+
+    curves
+}
+
+
+/* Stability crossing curves in the (tau1, tau2) plane */
+pub fn get_scs_curves(conf: &Gu2005Configuration) -> Vec<ScsCurve> {
+    let intervals = get_omega(conf);
+    debug!("Crossing frequency intervals: {:?}", intervals);
+
+    intervals
+        .iter()
+        .flat_map(|interval| get_interval_curves(conf, *interval))
+        .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comparison::GU2005_CONFIGURATIONS;
+
+    /* Reference values calculated by get_OMEGA and get_SCS_curves of the original Python
+     * implementation (python/utils/comparison_methods/gu2005.py) for a1=2/s, a2=1/s^2 */
+    const PYTHON_OMEGA: (f64, f64) = (0.41430414313728736, 2.4141241413172714);
+    const PYTHON_CURVE_COUNT: usize = 190;
+    const PYTHON_CURVES: [(usize, Par, Par); 6] = [
+        (964, (0.009741908143547468, 2.7483577659055674), (0.6489131016426858, 0.008479651837775236)),
+        (1000, (11.229318081099295, 15.045585420203258), (0.6524253837409105, 2.5941973189294174)),
+        (964, (0.009741908143547468, 15.666894708008186), (0.6489131016426858, 2.611156622604968)),
+        (1000, (11.229318081099295, 30.211220162836124), (0.6524253837409105, 5.196874289696611)),
+        (964, (0.009741908143547468, 28.585431650110802), (0.6489131016426858, 5.2138335933721605)),
+        (1000, (11.229318081099295, 45.376854905468996), (0.6524253837409105, 7.7995512604638035)),
+    ];
+
+    fn assert_pars_eq(x: Par, y: Par) {
+        assert!((x.0 - y.0).abs() < 1e-9 && (x.1 - y.1).abs() < 1e-9, "{:?} != {:?}", x, y);
+    }
+
+    #[test]
+    fn test_retarded1_omega() {
+        let conf = &GU2005_CONFIGURATIONS["retarded1"];
+        let omega = get_omega(conf);
+        assert_eq!(omega.len(), 1);
+        assert_pars_eq(omega[0], PYTHON_OMEGA);
+
+        /* Triangle inequalities for |a1|=2/w and |a2|=1/w^2 hold for w in [sqrt(2)-1, sqrt(2)+1] */
+        let step = (conf.w_max - conf.w_min) / (conf.omega_steps - 1) as f64;
+        assert!(omega[0].0 >= f64::sqrt(2.0) - 1.0 && omega[0].0 - step < f64::sqrt(2.0) - 1.0);
+        assert!(omega[0].1 <= f64::sqrt(2.0) + 1.0 && omega[0].1 + step > f64::sqrt(2.0) + 1.0);
+    }
+
+    #[test]
+    fn test_retarded1_scs_curves() {
+        let curves = get_scs_curves(&GU2005_CONFIGURATIONS["retarded1"]);
+        assert_eq!(curves.len(), PYTHON_CURVE_COUNT);
+
+        for (curve, (length, first, last)) in curves.iter().zip(PYTHON_CURVES) {
+            assert_eq!(curve.len(), length);
+            assert_pars_eq(curve[0], first);
+            assert_pars_eq(curve[curve.len() - 1], last);
+        }
+    }
+}
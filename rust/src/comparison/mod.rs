@@ -0,0 +1,30 @@
+mod configurations;
+pub mod gu2005;
+
+use log::info;
+
+use crate::Args;
+use crate::utils::storage;
+pub use configurations::{Gu2005Configuration, GU2005_CONFIGURATIONS};
+
+
+#[derive(Debug, serde::Serialize)]
+pub struct Gu2005Result {
+    curves: Vec<gu2005::ScsCurve>,
+    parameters: (&'static str, &'static str),
+}
+
+
+pub fn run_gu2005(args: &Args) {
+    let config_name = args.configuration
+        .as_ref()
+        .expect("gu2005 requires configuration to be specified");
+    let config = GU2005_CONFIGURATIONS.get(config_name.as_str()).expect("Unknown configuration");
+
+    let curves = gu2005::get_scs_curves(config);
+    info!("Calculated {} stability crossing curves", curves.len());
+
+    let results = Gu2005Result { curves, parameters: config.system.parameters };
+    let filename = storage::get_filepath("comparison", "gu2005", "scs", config_name);
+    storage::store_results(results, &filename);
+}
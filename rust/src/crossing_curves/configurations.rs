@@ -0,0 +1,114 @@
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+
+use crate::systems::distributed_delay1;
+use crate::systems::dopid3;
+use crate::systems::ln_system1;
+use crate::systems::retarded1;
+use crate::systems::retarded2;
+use crate::systems::test_system;
+use crate::types::{Limits, System};
+
+
+/* Configurations share names with region configurations, so that the curves can be drawn
+ * over the corresponding regions */
+pub struct CrossingCurvesConfiguration {
+    pub name: &'static str,
+    pub system: System,
+    pub limits: Limits,                      // Parametric domain in which curves are traced
+    pub w_min: f64,                          // Lowest crossing frequency of interest
+    pub w_max: f64,                          // Highest crossing frequency of interest
+    pub w_steps: usize,                      // Frequency granularity of the search for seeds
+    pub seed_grid_step: usize,               // Parametric granularity of the search for seeds
+    pub step: f64,                           // Continuation step, relative to the domain size
+    pub tolerance: f64,                      // Corrector termination criterion
+    pub max_points: usize,                   // Maximal number of points per curve
+}
+
+
+/* Global collection of all crossing curves configurations */
+lazy_static! {
+    pub static ref CONFIGURATIONS: HashMap<&'static str, CrossingCurvesConfiguration> = {
+        let mut configs = HashMap::new();
+
+        configs.insert("retarded1", CrossingCurvesConfiguration {
+            name: "retarded1",
+            system: retarded1::SYSTEM,
+            limits: Limits { p1_min: 0.0, p1_max: 2.6, p2_min: 0.0, p2_max: 3.3 },
+            w_min: 1e-3,
+            w_max: 10.0,
+            w_steps: 1_000,
+            seed_grid_step: 20,
+            step: 5e-3,
+            tolerance: 1e-10,
+            max_points: 10_000,
+        });
+
+        configs.insert("retarded2", CrossingCurvesConfiguration {
+            name: "retarded2",
+            system: retarded2::SYSTEM,
+            limits: Limits { p1_min: 0.0, p1_max: 5.0, p2_min: 0.0, p2_max: 5.0 },
+            w_min: 1e-3,
+            w_max: 10.0,
+            w_steps: 1_000,
+            seed_grid_step: 20,
+            step: 5e-3,
+            tolerance: 1e-10,
+            max_points: 10_000,
+        });
+
+        configs.insert("distributed_delay1", CrossingCurvesConfiguration {
+            name: "distributed_delay1",
+            system: distributed_delay1::SYSTEM,
+            limits: Limits { p1_min: 0.0, p1_max: 20.5, p2_min: 0.0, p2_max: 0.5 },
+            w_min: 1e-3,
+            w_max: 10.0,
+            w_steps: 1_000,
+            seed_grid_step: 20,
+            step: 5e-3,
+            tolerance: 1e-10,
+            max_points: 10_000,
+        });
+
+        configs.insert("ln_system1", CrossingCurvesConfiguration {
+            name: "ln_system1",
+            system: ln_system1::SYSTEM,
+            limits: Limits { p1_min: 0.0, p1_max: 20.0, p2_min: 0.0, p2_max: 20.0 },
+            w_min: 1e-3,
+            w_max: 10.0,
+            w_steps: 1_000,
+            seed_grid_step: 20,
+            step: 5e-3,
+            tolerance: 1e-10,
+            max_points: 10_000,
+        });
+
+        configs.insert("dopid3", CrossingCurvesConfiguration {
+            name: "dopid3",
+            system: dopid3::SYSTEM,
+            limits: Limits { p1_min: 0.0, p1_max: 10.0, p2_min: 0.0, p2_max: 10.0 },
+            w_min: 1e-3,
+            w_max: 10.0,
+            w_steps: 1_000,
+            seed_grid_step: 20,
+            step: 5e-3,
+            tolerance: 1e-10,
+            max_points: 10_000,
+        });
+
+        configs.insert("test_configuration", CrossingCurvesConfiguration {
+            name: "test_configuration",
+            system: test_system::SYSTEM,
+            limits: Limits { p1_min: 0.0, p1_max: 1.0, p2_min: 0.0, p2_max: 1.0 },
+            w_min: 1e-3,
+            w_max: 5.0,
+            w_steps: 1_000,
+            seed_grid_step: 20,
+            step: 5e-3,
+            tolerance: 1e-10,
+            max_points: 10_000,
+        });
+
+        configs
+    };
+}
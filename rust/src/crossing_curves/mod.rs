@@ -0,0 +1,483 @@
+mod configurations;
+
+use log::{debug, info, warn};
+use cgmath::{InnerSpace, Matrix, Matrix2, Matrix3, SquareMatrix, Vector2, Vector3};
+use iter_num_tools::lin_space;
+use rayon::prelude::*;
+
+use crate::Args;
+use crate::types::{Comp, Limits, Par};
+use crate::utils::storage;
+pub use configurations::{CrossingCurvesConfiguration, CONFIGURATIONS};
+
+
+#[derive(Debug, serde::Serialize)]
+pub struct CrossingPoint {
+    p: Par,
+    w: f64,
+}
+
+
+#[derive(Debug, serde::Serialize)]
+pub struct CrossingCurve {
+    points: Vec<CrossingPoint>,
+    closed: bool,
+}
+
+
+#[derive(Debug, serde::Serialize)]
+pub struct CrossingCurvesResult {
+    curves: Vec<CrossingCurve>,
+    limits: &'static Limits,
+    parameters: (&'static str, &'static str),
+}
+
+
+const DERIVATIVE_STEP: f64 = 1e-7;
+const NEWTON_MAX_ITERATIONS: u32 = 50;
+const CORRECTOR_MAX_ITERATIONS: u32 = 10;
+
+/* Continuation gives up once its step shrinks below this fraction of the configured step */
+const MIN_STEP_RATIO: f64 = 1e-4;
+
+/* Minimal number of points before a curve may be considered closed */
+const MIN_LOOP_POINTS: usize = 10;
+
+/* Seeds closer than this many steps to an already traced curve are considered traced */
+const DUPLICATE_STEPS: f64 = 3.0;
+
+
+/* Relative size of the imaginary part of f(0, p) below which f(0, p) is considered real */
+const REAL_TOLERANCE: f64 = 1e-12;
+
+
+/* Roots may cross the imaginary axis in conjugate pairs, or as a single real root at s=0 */
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Crossing {
+    Oscillatory,
+    Real,
+}
+
+
+/* Characteristic function on the imaginary axis, in coordinates (w, p1, p2) normalized so
+ * that the domain of interest is the unit cube */
+struct CrossingFunction<'a> {
+    conf: &'a CrossingCurvesConfiguration,
+    crossing: Crossing,
+}
+
+
+impl<'a> CrossingFunction<'a> {
+    fn denormalize(&self, x: Vector3<f64>) -> (f64, Par) {
+        let limits = &self.conf.limits;
+        let w = self.conf.w_min + x.x * (self.conf.w_max - self.conf.w_min);
+        let p1 = limits.p1_min + x.y * (limits.p1_max - limits.p1_min);
+        let p2 = limits.p2_min + x.z * (limits.p2_max - limits.p2_min);
+        (w, (p1, p2))
+    }
+
+    fn normalize(&self, w: f64, p: Par) -> Vector3<f64> {
+        let limits = &self.conf.limits;
+        Vector3::new(
+            (w - self.conf.w_min) / (self.conf.w_max - self.conf.w_min),
+            (p.0 - limits.p1_min) / (limits.p1_max - limits.p1_min),
+            (p.1 - limits.p2_min) / (limits.p2_max - limits.p2_min),
+        )
+    }
+
+    fn value(&self, x: Vector3<f64>) -> Comp {
+        let (w, p) = self.denormalize(x);
+
+        match self.crossing {
+            Crossing::Oscillatory => (self.conf.system.f_complex)(Comp::new(0.0, w), p),
+            /* f(0, p) is real, so the spare coordinate is pinned to zero by the imaginary part */
+            Crossing::Real => Comp::new((self.conf.system.f_complex)(Comp::new(0.0, 0.0), p).re, x.x),
+        }
+    }
+
+    fn frequency(&self, x: Vector3<f64>) -> f64 {
+        match self.crossing {
+            Crossing::Oscillatory => self.denormalize(x).0,
+            Crossing::Real => 0.0,
+        }
+    }
+
+    /* Gradients of the real and imaginary part of the function */
+    fn gradients(&self, x: Vector3<f64>) -> (Vector3<f64>, Vector3<f64>) {
+        let axes = [Vector3::unit_x(), Vector3::unit_y(), Vector3::unit_z()];
+        let partial = |axis: Vector3<f64>| {
+            let h = DERIVATIVE_STEP * axis;
+            (self.value(x + h) - self.value(x - h)) / (2.0 * DERIVATIVE_STEP)
+        };
+        let partials: Vec<Comp> = axes.iter().map(|axis| partial(*axis)).collect();
+
+        (
+            Vector3::new(partials[0].re, partials[1].re, partials[2].re),
+            Vector3::new(partials[0].im, partials[1].im, partials[2].im),
+        )
+    }
+
+    /* Unit direction of the curve, i.e. the null vector of the Jacobian */
+    fn tangent(&self, x: Vector3<f64>) -> Option<Vector3<f64>> {
+        let (grad_re, grad_im) = self.gradients(x);
+        let tangent = grad_re.cross(grad_im);
+
+        match tangent.magnitude() > 0.0 {
+            true => Some(tangent.normalize()),
+            false => Option::None,
+        }
+    }
+
+    fn in_domain(&self, x: Vector3<f64>) -> bool {
+        let parameters_inside = [x.y, x.z].iter().all(|c| (0.0..=1.0).contains(c));
+
+        match self.crossing {
+            Crossing::Oscillatory => parameters_inside && (0.0..=1.0).contains(&x.x),
+            Crossing::Real => parameters_inside,
+        }
+    }
+}
+
+
+/* Solve f = 0 for the two coordinates other than the fixed one */
+fn solve_on_line(f: &CrossingFunction, x0: Vector3<f64>, fixed_axis: usize, tolerance: f64)
+    -> Option<Vector3<f64>>
+{
+    let free_axes: Vec<usize> = (0..3).filter(|axis| *axis != fixed_axis).collect();
+    let mut x = x0;
+
+    for _ in 0..NEWTON_MAX_ITERATIONS {
+        let value = f.value(x);
+        let (grad_re, grad_im) = f.gradients(x);
+        let jacobian = Matrix2::new(
+            grad_re[free_axes[0]], grad_im[free_axes[0]],
+            grad_re[free_axes[1]], grad_im[free_axes[1]],
+        );
+        let step = jacobian.invert()? * Vector2::new(-value.re, -value.im);
+
+        if !step.magnitude().is_finite() {
+            return Option::None;
+        }
+        x[free_axes[0]] += step.x;
+        x[free_axes[1]] += step.y;
+
+        if step.magnitude() < tolerance {
+            return Some(x);
+        }
+    }
+
+    Option::None
+}
+
+
+/* Newton iterations restricted to the hyperplane orthogonal to the tangent */
+fn correct(f: &CrossingFunction, prediction: Vector3<f64>, tangent: Vector3<f64>, tolerance: f64)
+    -> Option<Vector3<f64>>
+{
+    let mut x = prediction;
+
+    for _ in 0..CORRECTOR_MAX_ITERATIONS {
+        let value = f.value(x);
+        let (grad_re, grad_im) = f.gradients(x);
+        let jacobian = Matrix3::from_cols(grad_re, grad_im, tangent).transpose();
+        let residual = Vector3::new(value.re, value.im, tangent.dot(x - prediction));
+        let step = jacobian.invert()? * -residual;
+
+        if !step.magnitude().is_finite() {
+            return Option::None;
+        }
+        x += step;
+
+        if step.magnitude() < tolerance {
+            return Some(x);
+        }
+    }
+
+    Option::None
+}
+
+
+/* Follow the curve from the seed in one direction, until it leaves the domain or closes */
+fn trace_branch(f: &CrossingFunction, seed: Vector3<f64>, direction: f64) -> (Vec<Vector3<f64>>, bool) {
+    let conf = f.conf;
+    let mut points = Vec::new();
+    let mut x = seed;
+    let mut step = conf.step;
+    let mut tangent = match f.tangent(seed) {
+        Some(tangent) => direction * tangent,
+        None => return (points, false),
+    };
+
+    while points.len() < conf.max_points {
+        let corrected = correct(f, x + step * tangent, tangent, conf.tolerance)
+            .filter(|x_new| (x_new - x).magnitude() < 2.0 * step);
+
+        let x_new = match corrected {
+            Some(x_new) => x_new,
+            None => {
+                /* The corrector diverged or jumped onto another branch; retry with a shorter step */
+                step /= 2.0;
+                if step < MIN_STEP_RATIO * conf.step {
+                    warn!("Continuation stalled at {:?}", f.denormalize(x));
+                    break;
+                }
+                continue;
+            },
+        };
+
+        let tangent_new = match f.tangent(x_new) {
+            Some(tangent_new) => tangent_new,
+            None => break,
+        };
+        tangent = if tangent_new.dot(tangent) < 0.0 { -tangent_new } else { tangent_new };
+        x = x_new;
+        points.push(x);
+        step = f64::min(2.0 * step, conf.step);
+
+        if !f.in_domain(x) {
+            return (points, false);
+        }
+        if points.len() > MIN_LOOP_POINTS && (x - seed).magnitude() < conf.step {
+            points.push(seed);
+            return (points, true);
+        }
+    }
+
+    (points, false)
+}
+
+
+fn trace_curve(f: &CrossingFunction, seed: Vector3<f64>) -> CrossingCurve {
+    let (forward, closed) = trace_branch(f, seed, 1.0);
+
+    let mut points: Vec<Vector3<f64>> = Vec::new();
+    if !closed {
+        let (backward, _) = trace_branch(f, seed, -1.0);
+        points.extend(backward.iter().rev());
+    }
+    points.push(seed);
+    points.extend(forward);
+
+    let points = points
+        .iter()
+        .map(|x| CrossingPoint { p: f.denormalize(*x).1, w: f.frequency(*x) })
+        .collect();
+
+    CrossingCurve { points, closed }
+}
+
+
+/* Points of oscillatory crossing curves near the seed grid; the frequency is swept once per grid
+ * point, and each local minimum of |f| is solved for with either parameter fixed */
+fn find_oscillatory_seeds(f: &CrossingFunction) -> Vec<Vector3<f64>> {
+    let conf = f.conf;
+    let grid: Vec<f64> = lin_space(0.0..=1.0, conf.seed_grid_step).collect();
+    let w_grid: Vec<f64> = lin_space(0.0..=1.0, conf.w_steps).collect();
+    let mut grid_points: Vec<(f64, f64)> = Vec::new();
+    for p1 in grid.iter() {
+        grid_points.extend(grid.iter().map(|p2| (*p1, *p2)));
+    }
+
+    grid_points
+        .par_iter()
+        .flat_map_iter(|(p1, p2)| {
+            /* Sweep the frequency with both parameters fixed */
+            let point = |w: f64| Vector3::new(w, *p1, *p2);
+            let norms: Vec<f64> = w_grid.iter().map(|w| f.value(point(*w)).norm()).collect();
+
+            (1..norms.len()-1)
+                .filter(|i| norms[*i] <= norms[i-1] && norms[*i] <= norms[i+1])
+                .flat_map(|i| [1, 2].map(|fixed_axis| solve_on_line(f, point(w_grid[i]), fixed_axis, conf.tolerance)))
+                                .flatten()
+                .filter(|x| f.in_domain(*x))
+                .collect::<Vec<Vector3<f64>>>()
+        })
+        .collect()
+}
+
+
+/* Points of real crossing curves found on lines of the seed grid where one parameter is fixed,
+ * starting from sign changes of f(0, p) along the other parameter */
+fn find_real_seeds(f: &CrossingFunction) -> Vec<Vector3<f64>> {
+    let conf = f.conf;
+    let grid: Vec<f64> = lin_space(0.0..=1.0, conf.seed_grid_step).collect();
+    let sweep: Vec<f64> = lin_space(0.0..=1.0, conf.w_steps).collect();
+    let mut lines: Vec<(usize, f64)> = Vec::new();
+    for fixed_axis in [1, 2] {
+        lines.extend(grid.iter().map(|fixed| (fixed_axis, *fixed)));
+    }
+
+    lines
+        .par_iter()
+        .flat_map_iter(|(fixed_axis, fixed)| {
+            let point = |other: f64| match fixed_axis {
+                1 => Vector3::new(0.0, *fixed, other),
+                _ => Vector3::new(0.0, other, *fixed),
+            };
+            let values: Vec<f64> = sweep.iter().map(|other| f.value(point(*other)).re).collect();
+
+            (1..values.len())
+                .filter(|i| values[i-1] * values[*i] <= 0.0)
+                .filter_map(|i| solve_on_line(f, point(sweep[i]), *fixed_axis, conf.tolerance))
+                .filter(|x| f.in_domain(*x))
+                .collect::<Vec<Vector3<f64>>>()
+        })
+        .collect()
+}
+
+
+/* Real crossings are traced only if f(0, p) is finite and real throughout the seed grid */
+fn has_real_crossings(conf: &CrossingCurvesConfiguration) -> bool {
+    let f = CrossingFunction { conf, crossing: Crossing::Real };
+    let grid: Vec<f64> = lin_space(0.0..=1.0, conf.seed_grid_step).collect();
+
+    grid.iter().all(|p1| grid.iter().all(|p2| {
+        let (_, p) = f.denormalize(Vector3::new(0.0, *p1, *p2));
+        let value = (conf.system.f_complex)(Comp::new(0.0, 0.0), p);
+        value.is_finite() && value.im.abs() <= REAL_TOLERANCE * (1.0 + value.re.abs())
+    }))
+}
+
+
+fn trace_curves(f: &CrossingFunction) -> Vec<CrossingCurve> {
+    let seeds = match f.crossing {
+        Crossing::Oscillatory => find_oscillatory_seeds(f),
+        Crossing::Real => find_real_seeds(f),
+    };
+    info!("Found {} seeds for {:?} crossing curves", seeds.len(), f.crossing);
+
+    let mut curves: Vec<CrossingCurve> = Vec::new();
+    let duplicate_distance = DUPLICATE_STEPS * f.conf.step;
+
+    for seed in seeds {
+        let traced = curves.iter().flat_map(|curve| curve.points.iter()).any(|point| {
+            let mut x = f.normalize(point.w, point.p);
+            if f.crossing == Crossing::Real {
+                x.x = 0.0;
+            }
+            (x - seed).magnitude() < duplicate_distance
+        });
+        if traced {
+            continue;
+        }
+
+        let curve = trace_curve(f, seed);
+        debug!("Traced crossing curve with {} points from seed {:?}", curve.points.len(), f.denormalize(seed));
+        curves.push(curve);
+    }
+    info!("Traced {} {:?} crossing curves", curves.len(), f.crossing);
+
+    curves
+}
+
+
+fn calculate_crossing_curves(conf: &'static CrossingCurvesConfiguration) -> CrossingCurvesResult {
+    let mut curves = trace_curves(&CrossingFunction { conf, crossing: Crossing::Oscillatory });
+
+    if has_real_crossings(conf) {
+        curves.extend(trace_curves(&CrossingFunction { conf, crossing: Crossing::Real }));
+    }
+
+    CrossingCurvesResult {
+        curves,
+        limits: &conf.limits,
+        parameters: conf.system.parameters,
+    }
+}
+
+
+pub fn run(args: &Args) {
+    /* Trace crossing curves */
+    let config_name = args.configuration
+        .as_ref()
+        .expect("crossing-curves requires configuration to be specified");
+    let config = CONFIGURATIONS.get(config_name.as_str()).expect("Unknown configuration");
+    let results = calculate_crossing_curves(config);
+
+    /* Store results next to the region results */
+    let filename = storage::get_filepath("data", "region", "curves", config_name);
+    storage::store_results(results, &filename);
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::test_system;
+
+    /* On the imaginary axis, test_system crosses for a=0, b=1+w^2 and at s=0 for b=1-a^2. The domain
+     * is centered at a=0, where f is real for fixed a, so the seeds are found only with b fixed */
+    fn get_test_configuration() -> &'static CrossingCurvesConfiguration {
+        Box::leak(Box::new(CrossingCurvesConfiguration {
+            name: "test_configuration",
+            system: test_system::SYSTEM,
+            limits: Limits { p1_min: -0.5, p1_max: 0.5, p2_min: 0.0, p2_max: 2.0 },
+            w_min: 1e-3,
+            w_max: 5.0,
+            w_steps: 1_000,
+            seed_grid_step: 21,
+            step: 5e-3,
+            tolerance: 1e-10,
+            max_points: 10_000,
+        }))
+    }
+
+    fn assert_on_crossing_set(p: Par, w: f64, crossing: Crossing) {
+        match crossing {
+            Crossing::Oscillatory => assert!(p.0.abs() < 1e-6 && (p.1 - (1.0 + w.powi(2))).abs() < 1e-6,
+                "Oscillatory crossing ({:?}, {}) off the crossing set", p, w),
+            Crossing::Real => assert!((p.1 - (1.0 - p.0.powi(2))).abs() < 1e-6,
+                "Real crossing {:?} off the crossing set", p),
+        }
+    }
+
+    #[test]
+    fn test_find_seeds() {
+        let conf = get_test_configuration();
+
+        let f = CrossingFunction { conf, crossing: Crossing::Oscillatory };
+        let seeds = find_oscillatory_seeds(&f);
+        assert!(!seeds.is_empty());
+        for seed in seeds {
+            let (w, p) = f.denormalize(seed);
+            assert_on_crossing_set(p, w, Crossing::Oscillatory);
+        }
+
+        let f = CrossingFunction { conf, crossing: Crossing::Real };
+        let seeds = find_real_seeds(&f);
+        assert!(!seeds.is_empty());
+        for seed in seeds {
+            assert_on_crossing_set(f.denormalize(seed).1, f.frequency(seed), Crossing::Real);
+        }
+    }
+
+    #[test]
+    fn test_calculate_crossing_curves() {
+        let conf = get_test_configuration();
+        let result = calculate_crossing_curves(conf);
+        let is_real = |curve: &CrossingCurve| curve.points.iter().all(|point| point.w == 0.0);
+        assert_eq!(result.curves.len(), 2);
+
+        /* The last points of curves leaving the domain lie past its edges, yet still on the set */
+        for curve in result.curves.iter() {
+            let crossing = match is_real(curve) {
+                true => Crossing::Real,
+                false => Crossing::Oscillatory,
+            };
+            for point in curve.points.iter() {
+                assert_on_crossing_set(point.p, point.w, crossing);
+            }
+        }
+
+        /* Both crossing curves span the domain from edge to edge */
+        let get_span = |values: Vec<f64>| values
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| (min.min(*value), max.max(*value)));
+        let oscillatory = result.curves.iter().find(|curve| !is_real(curve)).unwrap();
+        let (b_min, b_max) = get_span(oscillatory.points.iter().map(|point| point.p.1).collect());
+        assert!(b_min <= 1.0 + 1e-2 && b_max >= 2.0);
+        let real = result.curves.iter().find(|curve| is_real(curve)).unwrap();
+        let (a_min, a_max) = get_span(real.points.iter().map(|point| point.p.0).collect());
+        assert!(a_min <= -0.5 && a_max >= 0.5);
+    }
+}
@@ -15,7 +15,8 @@ use crate::systems::test_system;
 use crate::systems::ln_system1;
 
 use crate::utils::geometry::Delta;
-use crate::nu::ContourConfiguration;
+use crate::data::origins::Origins;
+use crate::nu::{ContourConfiguration, StabilityBoundary};
 use crate::types::{Limits, Par, System};
 
 
@@ -23,7 +24,7 @@ pub struct LineConfiguration {
     pub name: &'static str,                  // Config name
     pub system: System,                      // System to be used
     pub limits: Limits,                      // Parametric search domain
-    pub origins: Vec<Par>,                   // Origins upon which to run fsals
+    pub origins: Origins,                    // Origins upon which to run fsals
     pub ray_count: usize,                    // Number of rays emanating from each origin
     pub contour_conf: ContourConfiguration,  // Contour used for evaluating NU
     pub delta: Delta,                        // Termination criteria
@@ -32,6 +33,7 @@ pub struct LineConfiguration {
     pub log_space_minw: f64,                 // Lower w for logarithmic minimization
     pub log_space_maxw: f64,                 // Upper w for logarithmic minimization
     pub log_space_steps: usize,              // Domain granularity for logarithmic minimization
+    pub certified_minimum: bool,             // Use a guaranteed lower bound of the minimum
     pub corrective_ratio: Option<f64>,       // Correction for achieving 1:1 perceived aspect ratio
 }
 
@@ -55,23 +57,75 @@ lazy_static! {
             ray_count: 320,
             safeguard: 0.95,
             corrective_ratio: Option::None,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 (1e-2, 1e-2),
                 (1.75, 1.20),
                 (0.88, 2.73),
                 (0.20, 3.10),
                 (0.71, 3.22),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e5,
                 steps: 10_000usize,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(1e-3),
             w_steps_linear: 1_000,
             log_space_minw: 1e-3,
             log_space_maxw: 1e5,
             log_space_steps: 1000,
+            certified_minimum: false,
+        });
+
+        configs.insert("retarded1_certified", LineConfiguration {
+            name: "retarded1_certified",
+            system: retarded1::SYSTEM,
+            limits: Limits { p1_min: 0.0, p1_max: 2.6, p2_min: 0.0, p2_max: 3.3 },
+            ray_count: 320,
+            safeguard: 0.95,
+            corrective_ratio: Option::None,
+            origins: Origins::Manual(vec![
+                (1.75, 1.20),
+                (0.88, 2.73),
+                ]),
+            contour_conf: ContourConfiguration {
+                indentation_radius: 1e-3,
+                singular_points: vec![],
+                w_max: 1e5,
+                steps: 10_000usize,
+                boundary: StabilityBoundary::ImaginaryAxis,
+                },
+            delta: Delta::Abs(1e-3),
+            w_steps_linear: 1_000,
+            log_space_minw: 1e-3,
+            log_space_maxw: 1e5,
+            log_space_steps: 1000,
+            certified_minimum: true,
+        });
+
+        configs.insert("retarded1_auto", LineConfiguration {
+            name: "retarded1_auto",
+            system: retarded1::SYSTEM,
+            limits: Limits { p1_min: 0.0, p1_max: 2.6, p2_min: 0.0, p2_max: 3.3 },
+            ray_count: 320,
+            safeguard: 0.95,
+            corrective_ratio: Option::None,
+            origins: Origins::Auto(41),
+            contour_conf: ContourConfiguration {
+                indentation_radius: 1e-3,
+                singular_points: vec![],
+                w_max: 1e5,
+                steps: 10_000usize,
+                boundary: StabilityBoundary::ImaginaryAxis,
+                },
+            delta: Delta::Abs(1e-3),
+            w_steps_linear: 1_000,
+            log_space_minw: 1e-3,
+            log_space_maxw: 1e5,
+            log_space_steps: 1000,
+            certified_minimum: false,
         });
 
         configs.insert("distributed_delay1", LineConfiguration {
@@ -81,7 +135,7 @@ lazy_static! {
             ray_count: 160,
             safeguard: 0.95,
             corrective_ratio: Option::Some(5.0),
-            origins: vec![
+            origins: Origins::Manual(vec![
                 // Region 1
                 (1.0, 0.4),
                 (10.0, 0.4),
@@ -96,17 +150,20 @@ lazy_static! {
                 (18.0, 0.018),
                 (19.9, 0.047),
                 (16.19, 0.007),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e5,
                 steps: 1_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(1e-4),
             w_steps_linear: 1_000,
             log_space_minw: 1e-3,
             log_space_maxw: 1e5,
             log_space_steps: 1000,
+            certified_minimum: false,
         });
 
         configs.insert("semi_infinite_rod", LineConfiguration {
@@ -116,7 +173,7 @@ lazy_static! {
             ray_count: 160,
             safeguard: 0.80,
             corrective_ratio: Option::None,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 // Region 1
                 (1.0, 0.5e4),
                 (50.0, 0.5e4),
@@ -126,17 +183,20 @@ lazy_static! {
                 (50.0, 4e4),
                 (20.0, 6e4),
                 (90.0, 3e4),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e5,
                 steps: 1_000usize,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(0.8),
             w_steps_linear: 5_000,
             log_space_minw: 1e-3,
             log_space_maxw: 1e5,
             log_space_steps: 1000,
+            certified_minimum: false,
         });
 
         configs.insert("pde_complex_k_sigma", LineConfiguration {
@@ -146,7 +206,7 @@ lazy_static! {
             ray_count: 160,
             safeguard: 0.90,
             corrective_ratio: Option::None,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 // Region 1
                 (0.5, 0.5),
                 (4.0, 4.0),
@@ -156,17 +216,20 @@ lazy_static! {
                 (6.0, 6.0),
                 (16.0, 10.0),
                 (10.0, 16.0),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e5,
                 steps: 1_000usize,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(1e-3),
             w_steps_linear: 1000,
             log_space_minw: 1e-2,
             log_space_maxw: 1e2,
             log_space_steps: 1000,
+            certified_minimum: false,
         });
 
         configs.insert("pde_complex_beta_sigma", LineConfiguration {
@@ -176,7 +239,7 @@ lazy_static! {
             ray_count: 160,
             safeguard: 0.90,
             corrective_ratio: Option::None,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 // Region 1
                 // (1.00, 1.00),
                 (0.50, 1.50),
@@ -184,17 +247,20 @@ lazy_static! {
                 // Region 2
                 (1.80, 1.50),
                 (1.80, 0.30)
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e5,
                 steps: 1_000usize,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(1e-3),
             w_steps_linear: 1_000,
             log_space_minw: 1e-3,
             log_space_maxw: 1e5,
             log_space_steps: 1_000,
+            certified_minimum: false,
         });
 
         configs.insert("pde_complex_tau_sigma", LineConfiguration {
@@ -204,7 +270,7 @@ lazy_static! {
             ray_count: 160,
             safeguard: 0.90,
             corrective_ratio: Option::None,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 // Region 1
                 ( 1.0,  1.0),
                 ( 0.5, 12.0),
@@ -214,17 +280,20 @@ lazy_static! {
                 (18.0,  2.0),
                 // Region 4
                 (19.0,  0.15),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e5,
                 steps: 1_000usize,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(1e-2),
             w_steps_linear: 1_000,
             log_space_minw: 1e-3,
             log_space_maxw: 1e5,
             log_space_steps: 1_000,
+            certified_minimum: false,
         });
 
         configs.insert("pde_complex_tau_sigma_instructional", LineConfiguration {
@@ -234,7 +303,7 @@ lazy_static! {
             ray_count: 256,
             safeguard: 0.90,
             corrective_ratio: Option::None,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 // Region 2
                 (10.0, 10.0),
                 // Region 1
@@ -244,17 +313,20 @@ lazy_static! {
                 (18.0,  2.0),
                 // Region 4
                 (19.0,  0.15),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e5,
                 steps: 1_000usize,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(1e-2),
             w_steps_linear: 1_000,
             log_space_minw: 1e-3,
             log_space_maxw: 1e5,
             log_space_steps: 1_000,
+            certified_minimum: false,
         });
 
         configs.insert("telegrapher_x_k", LineConfiguration {
@@ -264,7 +336,7 @@ lazy_static! {
             ray_count: 160,
             safeguard: 0.90,
             corrective_ratio: Option::None,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 // Region 1
                 (0.4, 7.0),
                 (0.2, 5.0),
@@ -272,17 +344,20 @@ lazy_static! {
                 (3.0, 7.0),
                 (1.5, 5.0),
                 (3.5, 5.7),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e5,
                 steps: 1_000usize,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(1e-4),
             w_steps_linear: 1_000,
             log_space_minw: 1e-3,
             log_space_maxw: 1e5,
             log_space_steps: 1_000,
+            certified_minimum: false,
         });
 
         configs.insert("telegrapher_alpha_gamma", LineConfiguration {
@@ -292,7 +367,7 @@ lazy_static! {
             ray_count: 160,
             safeguard: 0.90,
             corrective_ratio: Option::None,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 // Region 1
                 (0.40, 0.40),
                 // Region 2
@@ -301,16 +376,19 @@ lazy_static! {
                 (0.86, 0.86),
                 // Region 4
                 (0.89, 0.89),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e5,
                 steps: 1_000usize,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(1e-4),
             log_space_minw: 1e-3,
             log_space_maxw: 1e7,
             log_space_steps: 5000,
+            certified_minimum: false,
             w_steps_linear: 5000,
         });
 
@@ -321,23 +399,26 @@ lazy_static! {
             ray_count: 160,
             safeguard: 0.80,
             corrective_ratio: Option::None,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 // Region 1
                 (17.0, 22_000.0),
                 (80.0,  5_000.0),
                 // Region 2
                 (40.0, 55_000.0),
                 (80.0, 30_000.0),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e3,
                 steps: 1_000usize,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(1.0),
             log_space_minw: 1e-2,
             log_space_maxw: 1e2,
             log_space_steps: 10_000,
+            certified_minimum: false,
             w_steps_linear: 10_000,
         });
 
@@ -348,7 +429,7 @@ lazy_static! {
             ray_count: 160,
             corrective_ratio: Option::None,
             safeguard: 0.85,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 // Region 1
                 (0.05, 1.3),
                 (0.15, 1.15),
@@ -357,16 +438,19 @@ lazy_static! {
                 (0.16, 1.38),
                 // Region 3
                 (0.175, 1.48),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e3,
                 steps: 1_000usize,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(1e-5),
             log_space_minw: 1e1,
             log_space_maxw: 1e4,
             log_space_steps: 1_000,
+            certified_minimum: false,
             w_steps_linear: 2_000,
         });
 
@@ -377,7 +461,7 @@ lazy_static! {
             ray_count: 160,
             corrective_ratio: Option::None,
             safeguard: 0.85,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 // Region 1
                 (0.9, 0.9),
                 // Region 2
@@ -386,16 +470,19 @@ lazy_static! {
                 (13.0, 12.0),
                 // Region 4
                 (18.5, 18.0),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e3,
                 steps: 1_000usize,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(1e-3),
             log_space_minw: 1e-4,
             log_space_maxw: 1e3,
             log_space_steps: 10_000,
+            certified_minimum: false,
             w_steps_linear: 10_000,
         });
 
@@ -406,7 +493,7 @@ lazy_static! {
             ray_count: 160,
             corrective_ratio: Option::None,
             safeguard: 0.60,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 // Region 1
                 (0.0, 1.0),
                 // Region 2
@@ -430,16 +517,19 @@ lazy_static! {
                 (18.0, -4.0),
                 // Region 10
                 (-19.8, 1.0),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e3,
                 steps: 1_000usize,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(0.05),
             log_space_minw: 1e-11,
             log_space_maxw: 1e3,
             log_space_steps: 200_000,
+            certified_minimum: false,
             w_steps_linear: 800_000,
         });
 
@@ -450,21 +540,52 @@ lazy_static! {
             ray_count: 160,
             corrective_ratio: Option::None,
             safeguard: 0.90,
-            origins: vec![
+            origins: Origins::Manual(vec![
+                // Region 1
+                (0.1, 0.1),
+                // Region 2
+                (0.9, 0.9),
+                ]),
+            contour_conf: ContourConfiguration {
+                indentation_radius: 1e-3,
+                singular_points: vec![],
+                w_max: 1e3,
+                steps: 1_000usize,
+                boundary: StabilityBoundary::ImaginaryAxis,
+                },
+            delta: Delta::Abs(1e-3),
+            log_space_minw: 1e-3,
+            log_space_maxw: 1e4,
+            log_space_steps: 10_000,
+            certified_minimum: false,
+            w_steps_linear: 10_000,
+        });
+
+        configs.insert("test_configuration_certified", LineConfiguration {
+            name: "test_configuration_certified",
+            system: test_system::SYSTEM,
+            limits: Limits { p1_min: 0.0, p1_max: 1.0, p2_min: 0.0, p2_max: 1.0 },
+            ray_count: 160,
+            corrective_ratio: Option::None,
+            safeguard: 0.90,
+            origins: Origins::Manual(vec![
                 // Region 1
                 (0.1, 0.1),
                 // Region 2
                 (0.9, 0.9),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e3,
                 steps: 1_000usize,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(1e-3),
             log_space_minw: 1e-3,
             log_space_maxw: 1e4,
             log_space_steps: 10_000,
+            certified_minimum: true,
             w_steps_linear: 10_000,
         });
 
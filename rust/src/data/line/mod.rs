@@ -9,15 +9,38 @@ use std::iter::Cloned;
 
 use crate::Args;
 use crate::types::{Comp, Par, System, Limits};
-use crate::nu;
-use crate::utils::optimization::{MinimizationProblemFast, MinimizationProblemSlow};
+use crate::nu::{self, StabilityBoundary};
+use crate::utils::optimization::{
+    BatchFraction, BrentSearch, CertifiedSearch};
 use crate::utils::{storage, geometry, optimization};
 use crate::utils::geometry::Delta;
-use configurations::{LineConfiguration, CONFIGURATONS};
+pub use configurations::{LineConfiguration, CONFIGURATONS};
 use cgmath::Vector2;
 use rayon::prelude::*;
 
 
+/* Lipschitz bound of the line fraction on [w_min, w_max] for the jump [th_min, th_max] */
+type LineLipschitz1D<'a> = dyn Fn(f64, f64, f64, f64) -> f64 + 'a;
+
+/* Lower bounds of the line fraction below w_min and above w_max for the jump [th_min, th_max] */
+type LineTails1D<'a> = dyn Fn(f64, f64, f64, f64) -> (f64, f64) + 'a;
+
+
+/* Optional bounds of the 1d line fraction which make its minimization more rigorous */
+struct LineBounds1D<'a> {
+    lipschitz: Option<&'a LineLipschitz1D<'a>>,
+    tails: Option<&'a LineTails1D<'a>>,
+}
+
+
+/* Frequency search settings shared by all jumps along a single direction */
+struct LineContext1D<'a> {
+    bounds: LineBounds1D<'a>,
+    w_steps_linear: usize,
+    log_space: &'a [f64],
+}
+
+
 #[derive(Serialize)]
 pub struct Ray {
     origin: Par,
@@ -61,33 +84,54 @@ fn spawn_angles(limits: &Limits, count: usize, corrective_ratio: Option<f64>) ->
 }
 
 
+/* The numerator is the magnitude of the characteristic function on the logspace at theta0; it
+ * does not depend on the jump length, so it is shared by all attempted jumps */
 fn check_jump_validity<F1, F2> (
     f: F1,
-    line_denominator: F2,
+    line_fraction: F2,
+    context: &LineContext1D<'_>,
     theta0: f64,
     delta_theta: f64,
-    w_steps_linear: usize,
-    log_space: &[f64],
+    numerator: &[f64],
 ) -> bool
 where
-    F1: Fn(Comp, f64) -> Comp,
-    F2: Fn(f64, f64, f64) -> f64
+    F1: Fn(f64, f64) -> Comp,
+    F2: Fn(&[f64], &[f64], f64, f64, &mut [f64])
 {
     let theta_min = theta0;
     let theta_max = theta0 + delta_theta;
     debug!("Checking jump validity with (th_min, th_max) = ({}, {})", theta_min, theta_max);
-    let numerator = |w: f64| Comp::norm(f(Comp::new(0.0, w), theta0));
-    let denominator = |w: f64| line_denominator(w, theta_min, theta_max);
-    let fraction = |w: f64| numerator(w) / denominator(w);
-    let precalculated_numerator: Vec<f64> = log_space.iter().map(|w| numerator(*w)).collect();
-    let minimization_problem = MinimizationProblemSlow {
-        log_space: log_space,
-        lin_steps: w_steps_linear,
-        precalculated_numerator: &precalculated_numerator,
-        denominator_function: &denominator,
-        fraction_function: &fraction,
+    let batch_fraction = |w: &[f64], out: &mut [f64]| {
+        let numerator: Vec<f64> = w.iter().map(|w| Comp::norm(f(*w, theta0))).collect();
+        line_fraction(&numerator, w, theta_min, theta_max, out)
+    };
+    let logspace_fraction = |w: &[f64], out: &mut [f64]| {
+        line_fraction(numerator, w, theta_min, theta_max, out)
+    };
+    let problem = BatchFraction {
+        log_space: context.log_space,
+        logspace_fraction_function: &logspace_fraction,
+        fraction_function: &batch_fraction,
     };
-    let min = optimization::find_minimum_fraction_slow(&minimization_problem);
+
+    /* Frequencies outside of the logspace are covered by tail bounds, if the system has them */
+    let tails = context.bounds.tails
+        .map(|tails| move |w_min: f64, w_max: f64| tails(w_min, w_max, theta_min, theta_max));
+    let min = match context.bounds.lipschitz {
+        Some(lipschitz) => {
+            let lipschitz = |w_min: f64, w_max: f64| lipschitz(w_min, w_max, theta_min, theta_max);
+            let strategy = CertifiedSearch {
+                lipschitz_function: &lipschitz,
+                threshold: Option::Some(delta_theta),
+            };
+            optimization::minimize_with_tails(&problem, &strategy, tails.as_ref(), delta_theta)
+        },
+        None => {
+            let strategy = BrentSearch { lin_steps: context.w_steps_linear };
+            optimization::minimize_with_tails(&problem, &strategy, tails.as_ref(), delta_theta)
+        },
+    };
+
     let jump_valid = delta_theta < min;
     jump_valid
 }
@@ -95,20 +139,20 @@ where
 
 fn find_max_delta_theta<F1, F2>(
     f: F1,
-    line_denominator: F2,
+    line_fraction: F2,
+    context: &LineContext1D<'_>,
     theta0: f64,
     delta: f64,
     limit: f64,
-    w_steps_linear: usize,
-    log_space: &[f64],
 ) -> f64
 where
-    F1: Fn(Comp, f64) -> Comp,
-    F2: Fn(f64, f64, f64) -> f64
+    F1: Fn(f64, f64) -> Comp,
+    F2: Fn(&[f64], &[f64], f64, f64, &mut [f64])
 {
     let min_step = delta;
+    let numerator: Vec<f64> = context.log_space.iter().map(|w| Comp::norm(f(*w, theta0))).collect();
     let condition = |delta_theta: f64| {
-        check_jump_validity(&f, &line_denominator, theta0, delta_theta, w_steps_linear, log_space)
+        check_jump_validity(&f, &line_fraction, context, theta0, delta_theta, &numerator)
     };
     optimization::get_maximum_condition(condition, min_step, limit)
 }
@@ -116,17 +160,17 @@ where
 
 fn get_stability_segment_1_d<F1, F2>(
     f_1_d: F1,
-    line_denom_1_d: F2,
+    line_fraction_1_d: F2,
+    context_1_d: &LineContext1D<'_>,
     theta0: f64,
     delta: f64,
     limit: f64,
     conf: &LineConfiguration,
-    log_space: &[f64],
     verbose: bool,
 ) -> (f64, Option<Vec<f64>>)
 where
-    F1: Fn(Comp, f64) -> Comp,
-    F2: Fn(f64, f64, f64) -> f64
+    F1: Fn(f64, f64) -> Comp,
+    F2: Fn(&[f64], &[f64], f64, f64, &mut [f64])
 {
     debug!("Finding stability segment for theta0={}", theta0);
     let mut theta = theta0;
@@ -143,12 +187,11 @@ where
         /* Find maximum offset allowed by Rouche's theorem */
         delta_theta = find_max_delta_theta(
             &f_1_d,
-            &line_denom_1_d,
+            &line_fraction_1_d,
+            context_1_d,
             theta,
             delta,
             limit,
-            conf.w_steps_linear,
-            log_space,
         );
 
         /* Reduce change for numerical errors caused by global optimization */
@@ -228,39 +271,127 @@ fn get_stability_segment(
         panic!("Origin not in given limits");
     }
 
-    let delta = match conf.delta {
+    let delta = get_delta(conf, angle);
+    let limit = get_max_theta(&conf.limits, origin, angle, delta);
+
+    get_directional_segment(conf, angle, origin, delta, limit, log_space, verbose)
+}
+
+
+fn get_delta(conf: &LineConfiguration, angle: f64) -> f64 {
+    match conf.delta {
         Delta::Abs(abs) => abs,
         Delta::Rel(rel) => delta_rel2abs(&conf.limits, rel, angle),
-    };
+    }
+}
 
-    // Create a closure which converts the 2d characteristic function to a 1d function
+
+/* Stability segment emanating from the origin in the given direction, no longer than limit */
+fn get_directional_segment(
+    conf: &LineConfiguration,
+    angle: f64,
+    origin: Par,
+    delta: f64,
+    limit: f64,
+    log_space: &[f64],
+    verbose: bool,
+) -> (f64, Option<Vec<f64>>)
+{
+    // Create a closure which converts the 2d characteristic function to a 1d function on the boundary
+    let boundary = conf.contour_conf.boundary;
     let directional_vec = (f64::cos(angle), f64::sin(angle));
-    let f_1_d = |s: Comp, theta: f64 | -> Comp {
-        let p0 = origin.0 + theta*directional_vec.0;
-        let p1 = origin.1 + theta*directional_vec.1;
-        let p = (p0, p1);
-        (conf.system.f_complex)(s, p)
+    let get_p = |theta: f64| (origin.0 + theta*directional_vec.0, origin.1 + theta*directional_vec.1);
+    let f_1_d = |w: f64, theta: f64 | -> Comp {
+        (conf.system.f_complex)(boundary.point(w), get_p(theta))
     };
 
     // Create a closure which converts the 2d denom function into a 1d denom function
-    let line_denom_2_d = conf.system.line_denominator.expect("System must have line denom impl");
+    let line_denom_1_d  = |w: f64, th_min: f64, th_max: f64| match boundary {
+        StabilityBoundary::ImaginaryAxis => {
+            let line_denom_2_d = conf.system.line_denominator.expect("System must have line denom impl");
+            line_denom_2_d(w, origin, angle, th_min, th_max)
+        },
+        _ => {
+            /* The segment [th_min, th_max] lies within a ball of radius th_max - th_min around its start */
+            let region_gradient = conf.system.region_gradient.expect("System must have region gradient impl");
+            let radius = th_max - th_min;
+            geometry::q_norm(region_gradient(boundary.point(w), get_p(th_min), (radius, radius)), 2.0)
+        },
+    };
 
-    let line_denom_1_d  = |w: f64, th_min: f64, th_max: f64| {
-        line_denom_2_d(w, origin, angle, th_min, th_max)
+    // Create a closure which evaluates the 1d fraction on a batch of frequencies, given the numerator
+    let line_fraction_1_d = |numerator: &[f64], w: &[f64], th_min: f64, th_max: f64, out: &mut [f64]| {
+        match (boundary, conf.system.line_fraction_precalculated_numerator) {
+            (StabilityBoundary::ImaginaryAxis, Some(line_fraction_2_d)) => {
+                line_fraction_2_d(numerator, w, origin, angle, th_min, th_max, out)
+            },
+            _ => {
+                for ((out, num), w) in out.iter_mut().zip(numerator).zip(w) {
+                    *out = num / line_denom_1_d(*w, th_min, th_max);
+                }
+            },
+        }
+    };
+
+    // Certified minimization additionally requires a Lipschitz bound of the line fraction
+    let lipschitz_1_d = |w_min: f64, w_max: f64, th_min: f64, th_max: f64| {
+        let lipschitz_2_d = conf.system.line_fraction_lipschitz.expect("System must have lipschitz impl");
+        lipschitz_2_d(w_min, w_max, origin, angle, th_min, th_max)
+    };
+    let tails_1_d = |w_min: f64, w_max: f64, th_min: f64, th_max: f64| {
+        let tails_2_d = conf.system.line_fraction_tails.unwrap();
+        tails_2_d(w_min, w_max, origin, angle, th_min, th_max)
+    };
+    // Bounds provided by systems hold only on the imaginary axis
+    if boundary != StabilityBoundary::ImaginaryAxis && conf.certified_minimum {
+        panic!("Certified minimization is supported only for the imaginary axis boundary");
+    }
+    let context_1_d = LineContext1D {
+        bounds: LineBounds1D {
+            lipschitz: match conf.certified_minimum {
+                true => Option::Some(&lipschitz_1_d),
+                false => Option::None,
+            },
+            tails: match (conf.system.line_fraction_tails, boundary) {
+                (Some(_), StabilityBoundary::ImaginaryAxis) => Option::Some(&tails_1_d),
+                _ => Option::None,
+            },
+        },
+        w_steps_linear: conf.w_steps_linear,
+        log_space,
     };
 
-    let limit = get_max_theta(&conf.limits, origin, angle, delta);
     let theta0 = 0.0;
 
-    get_stability_segment_1_d(f_1_d, line_denom_1_d, theta0, delta, limit, conf, log_space, verbose)
+    get_stability_segment_1_d(
+        f_1_d,
+        line_fraction_1_d,
+        &context_1_d,
+        theta0,
+        delta,
+        limit,
+        conf,
+        verbose)
+}
+
+
+/* Certified length of the straight segment between the given points (its full length on success) */
+pub fn get_certified_length(conf: &LineConfiguration, from: Par, to: Par, log_space: &[f64]) -> f64 {
+    let length = f64::hypot(to.0 - from.0, to.1 - from.1);
+    let angle = f64::atan2(to.1 - from.1, to.0 - from.0);
+    let delta = get_delta(conf, angle);
+    info!("Certifying segment from {:?} to {:?}", from, to);
+
+    let (theta, _) = get_directional_segment(conf, angle, from, delta, length, log_space, false);
+    theta
 }
 
 
-fn get_rayfan(conf: &LineConfiguration, origin: Par, log_space: &[f64], verbose: bool) -> RayFan {
+pub fn get_rayfan(conf: &LineConfiguration, origin: Par, log_space: &[f64], verbose: bool) -> RayFan {
     info!("Calculating line algo for rayfan {:?}", origin);
 
     /* Determine NU for given rayfan (used in python figure subsystem for plotting results) */
-    let nu = nu::calculate_nu_single(&conf.contour_conf, conf.system.f_complex, origin);
+    let nu = nu::calculate_nu_single(&conf.contour_conf, &conf.system, origin);
 
     /* Determine maximal stability equivalent segment along each rayfan angle */
     let angles = spawn_angles(&conf.limits, conf.ray_count, conf.corrective_ratio);
@@ -306,9 +437,8 @@ pub fn run_line(args: &Args) {
     let w_log_space = config.get_log_space();
 
     /* Determine a stability equivalent rayfan for each given origin */
-    let rayfans = config
-        .origins
-        .clone()
+    let origins = config.origins.get_origins(&config.system, &config.limits, &config.contour_conf);
+    let rayfans = origins
         .into_par_iter()
         .map(|origin| get_rayfan(config, origin, &w_log_space, args.verbose_data));
 
@@ -339,4 +469,35 @@ pub fn run_line(args: &Args) {
 
     info!("Storing results into {}", filename);
     storage::store_results(results, &filename);
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_certified_segments() {
+        let pairs = [
+            ("retarded1", "retarded1_certified"),
+            ("test_configuration", "test_configuration_certified"),
+        ];
+        for (brent_name, certified_name) in pairs {
+            let brent_conf = &CONFIGURATONS[brent_name];
+            let certified_conf = &CONFIGURATONS[certified_name];
+            let log_space = brent_conf.get_log_space();
+            let origins = certified_conf.origins
+                .get_origins(&certified_conf.system, &certified_conf.limits, &certified_conf.contour_conf);
+
+            for origin in origins {
+                for angle in spawn_angles(&brent_conf.limits, 16, Option::None) {
+                    let (brent, _) = get_stability_segment(brent_conf, angle, origin, &log_space, false);
+                    let (certified, _) = get_stability_segment(certified_conf, angle, origin, &log_space, false);
+
+                    /* Certified jumps are never longer, yet the segments must not collapse */
+                    assert!(certified <= brent * (1.0 + 1e-9));
+                    assert!(certified > 0.5 * brent);
+                }
+            }
+        }
+    }
+}
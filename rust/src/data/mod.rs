@@ -1,5 +1,6 @@
-mod line;
-mod region;
+pub mod line;
+pub mod origins;
+pub mod region;
 
 use log::{debug, info};
 
@@ -0,0 +1,233 @@
+use std::collections::VecDeque;
+
+use log::{debug, info};
+use rayon::prelude::*;
+use iter_num_tools::lin_space;
+
+use crate::nu::{self, ContourConfiguration};
+use crate::types::{Limits, Par, System};
+
+
+/* Origins upon which the line and region algorithms are run */
+#[derive(Clone)]
+pub enum Origins {
+    /* Origins given explicitly */
+    Manual(Vec<Par>),
+    /* Origins seeded from a coarse nu map with given number of steps per axis */
+    Auto(usize),
+}
+
+
+impl Origins {
+    pub fn get_origins(
+        &self,
+        system: &System,
+        limits: &Limits,
+        contour_conf: &ContourConfiguration,
+    ) -> Vec<Par>
+    {
+        match self {
+            Origins::Manual(origins) => origins.clone(),
+            Origins::Auto(grid_step) => seed_origins(system, limits, contour_conf, *grid_step),
+        }
+    }
+}
+
+
+/* Grid of labels over a parametric domain; unlabeled (None) cells belong to no component */
+pub struct LabelMap {
+    pub values: Vec<Option<i32>>,
+    pub points: Vec<Par>,
+    pub steps: usize,
+}
+
+
+impl LabelMap {
+    pub fn get_grid(limits: &Limits, steps: usize) -> Vec<Par> {
+        let p1s: Vec<f64> = lin_space(limits.p1_min..=limits.p1_max, steps).collect();
+        let p2s: Vec<f64> = lin_space(limits.p2_min..=limits.p2_max, steps).collect();
+        p1s.iter().flat_map(|&p1| p2s.iter().map(move |&p2| (p1, p2))).collect()
+    }
+
+    pub fn index(&self, i: usize, j: usize) -> usize {
+        i * self.steps + j
+    }
+
+    fn neighbours(&self, i: usize, j: usize) -> Vec<(usize, usize)> {
+        let mut neighbours = Vec::new();
+        if i > 0 { neighbours.push((i - 1, j)); }
+        if j > 0 { neighbours.push((i, j - 1)); }
+        if i + 1 < self.steps { neighbours.push((i + 1, j)); }
+        if j + 1 < self.steps { neighbours.push((i, j + 1)); }
+        neighbours
+    }
+
+    /* Split the map into 4-connected components of equal labels */
+    pub fn get_components(&self) -> Vec<Vec<(usize, usize)>> {
+        let mut visited = vec![false; self.values.len()];
+        let mut components = Vec::new();
+
+        for i in 0..self.steps {
+            for j in 0..self.steps {
+                let start = self.index(i, j);
+                if visited[start] || self.values[start].is_none() {
+                    continue;
+                }
+
+                let label = self.values[start];
+                let mut component = Vec::new();
+                let mut queue = VecDeque::from([(i, j)]);
+                visited[start] = true;
+
+                while let Some((ci, cj)) = queue.pop_front() {
+                    component.push((ci, cj));
+                    for (ni, nj) in self.neighbours(ci, cj) {
+                        let index = self.index(ni, nj);
+                        if !visited[index] && self.values[index] == label {
+                            visited[index] = true;
+                            queue.push_back((ni, nj));
+                        }
+                    }
+                }
+
+                components.push(component);
+            }
+        }
+
+        components
+    }
+
+    /* Cell of the component farthest from differently labeled cells and domain edges,
+     * along with its distance in grid units */
+    pub fn get_deepest_cell(&self, component: &[(usize, usize)]) -> ((usize, usize), f64) {
+        let label = self.values[self.index(component[0].0, component[0].1)];
+        let n = self.steps as f64;
+
+        let differing: Vec<(f64, f64)> = (0..self.steps)
+            .flat_map(|i| (0..self.steps).map(move |j| (i, j)))
+            .filter(|&(i, j)| self.values[self.index(i, j)] != label)
+            .map(|(i, j)| (i as f64, j as f64))
+            .collect();
+
+        let depth = |&(i, j): &(usize, usize)| {
+            let (x, y) = (i as f64, j as f64);
+            let edge_distance = (x + 1.0).min(y + 1.0).min(n - x).min(n - y);
+            differing
+                .iter()
+                .map(|(dx, dy)| ((x - dx).powi(2) + (y - dy).powi(2)).sqrt())
+                .fold(edge_distance, f64::min)
+        };
+
+        component
+            .iter()
+            .map(|cell| (*cell, depth(cell)))
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+            .unwrap()
+    }
+}
+
+
+/* Place one origin deep inside each connected component of equal nu on a coarse grid */
+pub fn seed_origins(
+    system: &System,
+    limits: &Limits,
+    contour_conf: &ContourConfiguration,
+    steps: usize,
+) -> Vec<Par>
+{
+    let points = LabelMap::get_grid(limits, steps);
+    let values = points
+        .par_iter()
+        .map(|&p| {
+            let estimate = nu::calculate_nu_detailed(contour_conf, system, p);
+            if estimate.reliable { Option::Some(estimate.nu) } else { Option::None }
+        })
+        .collect();
+    let map = LabelMap { values, points, steps };
+
+    let origins: Vec<Par> = map
+        .get_components()
+        .iter()
+        .map(|component| {
+            let ((i, j), _) = map.get_deepest_cell(component);
+            let origin = map.points[map.index(i, j)];
+            debug!(
+                "Component with nu {:?} and {} cells seeded at {:?}",
+                map.values[map.index(i, j)],
+                component.len(),
+                origin);
+            origin
+        })
+        .collect();
+
+    info!("Seeded {} origins from a {}x{} nu map", origins.len(), steps, steps);
+    origins
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /* Map from rows of labels, with negative entries standing for unlabeled cells */
+    fn get_label_map(rows: &[&[i32]]) -> LabelMap {
+        let steps = rows.len();
+        let values = rows
+            .iter()
+            .flat_map(|row| row.iter().map(|&v| if v < 0 { Option::None } else { Option::Some(v) }))
+            .collect();
+        let points = (0..steps).flat_map(|i| (0..steps).map(move |j| (i as f64, j as f64))).collect();
+        LabelMap { values, points, steps }
+    }
+
+    #[test]
+    fn test_get_components() {
+        let map = get_label_map(&[
+            &[ 0,  0,  0,  0,  1,  1],
+            &[ 0,  0,  0,  0,  1,  1],
+            &[ 0,  0,  0,  0, -1,  1],
+            &[ 0,  0,  0,  0, -1, -1],
+            &[-1, -1, -1, -1,  0,  0],
+            &[ 1,  1, -1, -1,  0,  0],
+        ]);
+
+        let mut components = map.get_components();
+        components.iter_mut().for_each(|component| component.sort());
+        components.sort_by_key(|component| component[0]);
+
+        /* Unlabeled cells form no component, and diagonal neighbours are not connected */
+        assert_eq!(components.len(), 4);
+        assert_eq!(components[0].len(), 16);
+        assert_eq!(components[1], vec![(0, 4), (0, 5), (1, 4), (1, 5), (2, 5)]);
+        assert_eq!(components[2], vec![(4, 4), (4, 5), (5, 4), (5, 5)]);
+        assert_eq!(components[3], vec![(5, 0), (5, 1)]);
+
+        for component in &components {
+            let label = map.values[map.index(component[0].0, component[0].1)];
+            assert!(component.iter().all(|&(i, j)| map.values[map.index(i, j)] == label));
+        }
+    }
+
+    #[test]
+    fn test_get_deepest_cell() {
+        /* Without other labels, the center is farthest from the domain edges */
+        let uniform = get_label_map(&[&[2; 7] as &[i32]; 7]);
+        let components = uniform.get_components();
+        assert_eq!(components.len(), 1);
+        assert_eq!(uniform.get_deepest_cell(&components[0]), ((3, 3), 4.0));
+
+        /* Differently labeled cells push the deepest cell away from them */
+        let map = get_label_map(&[
+            &[0, 0, 0, 0, 0],
+            &[0, 0, 0, 0, 0],
+            &[0, 0, 0, 0, 0],
+            &[1, 1, 1, 1, 1],
+            &[1, 1, 1, 1, 1],
+        ]);
+        let mut components = map.get_components();
+        components.sort_by_key(|component| map.values[map.index(component[0].0, component[0].1)]);
+        let ((i, _), depth) = map.get_deepest_cell(&components[0]);
+        assert_eq!((i, depth), (1, 2.0));
+        assert_eq!(map.get_deepest_cell(&components[1]).1, 1.0);
+    }
+}
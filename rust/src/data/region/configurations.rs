@@ -17,10 +17,13 @@ use crate::systems::ln_system1;
 use crate::systems::dopid2;
 use crate::systems::dopid3;
 
-use crate::nu::ContourConfiguration;
+use crate::data::origins::Origins;
+use crate::nu::{ContourConfiguration, StabilityBoundary};
 use crate::types::{Limits, Par, System};
+use crate::utils::batch::FrequencyGrid;
 
 
+#[derive(Clone)]
 pub enum Delta {
     Abs(f64),
     Rel(f64),
@@ -31,7 +34,7 @@ pub struct RegionConfiguration {
     pub name: &'static str,                  // Config name
     pub system: System,                      // System to be used
     pub limits: Limits,                      // Parametric search domain
-    pub origins: Vec<Par>,                   // Origins upon which to run fsals
+    pub origins: Origins,                    // Origins upon which to run fsals
     pub contour_conf: ContourConfiguration,  // Contour used for evaluating NU
     pub delta: Delta,                        // Termination criteria (relative or absolute)
     pub safeguard: f64,                      // Safeguard against numerical optimization errors
@@ -41,9 +44,20 @@ pub struct RegionConfiguration {
     pub log_space_minw: f64,                 // Lower w for logarithmic minimization
     pub log_space_maxw: f64,                 // Upper w for logarithmic minimization
     pub log_space_steps: usize,              // Domain granularity for logarithmic minimization
+    pub certified_minimum: bool,             // Use a guaranteed lower bound of the minimum
     pub max_iter: Option<u32>,               // Maximal number of allowed iterations (depth)
     pub check_obsoletion: bool,              // Check if points are obsolete before evaluating them
-    pub nu_modifier: i32,                    // Modify resulting NU by this value
+    pub nu_modifier: i32,                    // Discount zeros of f which do not depend on parameters
+    pub q: f64,                              // Hölder exponent of pregion balls, from 1 to infinity
+    pub scaling: Par,                        // Per-axis scale; pregions are q-balls of scaled parameters
+    pub coverage: Option<CoverageConfiguration>, // Keep seeding new origins until limits are covered
+}
+
+
+pub struct CoverageConfiguration {
+    pub target: f64,                         // Fraction of limits to be covered by regions
+    pub grid_step: usize,                    // Steps per axis of the grid measuring coverage
+    pub max_rounds: u32,                     // Maximal number of reseeding rounds
 }
 
 
@@ -52,6 +66,18 @@ impl RegionConfiguration {
         iter_num_tools::log_space(self.log_space_minw..=self.log_space_maxw, self.log_space_steps)
             .collect()
     }
+
+    /* Logspace along with frequency kernels of the system, shared by all pregions */
+    pub fn get_frequency_grid(&self) -> FrequencyGrid {
+        FrequencyGrid::new(self.get_log_space(), self.system.frequency_kernels)
+    }
+
+    /* Fractions provided by systems hold only for unscaled disks and the imaginary axis */
+    pub fn uses_system_fractions(&self) -> bool {
+        self.contour_conf.boundary == StabilityBoundary::ImaginaryAxis
+            && self.q == 2.0
+            && self.scaling == (1.0, 1.0)
+    }
 }
 
 
@@ -65,17 +91,184 @@ lazy_static! {
             limits: Limits { p1_min: 0.0, p1_max: 2.6, p2_min: 0.0, p2_max: 3.3 },
             safeguard: 0.98,
             check_obsoletion: true,
-            origins: vec![
+            origins: Origins::Manual(vec![
+                (0.25, 1.00),
+                (1.75, 1.20),
+                (0.88, 2.73),
+                (0.20, 3.10),
+                (0.71, 3.22),
+                ]),
+            contour_conf: ContourConfiguration {
+                indentation_radius: 1e-3,
+                singular_points: vec![],
+                w_max: 1e5,
+                steps: 10_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
+                },
+            delta: Delta::Abs(1e-3),
+            spawn_count: 32,
+            enforce_limits: false,
+            lin_steps: 10_000,
+            log_space_minw: 1e-3,
+            log_space_maxw: 1e5,
+            log_space_steps: 10_000,
+            certified_minimum: false,
+            max_iter: Option::None,
+            nu_modifier: 0,
+            q: 2.0,
+            scaling: (1.0, 1.0),
+            coverage: Option::None,
+        });
+
+        configs.insert("retarded1_certified", RegionConfiguration {
+            name: "retarded1_certified",
+            system: retarded1::SYSTEM,
+            limits: Limits { p1_min: 0.0, p1_max: 2.6, p2_min: 0.0, p2_max: 3.3 },
+            safeguard: 0.98,
+            check_obsoletion: true,
+            origins: Origins::Manual(vec![
+                (0.25, 1.00),
+                (1.75, 1.20),
+                (0.88, 2.73),
+                ]),
+            contour_conf: ContourConfiguration {
+                indentation_radius: 1e-3,
+                singular_points: vec![],
+                w_max: 1e5,
+                steps: 10_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
+                },
+            delta: Delta::Abs(1e-3),
+            spawn_count: 32,
+            enforce_limits: false,
+            lin_steps: 10_000,
+            log_space_minw: 1e-3,
+            log_space_maxw: 1e5,
+            log_space_steps: 10_000,
+            certified_minimum: true,
+            max_iter: Option::None,
+            nu_modifier: 0,
+            q: 2.0,
+            scaling: (1.0, 1.0),
+            coverage: Option::None,
+        });
+
+        configs.insert("retarded1_auto", RegionConfiguration {
+            name: "retarded1_auto",
+            system: retarded1::SYSTEM,
+            limits: Limits { p1_min: 0.0, p1_max: 2.6, p2_min: 0.0, p2_max: 3.3 },
+            safeguard: 0.98,
+            check_obsoletion: true,
+            origins: Origins::Auto(41),
+            contour_conf: ContourConfiguration {
+                indentation_radius: 1e-3,
+                singular_points: vec![],
+                w_max: 1e5,
+                steps: 10_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
+                },
+            delta: Delta::Abs(1e-3),
+            spawn_count: 32,
+            enforce_limits: false,
+            lin_steps: 10_000,
+            log_space_minw: 1e-3,
+            log_space_maxw: 1e5,
+            log_space_steps: 10_000,
+            certified_minimum: false,
+            max_iter: Option::None,
+            nu_modifier: 0,
+            q: 2.0,
+            scaling: (1.0, 1.0),
+            coverage: Option::None,
+        });
+
+        configs.insert("retarded1_shared", RegionConfiguration {
+            name: "retarded1_shared",
+            system: retarded1::SYSTEM,
+            limits: Limits { p1_min: 0.0, p1_max: 2.6, p2_min: 0.0, p2_max: 3.3 },
+            safeguard: 0.98,
+            check_obsoletion: true,
+            origins: Origins::Manual(vec![
+                (0.25, 1.00),
+                (0.50, 2.00),
+                (1.75, 1.20),
+                (2.30, 0.50),
+                (0.88, 2.73),
+                ]),
+            contour_conf: ContourConfiguration {
+                indentation_radius: 1e-3,
+                singular_points: vec![],
+                w_max: 1e5,
+                steps: 10_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
+                },
+            delta: Delta::Abs(1e-3),
+            spawn_count: 32,
+            enforce_limits: false,
+            lin_steps: 10_000,
+            log_space_minw: 1e-3,
+            log_space_maxw: 1e5,
+            log_space_steps: 10_000,
+            certified_minimum: false,
+            max_iter: Option::None,
+            nu_modifier: 0,
+            q: 2.0,
+            scaling: (1.0, 1.0),
+            coverage: Option::None,
+        });
+
+        configs.insert("retarded1_coverage", RegionConfiguration {
+            name: "retarded1_coverage",
+            system: retarded1::SYSTEM,
+            limits: Limits { p1_min: 0.0, p1_max: 2.6, p2_min: 0.0, p2_max: 3.3 },
+            safeguard: 0.98,
+            check_obsoletion: true,
+            origins: Origins::Auto(41),
+            contour_conf: ContourConfiguration {
+                indentation_radius: 1e-3,
+                singular_points: vec![],
+                w_max: 1e5,
+                steps: 10_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
+                },
+            delta: Delta::Abs(1e-3),
+            spawn_count: 32,
+            enforce_limits: false,
+            lin_steps: 10_000,
+            log_space_minw: 1e-3,
+            log_space_maxw: 1e5,
+            log_space_steps: 10_000,
+            certified_minimum: false,
+            max_iter: Option::None,
+            nu_modifier: 0,
+            q: 2.0,
+            scaling: (1.0, 1.0),
+            coverage: Option::Some(CoverageConfiguration {
+                target: 0.95,
+                grid_step: 100,
+                max_rounds: 10,
+                }),
+        });
+
+        configs.insert("retarded1_infinity_norm", RegionConfiguration {
+            name: "retarded1_infinity_norm",
+            system: retarded1::SYSTEM,
+            limits: Limits { p1_min: 0.0, p1_max: 2.6, p2_min: 0.0, p2_max: 3.3 },
+            safeguard: 0.98,
+            check_obsoletion: true,
+            origins: Origins::Manual(vec![
                 (0.25, 1.00),
                 (1.75, 1.20),
                 (0.88, 2.73),
                 (0.20, 3.10),
                 (0.71, 3.22),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e5,
                 steps: 10_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(1e-3),
             spawn_count: 32,
@@ -84,8 +277,44 @@ lazy_static! {
             log_space_minw: 1e-3,
             log_space_maxw: 1e5,
             log_space_steps: 10_000,
+            certified_minimum: false,
+            max_iter: Option::None,
+            nu_modifier: 0,
+            q: f64::INFINITY,
+            scaling: (1.0, 1.0),
+            coverage: Option::None,
+        });
+
+        configs.insert("retarded1_decay", RegionConfiguration {
+            name: "retarded1_decay",
+            system: retarded1::SYSTEM,
+            limits: Limits { p1_min: 0.0, p1_max: 2.6, p2_min: 0.0, p2_max: 3.3 },
+            safeguard: 0.98,
+            check_obsoletion: true,
+            origins: Origins::Manual(vec![
+                (0.25, 1.00),
+                (1.75, 1.20),
+                ]),
+            contour_conf: ContourConfiguration {
+                indentation_radius: 1e-3,
+                singular_points: vec![],
+                w_max: 1e3,
+                steps: 10_000,
+                boundary: StabilityBoundary::ShiftedAxis(0.1),
+                },
+            delta: Delta::Abs(1e-2),
+            spawn_count: 32,
+            enforce_limits: false,
+            lin_steps: 1_000,
+            log_space_minw: 1e-3,
+            log_space_maxw: 1e3,
+            log_space_steps: 1_000,
+            certified_minimum: false,
             max_iter: Option::None,
             nu_modifier: 0,
+            q: 2.0,
+            scaling: (1.0, 1.0),
+            coverage: Option::None,
         });
 
         configs.insert("retarded2", RegionConfiguration {
@@ -94,7 +323,7 @@ lazy_static! {
             limits: Limits { p1_min: 0.0, p1_max: 5.0, p2_min: 0.0, p2_max: 5.0 },
             safeguard: 0.80,
             check_obsoletion: true,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 (1.5, 1.5),
                 (0.1, 0.1),
                 (0.1, 3.0),
@@ -106,11 +335,13 @@ lazy_static! {
                 (0.5, 4.0),
                 (0.44, 4.95),
                 (2.2, 4.95),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e5,
                 steps: 10_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(0.0001),
             spawn_count: 16,
@@ -119,8 +350,12 @@ lazy_static! {
             log_space_minw: 0.65,
             log_space_maxw: 1e4,
             log_space_steps: 30_000,
+            certified_minimum: false,
             max_iter: Option::None,
             nu_modifier: 0,
+            q: 2.0,
+            scaling: (1.0, 1.0),
+            coverage: Option::None,
         });
 
 
@@ -130,7 +365,47 @@ lazy_static! {
             limits: Limits { p1_min: 0.0, p1_max: 20.5, p2_min: 0.0, p2_max: 0.5 },
             safeguard: 0.95,
             check_obsoletion: true,
-            origins: vec![
+            origins: Origins::Manual(vec![
+                (1.0, 0.2),
+                (4.9, 0.1),
+                (8.0, 0.04),
+                (11.3, 0.08),
+                (14.5, 0.04),
+                (17.5, 0.08),
+                (12.0, 0.013),
+                (18.0, 0.018),
+                (19.9, 0.047),
+                (16.19, 0.007),
+                ]),
+            contour_conf: ContourConfiguration {
+                indentation_radius: 1e-3,
+                singular_points: vec![],
+                w_max: 1e5,
+                steps: 1_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
+                },
+            delta: Delta::Abs(5e-4),
+            spawn_count: 32,
+            enforce_limits: false,
+            log_space_minw: 1e-4,
+            log_space_maxw: 1e1,
+            log_space_steps: 10_000,
+            certified_minimum: false,
+            lin_steps: 10_000,
+            max_iter: Option::None,
+            nu_modifier: 0,
+            q: 2.0,
+            scaling: (1.0, 1.0),
+            coverage: Option::None,
+        });
+
+        configs.insert("distributed_delay1_scaled", RegionConfiguration {
+            name: "distributed_delay1_scaled",
+            system: distributed_delay1::SYSTEM,
+            limits: Limits { p1_min: 0.0, p1_max: 20.5, p2_min: 0.0, p2_max: 0.5 },
+            safeguard: 0.95,
+            check_obsoletion: true,
+            origins: Origins::Manual(vec![
                 (1.0, 0.2),
                 (4.9, 0.1),
                 (8.0, 0.04),
@@ -141,11 +416,13 @@ lazy_static! {
                 (18.0, 0.018),
                 (19.9, 0.047),
                 (16.19, 0.007),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e5,
                 steps: 1_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(5e-4),
             spawn_count: 32,
@@ -153,9 +430,13 @@ lazy_static! {
             log_space_minw: 1e-4,
             log_space_maxw: 1e1,
             log_space_steps: 10_000,
+            certified_minimum: false,
             lin_steps: 10_000,
             max_iter: Option::None,
             nu_modifier: 0,
+            q: 2.0,
+            scaling: (40.0, 1.0),
+            coverage: Option::None,
         });
 
         configs.insert("pde_complex_k_sigma", RegionConfiguration {
@@ -164,14 +445,16 @@ lazy_static! {
             limits: Limits { p1_min: 0.1, p1_max: 20.0, p2_min: 0.1, p2_max: 20.0 },
             safeguard: 1.0,
             check_obsoletion: true,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 (4.0, 4.0),
                 (6.0, 6.0),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e5,
                 steps: 1_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(1e-2),
             spawn_count: 32, // Changing to higher value might fix issue in upper right corner
@@ -180,8 +463,12 @@ lazy_static! {
             log_space_minw: 1e-3,   // 1e-3 in python
             log_space_maxw: 1e5,    // 1e7 in python
             log_space_steps: 1_000, // 10_000 in python
+            certified_minimum: false,
             max_iter: Option::None,
             nu_modifier: 0,
+            q: 2.0,
+            scaling: (1.0, 1.0),
+            coverage: Option::None,
         });
 
         configs.insert("pde_complex_beta_sigma", RegionConfiguration {
@@ -190,14 +477,16 @@ lazy_static! {
             limits: Limits { p1_min: 0.0, p1_max: 2.0, p2_min: 0.0, p2_max: 2.0 },
             safeguard: 1.0,
             check_obsoletion: true,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 (1.0, 1.0),
                 (1.80, 1.50),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e5,
                 steps: 1_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(1e-3),
             spawn_count: 32,
@@ -206,8 +495,12 @@ lazy_static! {
             log_space_minw: 1e-3,   // 1e-3 in python
             log_space_maxw: 1e5,    // 1e7 in python
             log_space_steps: 1_000, // 10_000 in python
+            certified_minimum: false,
             max_iter: Option::None,
             nu_modifier: 0,
+            q: 2.0,
+            scaling: (1.0, 1.0),
+            coverage: Option::None,
         });
 
         configs.insert("pde_complex_tau_sigma", RegionConfiguration {
@@ -216,16 +509,18 @@ lazy_static! {
             limits: Limits { p1_min: 0.0, p1_max: 20.0, p2_min: 0.0, p2_max: 20.0 },
             safeguard: 1.0,
             check_obsoletion: true,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 ( 1.0,  1.0),
                 (10.0, 10.0),
                 (18.0,  2.0),
                 (19.0,  0.15),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e5,
                 steps: 1_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             // delta: Delta::Abs(1e-3),
             delta: Delta::Abs(1e-1),
@@ -235,8 +530,12 @@ lazy_static! {
             log_space_minw: 1e-3,
             log_space_maxw: 1e5,
             log_space_steps: 1_000,
+            certified_minimum: false,
             max_iter: Option::None,
             nu_modifier: 0,
+            q: 2.0,
+            scaling: (1.0, 1.0),
+            coverage: Option::None,
         });
 
         configs.insert("telegrapher_x_k", RegionConfiguration {
@@ -245,14 +544,16 @@ lazy_static! {
             limits: Limits { p1_min: 0.1, p1_max: 4.0, p2_min: 4.1, p2_max: 8.0 },
             safeguard: 0.95,
             check_obsoletion: true,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 (0.4, 7.0),
                 (2.5, 6.0),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e5,
                 steps: 1_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(1e-4),
             spawn_count: 32,
@@ -261,8 +562,12 @@ lazy_static! {
             log_space_minw: 1e-2,
             log_space_maxw: 1e5,
             log_space_steps: 1_000,
+            certified_minimum: false,
             max_iter: Option::None,
             nu_modifier: 0,
+            q: 2.0,
+            scaling: (1.0, 1.0),
+            coverage: Option::None,
         });
 
         configs.insert("telegrapher_alpha_gamma", RegionConfiguration {
@@ -271,16 +576,18 @@ lazy_static! {
             limits: Limits { p1_min: 0.2, p1_max: 0.9, p2_min: 0.2, p2_max: 0.9 },
             safeguard: 1.0,
             check_obsoletion: true,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 (0.40, 0.40),
                 (0.70, 0.70),
                 (0.86, 0.86),
                 (0.89, 0.89),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e7,
                 steps: 10_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(0.00005),
             spawn_count: 32,
@@ -288,9 +595,13 @@ lazy_static! {
             log_space_minw: 1e-3,
             log_space_maxw: 1e7,
             log_space_steps: 1_000,
+            certified_minimum: false,
             lin_steps: 1_000,
             max_iter: Option::None,
             nu_modifier: 0,
+            q: 2.0,
+            scaling: (1.0, 1.0),
+            coverage: Option::None,
         });
 
         configs.insert("telegrapher_alpha_gamma_single_region", RegionConfiguration {
@@ -299,13 +610,15 @@ lazy_static! {
             limits: Limits { p1_min: 0.2, p1_max: 0.9, p2_min: 0.2, p2_max: 0.9 },
             safeguard: 1.0,
             check_obsoletion: true,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 (0.70, 0.70),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e7,
                 steps: 10_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(0.00005),
             spawn_count: 32,
@@ -313,9 +626,13 @@ lazy_static! {
             log_space_minw: 1e-3,
             log_space_maxw: 1e7,
             log_space_steps: 1_000,
+            certified_minimum: false,
             lin_steps: 1_000,
             max_iter: Option::None,
             nu_modifier: 0,
+            q: 2.0,
+            scaling: (1.0, 1.0),
+            coverage: Option::None,
         });
 
         configs.insert("semi_infinite_rod", RegionConfiguration {
@@ -324,14 +641,16 @@ lazy_static! {
             limits: Limits { p1_min: 0.0, p1_max: 100.0, p2_min: 0.0, p2_max: 7e4 },
             safeguard: 0.90,
             check_obsoletion: true,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 (20.0, 1e4),
                 (60.0, 5e4),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e5,
                 steps: 1_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(0.1),
             spawn_count: 32,
@@ -339,9 +658,13 @@ lazy_static! {
             log_space_minw: 1e-2,
             log_space_maxw: 1e-1,
             log_space_steps: 1_000,
+            certified_minimum: false,
             lin_steps: 1_000,
             max_iter: Option::None,
             nu_modifier: 0,
+            q: 2.0,
+            scaling: (1.0, 1.0),
+            coverage: Option::None,
         });
 
         configs.insert("finite_rod", RegionConfiguration {
@@ -350,14 +673,16 @@ lazy_static! {
             limits: Limits { p1_min: 0.0, p1_max: 100.0, p2_min: 0.0, p2_max: 7e4 },
             safeguard: 0.9,
             check_obsoletion: true,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 (25.0, 15_000.0),
                 (60.0, 50_000.0),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e3,
                 steps: 10_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(0.1),
             spawn_count: 32,
@@ -365,9 +690,13 @@ lazy_static! {
             log_space_minw: 1e-3,
             log_space_maxw: 1e4,
             log_space_steps: 1_000,
+            certified_minimum: false,
             lin_steps: 1_000,
             max_iter: Option::None,
             nu_modifier: 0,
+            q: 2.0,
+            scaling: (1.0, 1.0),
+            coverage: Option::None,
         });
 
         configs.insert("telegrapher_standard", RegionConfiguration {
@@ -376,15 +705,17 @@ lazy_static! {
             limits: Limits { p1_min: 0.0, p1_max: 0.2, p2_min: 1.0, p2_max: 1.5 },
             safeguard: 0.9,
             check_obsoletion: true,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 (0.1, 1.2),
                 (0.125, 1.41),
                 (0.175, 1.48),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e4,
                 steps: 10_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(1e-5),
             spawn_count: 32,
@@ -392,9 +723,13 @@ lazy_static! {
             log_space_minw: 1e1,
             log_space_maxw: 1e4,
             log_space_steps: 1_000,
+            certified_minimum: false,
             lin_steps: 2_000,
             max_iter: Option::None,
             nu_modifier: 0,
+            q: 2.0,
+            scaling: (1.0, 1.0),
+            coverage: Option::None,
         });
 
         configs.insert("pde_complex_instructional", RegionConfiguration {
@@ -403,16 +738,18 @@ lazy_static! {
             limits: Limits { p1_min: 0.0, p1_max: 20.0, p2_min: 0.0, p2_max: 20.0 },
             safeguard: 1.0,
             check_obsoletion: false,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 ( 1.0,  1.0),
                 (10.0, 10.0),
                 (18.0,  2.0),
                 (19.0,  0.15),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e5,
                 steps: 1_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(1e-3),
             spawn_count: 32,
@@ -421,8 +758,12 @@ lazy_static! {
             log_space_minw: 1e-3,
             log_space_maxw: 1e5,
             log_space_steps: 1_000,
+            certified_minimum: false,
             max_iter: Option::Some(12),
             nu_modifier: 0,
+            q: 2.0,
+            scaling: (1.0, 1.0),
+            coverage: Option::None,
         });
 
         configs.insert("test_configuration", RegionConfiguration {
@@ -431,14 +772,48 @@ lazy_static! {
             limits: Limits { p1_min: 0.0, p1_max: 1.0, p2_min: 0.0, p2_max: 1.0 },
             safeguard: 0.9,
             check_obsoletion: false,
-            origins: vec![
+            origins: Origins::Manual(vec![
+                (0.1, 0.1),
+                (0.9, 0.9),
+                ]),
+            contour_conf: ContourConfiguration {
+                indentation_radius: 1e-3,
+                singular_points: vec![],
+                w_max: 1e5,
+                steps: 1_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
+                },
+            delta: Delta::Abs(1e-3),
+            spawn_count: 32,
+            enforce_limits: false,
+            lin_steps: 1_000,
+            log_space_minw: 1e-3,
+            log_space_maxw: 1e5,
+            log_space_steps: 10_000,
+            certified_minimum: false,
+            max_iter: Option::None,
+            nu_modifier: 0,
+            q: 2.0,
+            scaling: (1.0, 1.0),
+            coverage: Option::None,
+        });
+
+        configs.insert("test_configuration_certified", RegionConfiguration {
+            name: "test_configuration_certified",
+            system: test_system::SYSTEM,
+            limits: Limits { p1_min: 0.0, p1_max: 1.0, p2_min: 0.0, p2_max: 1.0 },
+            safeguard: 0.9,
+            check_obsoletion: false,
+            origins: Origins::Manual(vec![
                 (0.1, 0.1),
                 (0.9, 0.9),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e5,
                 steps: 1_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(1e-3),
             spawn_count: 32,
@@ -447,8 +822,12 @@ lazy_static! {
             log_space_minw: 1e-3,
             log_space_maxw: 1e5,
             log_space_steps: 10_000,
+            certified_minimum: true,
             max_iter: Option::None,
             nu_modifier: 0,
+            q: 2.0,
+            scaling: (1.0, 1.0),
+            coverage: Option::None,
         });
 
         configs.insert("telegrapher_alpha_gamma_instructional", RegionConfiguration {
@@ -457,16 +836,18 @@ lazy_static! {
             limits: Limits { p1_min: 0.2, p1_max: 0.9, p2_min: 0.2, p2_max: 0.9 },
             safeguard: 1.0,
             check_obsoletion: true,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 (0.40, 0.40),
                 (0.70, 0.70),
                 (0.86, 0.86),
                 (0.89, 0.89),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e7,
                 steps: 10_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(0.00005),
             spawn_count: 32,
@@ -474,9 +855,13 @@ lazy_static! {
             log_space_minw: 1e-3,
             log_space_maxw: 1e7,
             log_space_steps: 1_000,
+            certified_minimum: false,
             lin_steps: 1_000,
             max_iter: Option::Some(12),
             nu_modifier: 0,
+            q: 2.0,
+            scaling: (1.0, 1.0),
+            coverage: Option::None,
         });
         
         configs.insert("ln_system1", RegionConfiguration {
@@ -485,16 +870,18 @@ lazy_static! {
             limits: Limits { p1_min: 0.0, p1_max: 20.0, p2_min: 0.0, p2_max: 20.0 },
             safeguard: 1.0,
             check_obsoletion: true,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 (0.9, 0.9),
                 (4.0, 5.5),
                 (13.0, 12.0),
                 (18.5, 18.0),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e3,
                 steps: 10_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(1e-3),
             spawn_count: 32,
@@ -502,9 +889,13 @@ lazy_static! {
             log_space_minw: 1e-4,
             log_space_maxw: 1e3,
             log_space_steps: 10_000,
+            certified_minimum: false,
             lin_steps: 10_000,
             max_iter: Option::None,
             nu_modifier: 0,
+            q: 2.0,
+            scaling: (1.0, 1.0),
+            coverage: Option::None,
         });
 
         configs.insert("ln_system1_negative", RegionConfiguration {
@@ -514,7 +905,7 @@ lazy_static! {
             // limits: Limits { p1_min: -20.0, p1_max: -6.0, p2_min: -2.0, p2_max: 2.0 },
             safeguard: 0.85,
             check_obsoletion: true,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 // // Region 1
                 (-0.5, 1.1),
                 // // Region 2
@@ -537,11 +928,13 @@ lazy_static! {
                 (-19.8, 1.0),
                 // Fake region
                 // (-15.0, -1.0)
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e3,
                 steps: 10_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(0.02),
             spawn_count: 32,
@@ -549,9 +942,13 @@ lazy_static! {
             log_space_minw: 1e-13,
             log_space_maxw: 1e2,
             log_space_steps: 200_000,
+            certified_minimum: false,
             lin_steps: 3_000_000,
             max_iter: Option::None,
             nu_modifier: 0,
+            q: 2.0,
+            scaling: (1.0, 1.0),
+            coverage: Option::None,
         });
 
         configs.insert("dopid2", RegionConfiguration {
@@ -560,14 +957,16 @@ lazy_static! {
             limits: Limits { p1_min: 0.0, p1_max: 100.0, p2_min: 0.0, p2_max: 5.0 },
             safeguard: 1.0,
             check_obsoletion: true,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 (20.0, 0.1),
                 // (50.0, 0.2),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e3,
                 steps: 10_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(1e-3),
             spawn_count: 32,
@@ -575,9 +974,13 @@ lazy_static! {
             log_space_minw: 1e-3,
             log_space_maxw: 1e8,
             log_space_steps: 10_000,
+            certified_minimum: false,
             lin_steps: 10_000,
             max_iter: Option::None,
             nu_modifier: 0,
+            q: 2.0,
+            scaling: (1.0, 1.0),
+            coverage: Option::None,
         });
 
         configs.insert("dopid3", RegionConfiguration {
@@ -586,18 +989,20 @@ lazy_static! {
             limits: Limits { p1_min: 0.0, p1_max: 10.0, p2_min: 0.0, p2_max: 10.0 },
             safeguard: 0.95,
             check_obsoletion: true,
-            origins: vec![
+            origins: Origins::Manual(vec![
                 (1.0, 1.0),
                 (3.0, 3.0),
                 (5.5, 5.5),
                 (6.8, 6.8),
                 (8.2, 8.2),
                 (9.3, 9.3),
-                ],
+                ]),
             contour_conf: ContourConfiguration {
-                w_min: 1e-3,
+                indentation_radius: 1e-3,
+                singular_points: vec![],
                 w_max: 1e3,
                 steps: 10_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
                 },
             delta: Delta::Abs(5.0 * 1e-4),
             spawn_count: 32,
@@ -605,9 +1010,13 @@ lazy_static! {
             log_space_minw: 1e-1,
             log_space_maxw: 1e2,
             log_space_steps: 10_000,
+            certified_minimum: false,
             lin_steps: 10_000,
             max_iter: Option::None,
-            nu_modifier: -1,
+            nu_modifier: -1, // f has a zero at s=1 for all parameters
+            q: 2.0,
+            scaling: (1.0, 1.0),
+            coverage: Option::None,
         });
 
         configs
@@ -0,0 +1,214 @@
+use std::collections::HashSet;
+
+use log::info;
+
+use crate::data::origins::LabelMap;
+use crate::types::Par;
+use super::{absolutize_delta, get_regions, CoverageConfiguration, Region, RegionConfiguration};
+
+
+/* Uncovered areas shallower than this (in grid cells) are considered slivers and not seeded */
+const MIN_SEED_DEPTH: f64 = 2.0;
+
+
+/* Mark grid points covered by pregions as None and the rest as Some(0) */
+fn get_coverage_map(conf: &RegionConfiguration, regions: &[Region], steps: usize) -> LabelMap {
+    let points = LabelMap::get_grid(&conf.limits, steps);
+    let mut values = vec![Option::Some(0); points.len()];
+
+    let limits = &conf.limits;
+    let step1 = (limits.p1_max - limits.p1_min) / (steps - 1) as f64;
+    let step2 = (limits.p2_max - limits.p2_min) / (steps - 1) as f64;
+    let to_index = |p: f64, min: f64, step: f64| ((p - min) / step).max(0.0).min((steps - 1) as f64);
+
+    /* Only grid points within the bounding box of each pregion need to be checked */
+    for pregion in regions.iter().flat_map(|region| region.pregions.iter()) {
+        let half_width = (pregion.radius * pregion.scaling.0, pregion.radius * pregion.scaling.1);
+        let i_min = to_index(pregion.origin.0 - half_width.0, limits.p1_min, step1).ceil() as usize;
+        let i_max = to_index(pregion.origin.0 + half_width.0, limits.p1_min, step1).floor() as usize;
+        let j_min = to_index(pregion.origin.1 - half_width.1, limits.p2_min, step2).ceil() as usize;
+        let j_max = to_index(pregion.origin.1 + half_width.1, limits.p2_min, step2).floor() as usize;
+
+        for i in i_min..=i_max {
+            for j in j_min..=j_max {
+                let index = i * steps + j;
+                if values[index].is_some() && pregion.is_point_inside(points[index]) {
+                    values[index] = Option::None;
+                }
+            }
+        }
+    }
+
+    LabelMap { values, points, steps }
+}
+
+
+fn get_covered_fraction(map: &LabelMap) -> f64 {
+    let covered = map.values.iter().filter(|value| value.is_none()).count();
+    covered as f64 / map.values.len() as f64
+}
+
+
+/* Place one origin deep inside each sufficiently large uncovered area; previously tried seeds
+ * are treated as covered, so that a seed whose regions do not cover it is not retried, and new
+ * seeds are kept away from it */
+fn get_seeds(map: &mut LabelMap, tried: &mut HashSet<usize>) -> Vec<Par> {
+    for &index in tried.iter() {
+        map.values[index] = Option::None;
+    }
+
+    let seeds: Vec<usize> = map.get_components()
+        .iter()
+        .map(|component| map.get_deepest_cell(component))
+        .filter(|(_, depth)| *depth >= MIN_SEED_DEPTH)
+        .map(|((i, j), _)| map.index(i, j))
+        .collect();
+
+    tried.extend(seeds.iter());
+    seeds.iter().map(|&index| map.points[index]).collect()
+}
+
+
+fn get_region_size(region: &Region) -> f64 {
+    region.pregions.iter().map(|pregion| pregion.radius).fold(0.0, f64::max)
+}
+
+
+/* Keep seeding regions in uncovered parts of the limits until coverage is satisfactory */
+pub fn extend_coverage(
+    conf: &RegionConfiguration,
+    coverage: &CoverageConfiguration,
+    regions: &mut Vec<Region>,
+)
+{
+    let delta = absolutize_delta(&conf.delta, &conf.limits, conf.scaling);
+    let mut tried = HashSet::new();
+
+    for round in 1..=coverage.max_rounds {
+        let mut map = get_coverage_map(conf, regions, coverage.grid_step);
+        let fraction = get_covered_fraction(&map);
+        info!("Coverage round {}: {:.2}% of limits covered", round, fraction * 100.0);
+
+        if fraction >= coverage.target {
+            info!("Target coverage of {:.2}% reached", coverage.target * 100.0);
+            return;
+        }
+
+        let seeds = get_seeds(&mut map, &mut tried);
+        if seeds.is_empty() {
+            info!("No uncovered area is large enough to be seeded");
+            return;
+        }
+
+        info!("Seeding {} new origins", seeds.len());
+        let new_regions = get_regions(conf, &seeds);
+
+        let largest = new_regions.iter().map(get_region_size).fold(0.0, f64::max);
+        regions.extend(new_regions);
+
+        if largest < delta {
+            info!("New regions are smaller than delta={}; stopping", delta);
+            return;
+        }
+    }
+
+    let map = get_coverage_map(conf, regions, coverage.grid_step);
+    info!(
+        "Maximal number of coverage rounds reached with {:.2}% of limits covered",
+        get_covered_fraction(&map) * 100.0);
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::origins::Origins;
+    use crate::data::region::Delta;
+    use crate::nu::{ContourConfiguration, StabilityBoundary};
+    use crate::systems::test_system;
+    use crate::types::Limits;
+
+    /* Unstable origin, whose region leaves the stable part of the limits uncovered */
+    const ORIGIN: Par = (0.1, 0.1);
+
+    fn get_conf(delta: f64) -> RegionConfiguration {
+        RegionConfiguration {
+            name: "test_coverage",
+            system: test_system::SYSTEM,
+            limits: Limits { p1_min: 0.0, p1_max: 1.0, p2_min: 0.0, p2_max: 1.0 },
+            safeguard: 0.9,
+            check_obsoletion: false,
+            origins: Origins::Manual(vec![ORIGIN]),
+            contour_conf: ContourConfiguration {
+                indentation_radius: 1e-3,
+                singular_points: vec![],
+                w_max: 1e5,
+                steps: 1_000,
+                boundary: StabilityBoundary::ImaginaryAxis,
+                },
+            delta: Delta::Abs(delta),
+            spawn_count: 32,
+            enforce_limits: false,
+            lin_steps: 1_000,
+            log_space_minw: 1e-3,
+            log_space_maxw: 1e5,
+            log_space_steps: 1_000,
+            certified_minimum: false,
+            max_iter: Option::Some(3),
+            nu_modifier: 0,
+            q: 2.0,
+            scaling: (1.0, 1.0),
+            coverage: Option::None,
+        }
+    }
+
+    fn get_origin_count(regions: &[Region]) -> usize {
+        regions.iter().map(|region| region.origins.len()).sum()
+    }
+
+    fn get_coverage(target: f64, max_rounds: u32) -> CoverageConfiguration {
+        CoverageConfiguration { target, grid_step: 11, max_rounds }
+    }
+
+    #[test]
+    fn test_target_coverage_reached() {
+        let conf = get_conf(1e-3);
+        let regions = get_regions(&conf, &[ORIGIN]);
+        let fraction = get_covered_fraction(&get_coverage_map(&conf, &regions, 11));
+        assert!(fraction > 0.0 && fraction < 1.0);
+
+        /* Nothing is seeded once the target is reached */
+        let mut covered = get_regions(&conf, &[ORIGIN]);
+        extend_coverage(&conf, &get_coverage(fraction, 5), &mut covered);
+        assert_eq!(get_origin_count(&covered), get_origin_count(&regions));
+
+        /* A higher target makes uncovered areas seeded */
+        let mut extended = get_regions(&conf, &[ORIGIN]);
+        extend_coverage(&conf, &get_coverage(1.0, 1), &mut extended);
+        assert!(get_origin_count(&extended) > get_origin_count(&regions));
+        let extended_fraction = get_covered_fraction(&get_coverage_map(&conf, &extended, 11));
+        assert!(extended_fraction > fraction);
+    }
+
+    #[test]
+    fn test_small_regions_stop() {
+        /* With a small delta, seeding continues after the first round */
+        let conf = get_conf(1e-3);
+        let mut one_round = Vec::new();
+        extend_coverage(&conf, &get_coverage(1.0, 1), &mut one_round);
+        let mut more_rounds = Vec::new();
+        extend_coverage(&conf, &get_coverage(1.0, 3), &mut more_rounds);
+        assert!(!one_round.is_empty());
+        assert!(get_origin_count(&more_rounds) > get_origin_count(&one_round));
+
+        /* New regions smaller than delta stop seeding after the first round */
+        let conf = get_conf(10.0);
+        let mut one_round = Vec::new();
+        extend_coverage(&conf, &get_coverage(1.0, 1), &mut one_round);
+        let mut more_rounds = Vec::new();
+        extend_coverage(&conf, &get_coverage(1.0, 3), &mut more_rounds);
+        assert!(!one_round.is_empty());
+        assert!(one_round.iter().all(|region| get_region_size(region) < 10.0));
+        assert_eq!(get_origin_count(&more_rounds), get_origin_count(&one_round));
+    }
+}
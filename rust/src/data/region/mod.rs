@@ -1,22 +1,24 @@
 mod configurations;
+mod coverage;
+mod store;
 
-use std::borrow::BorrowMut;
-use std::sync::{Arc, Mutex, RwLock};
+use std::sync::RwLock;
 use iter_num_tools::lin_space;
 use std::f64::consts::PI;
-use log::{debug, info};
+use log::info;
 use serde::Serialize;
-use std::collections::VecDeque;
-use std::{fs, slice};
 
 use crate::nu;
-use crate::types::{Comp, Limits, Par, System};
-use crate::utils::optimization::MinimizationProblemFast;
+use crate::types::{Limits, Par};
+use crate::utils::optimization::{
+    BatchFraction, BrentSearch, CertifiedSearch, MinimizationResult, MinimizationStrategy};
+use crate::utils::batch::{CompBatch, FrequencyGrid};
 use crate::utils::{geometry, optimization, storage};
 use crate::Args;
 use rayon::{prelude::*, ScopeFifo};
-use configurations::{Delta, RegionConfiguration, CONFIGURATONS};
-use crate::systems::distributed_delay1;
+use store::PRegionStore;
+pub use coverage::extend_coverage;
+pub use configurations::{CoverageConfiguration, Delta, RegionConfiguration, CONFIGURATONS};
 
 
 #[derive(Serialize, Debug)]
@@ -32,69 +34,135 @@ pub struct PRegion {
     pub origin: Par,
     pub radius: f64,
     pub depth: u32,
+    pub q: f64,
+    pub scaling: Par,
 }
 
 #[derive(Serialize, Debug)]
 pub struct Region {
     pub pregions: Vec<PRegion>,
     pub nu: i32,
-    pub origin: Par,
+    pub origins: Vec<Par>,
 }
 
 
 impl PRegion {
     fn spawn_edge_points(&self, point_count: usize) -> Vec<Par> {
         let angles = lin_space(0.0..2.0*PI, point_count);
-        let angles2 = angles.clone();
-        let p1_offsets = angles.into_iter().map(|angle| self.radius * f64::cos(angle));
-        let p2_offsets = angles2.into_iter().map(|angle| self.radius * f64::sin(angle));
-        let it = p1_offsets.zip(p2_offsets);
-        it.map(|(p1_offset, p2_offset)| (self.origin.0 + p1_offset, self.origin.1 + p2_offset))
+
+        /* Directions are scaled so that they reach the boundary of the q-ball */
+        angles
+            .into_iter()
+            .map(|angle| {
+                let direction = (f64::cos(angle), f64::sin(angle));
+                let scale = self.radius / geometry::q_norm(direction, self.q);
+                let offset = (scale * direction.0 * self.scaling.0, scale * direction.1 * self.scaling.1);
+                (self.origin.0 + offset.0, self.origin.1 + offset.1)
+            })
             .collect()
     }
 
-    fn is_point_inside(&self, p: Par) -> bool {
+    pub fn is_point_inside(&self, p: Par) -> bool {
         const SAFEGUARD: f64 = 0.99;
-        let x = f64::abs(self.origin.0 - p.0);
-        let y = f64::abs(self.origin.1 - p.1);
-        let distance = f64::sqrt(x.powi(2) + y.powi(2));
+        let offset = ((self.origin.0 - p.0) / self.scaling.0, (self.origin.1 - p.1) / self.scaling.1);
+        let distance = geometry::q_norm(offset, self.q);
         distance < self.radius * SAFEGUARD
     }
 }
 
 
-fn check_jump_validity (
+/* Minimum of the fraction over frequencies, which must exceed eps for a pregion of radius eps */
+fn find_jump_minimum(
     conf: &RegionConfiguration,
     origin: Par,
     eps: f64,
     precalculated_numerator: &[f64],
-    log_space: &[f64],
-) -> bool
+    grid: &FrequencyGrid,
+) -> f64
 {
-    let minimization_problem = MinimizationProblemFast {
-        log_space: log_space,
-        lin_steps: conf.lin_steps,
-        logspace_fraction_iterator: (conf.system.region_fraction_precalculated_numerator.unwrap())(
-                precalculated_numerator,
-                log_space,
-                origin,
-                eps),
-        linspace_fraction_generator: {
-            Box::new(move |w_linspace| conf.system.region_fraction.unwrap()(w_linspace, origin, eps))
+    let log_space = &grid.w;
+    if !conf.uses_system_fractions() {
+        return find_minimum_gradient(conf, origin, eps, precalculated_numerator, log_space).min;
+    }
+
+    let region_fraction_precalculated_numerator = conf.system.region_fraction_precalculated_numerator.unwrap();
+    let region_fraction = conf.system.region_fraction.unwrap();
+    let logspace_fraction = |w: &[f64], out: &mut [f64]| {
+        region_fraction_precalculated_numerator(precalculated_numerator, w, &grid.kernels, origin, eps, out)
+    };
+    let fraction = |w: &[f64], out: &mut [f64]| region_fraction(w, origin, eps, out);
+    let problem = BatchFraction {
+        log_space,
+        logspace_fraction_function: &logspace_fraction,
+        fraction_function: &fraction,
+    };
+
+    /* Frequencies outside of the logspace are covered by tail bounds, if the system has them */
+    let tails = conf.system.region_fraction_tails
+        .map(|tails| move |w_min: f64, w_max: f64| tails(w_min, w_max, origin, eps));
+    match conf.certified_minimum {
+        true => {
+            let lipschitz = conf.system.region_fraction_lipschitz.expect("System must have lipschitz impl");
+            let lipschitz = |w_min: f64, w_max: f64| lipschitz(w_min, w_max, origin, eps);
+            let strategy = CertifiedSearch {
+                lipschitz_function: &lipschitz,
+                threshold: Option::Some(eps),
+            };
+            optimization::minimize_with_tails(&problem, &strategy, tails.as_ref(), eps)
         },
+        false => {
+            let strategy = BrentSearch { lin_steps: conf.lin_steps };
+            optimization::minimize_with_tails(&problem, &strategy, tails.as_ref(), eps)
+        },
+    }
+}
+
+
+/* Minimum of the fraction along a general D-region boundary, for a general scaled q-ball; by
+ * Hölder's inequality, the change of f over the ball is bounded by the dual norm of its gradient,
+ * weighted by the scaling */
+fn find_minimum_gradient(
+    conf: &RegionConfiguration,
+    origin: Par,
+    eps: f64,
+    precalculated_numerator: &[f64],
+    log_space: &[f64],
+) -> MinimizationResult
+{
+    let boundary = conf.contour_conf.boundary;
+    let region_gradient = conf.system.region_gradient.expect("System must have region gradient impl");
+    let dual_q = geometry::dual_exponent(conf.q);
+    let (a1, a2) = conf.scaling;
+    let denominator = |w: f64| {
+        let (gradient_p1, gradient_p2) = region_gradient(boundary.point(w), origin, (a1 * eps, a2 * eps));
+        geometry::q_norm((a1 * gradient_p1, a2 * gradient_p2), dual_q)
+    };
+    let logspace_fraction = |w: &[f64], out: &mut [f64]| {
+        for ((out, num), w) in out.iter_mut().zip(precalculated_numerator).zip(w) {
+            *out = num / denominator(*w);
+        }
+    };
+    let fraction = |w: &[f64], out: &mut [f64]| {
+        for (out, w) in out.iter_mut().zip(w) {
+            *out = (conf.system.f_complex)(boundary.point(*w), origin).norm() / denominator(*w);
+        }
     };
 
-    let min = optimization::find_minimum_fraction_fast(minimization_problem);
+    let problem = BatchFraction {
+        log_space,
+        logspace_fraction_function: &logspace_fraction,
+        fraction_function: &fraction,
+    };
 
-    eps < min
+    BrentSearch { lin_steps: conf.lin_steps }.minimize(&problem)
 }
 
 
-fn get_limiting_eps(p: Par, limits: &Limits) -> f64 {
-    let p1min_distance = f64::abs(p.0 - limits.p1_min);
-    let p1max_distance = f64::abs(p.0 - limits.p1_max);
-    let p2min_distance = f64::abs(p.1 - limits.p2_min);
-    let p2max_distance = f64::abs(p.1 - limits.p2_max);
+fn get_limiting_eps(p: Par, limits: &Limits, scaling: Par) -> f64 {
+    let p1min_distance = f64::abs(p.0 - limits.p1_min) / scaling.0;
+    let p1max_distance = f64::abs(p.0 - limits.p1_max) / scaling.0;
+    let p2min_distance = f64::abs(p.1 - limits.p2_min) / scaling.1;
+    let p2max_distance = f64::abs(p.1 - limits.p2_max) / scaling.1;
     let p1_limiting_eps = f64::min(p1min_distance, p1max_distance);
     let p2_limiting_eps = f64::min(p2min_distance, p2max_distance);
 
@@ -102,9 +170,9 @@ fn get_limiting_eps(p: Par, limits: &Limits) -> f64 {
 }
 
 
-fn delta_rel2abs(delta: f64, limits: &Limits) -> f64 {
-    let p1span = limits.p1_max - limits.p1_min;
-    let p2span = limits.p2_max - limits.p2_min;
+fn delta_rel2abs(delta: f64, limits: &Limits, scaling: Par) -> f64 {
+    let p1span = (limits.p1_max - limits.p1_min) / scaling.0;
+    let p2span = (limits.p2_max - limits.p2_min) / scaling.1;
     let p1delta = p1span * delta;
     let p2delta = p2span * delta;
 
@@ -112,169 +180,229 @@ fn delta_rel2abs(delta: f64, limits: &Limits) -> f64 {
 }
 
 
-fn get_pregion(
+pub fn get_pregion(
     conf: &RegionConfiguration,
     origin: Par,
     enforce_limits: bool,
     delta_abs: f64,
-    log_space: &[f64],
+    grid: &FrequencyGrid,
     depth: u32,
+    initial_radius: Option<f64>,
 ) -> PRegion
 {
-    let precalculated_numerator: Vec<f64> = log_space
-        .iter()
-        .map(|w| (conf.system.f_complex)(Comp::new(0.0, *w), origin).norm())
-        .collect();
-
-    let condition = |eps| check_jump_validity(
-        conf,
-        origin,
-        eps,
-        &precalculated_numerator,
-        log_space);
+    let log_space = &grid.w;
+    let boundary = conf.contour_conf.boundary;
+    let values = CompBatch::evaluate(log_space, |w| (conf.system.f_complex)(boundary.point(w), origin));
+    let mut precalculated_numerator = vec![0.0; log_space.len()];
+    values.norms(&mut precalculated_numerator);
+
+    /* Certified searches stop once the threshold is exceeded and tails only bound the minimum */
+    let exact = !conf.uses_system_fractions()
+        || (!conf.certified_minimum && conf.system.region_fraction_tails.is_none());
+    let minimum = |eps| {
+        let minimum = find_jump_minimum(conf, origin, eps, &precalculated_numerator, grid);
+        (minimum, exact)
+    };
 
     let limit = match enforce_limits {
-        true => get_limiting_eps(origin, &conf.limits),
+        true => get_limiting_eps(origin, &conf.limits, conf.scaling),
         false => f64::INFINITY,
     };
     info!("Finding pregion for origin {:?}; delta={}, limit={}", origin, delta_abs, limit);
-    let radius = optimization::get_maximum_condition(condition, delta_abs, limit);
 
-    PRegion { origin, radius: radius * conf.safeguard, depth }
+    /* Neighbouring pregions tend to be of similar size, so the parent's radius is a good guess */
+    let initial_radius = initial_radius.unwrap_or(delta_abs);
+    let radius = optimization::get_maximum_fixed_point(minimum, initial_radius, delta_abs, limit);
+
+    PRegion { origin, radius: radius * conf.safeguard, depth, q: conf.q, scaling: conf.scaling }
 }
 
 
-pub fn absolutize_delta(delta: &Delta, limits: &Limits) -> f64 {
+/* Radii of pregions are measured in scaled parameters, and so is the absolute delta */
+pub fn absolutize_delta(delta: &Delta, limits: &Limits, scaling: Par) -> f64 {
     match delta {
         Delta::Abs(abs) => *abs,
-        Delta::Rel(rel) => delta_rel2abs(*rel, limits),
+        Delta::Rel(rel) => delta_rel2abs(*rel, limits, scaling),
     }
 }
 
 
-pub fn spawn_valid_points<'a, I>(
-    pregion: &'a PRegion,
-    conf: &'a RegionConfiguration,
-    existing_pregs: &'a I,
-) -> impl Iterator<Item=Par> + 'a
-where
-    for<'b> &'b I: IntoIterator<Item=&'b PRegion>
+/* Split edge points of a new pregion into uncovered points and seeds whose pregions cover some */
+pub fn spawn_valid_points(
+    pregion: &PRegion,
+    conf: &RegionConfiguration,
+    store: &PRegionStore,
+    seed: usize,
+) -> (Vec<Par>, Vec<usize>)
 {
-    let new_points = pregion.spawn_edge_points(conf.spawn_count);
-    let valid_points = new_points
-        .into_iter()
-        .filter(move |p| {
-            (&existing_pregs).into_iter().map(|preg| !preg.is_point_inside(*p)).all(|b| b)})
-        .filter(move |p| geometry::is_point_in_limits(*p, &conf.limits));
+    let mut valid_points = Vec::new();
+    let mut touching_seeds = Vec::new();
+
+    for p in pregion.spawn_edge_points(conf.spawn_count) {
+        match store.find_covering(p, |_| true) {
+            Some(other) => if other != seed { touching_seeds.push(other) },
+            None => if geometry::is_point_in_limits(p, &conf.limits) { valid_points.push(p) },
+        }
+    }
 
-    valid_points
+    (valid_points, touching_seeds)
+}
+
+
+/* State shared by all tasks searching for regions of the same nu */
+pub struct RegionContext<'a> {
+    conf: &'a RegionConfiguration,
+    store: RwLock<PRegionStore>,
+    delta: f64,
+    grid: &'a FrequencyGrid,
 }
 
 
 pub fn get_region_parallel<'a>(
     scope: &ScopeFifo<'a>,
-    conf: &'a RegionConfiguration,
+    context: &'a RegionContext<'a>,
     origin: Par,
-    pregions: &'a Arc<RwLock<Vec<PRegion>>>,
-    delta: f64,
-    log_space: &'a [f64],
+    seed: usize,
     depth: u32,
-    max_depth: Option<u32>,
+    parent_radius: Option<f64>,
 )
 {
+    let (conf, store, delta, grid) = (context.conf, &context.store, context.delta, context.grid);
+
     /* Check if the point is obsolete */
-    {
-        let pregions_unlocked = pregions.read().unwrap();
+    let covering_seed = {
+        let store_unlocked = store.read().unwrap();
 
         /* Check if it has been enough */
         const THRESHOLD: usize = 9999999;
-        if pregions_unlocked.len() > THRESHOLD { return }
-
-        /* Check if the point is obsolete */
-        if pregions_unlocked
-            .iter()
-            .map(|preg|
-                /* If obsoletion is not to be fully checked, then consider only shallower pregs */
-                preg.is_point_inside(origin) && (preg.depth < depth || conf.check_obsoletion))
-            .any(|b| b)
-        
-        {
-            return;
+        if store_unlocked.pregion_count() > THRESHOLD { return }
+
+        /* If obsoletion is not to be fully checked, then consider only shallower pregs */
+        store_unlocked.find_covering(
+            origin,
+            |preg| preg.depth < depth || conf.check_obsoletion)
+    };
+
+    /* A point covered by another seed's pregion means that the two regions touch */
+    if let Some(covering_seed) = covering_seed {
+        if covering_seed != seed {
+            store.write().unwrap().link(seed, covering_seed);
         }
+        return;
     }
 
     /* Find new PRegion around the point */
-    let pregion = get_pregion(conf, origin, conf.enforce_limits, delta, log_space, depth);
+    let pregion = get_pregion(conf, origin, conf.enforce_limits, delta, grid, depth, parent_radius);
+    let radius = pregion.radius;
 
-    let new_points = {
-        let pregions_unlocked = pregions.read().unwrap();
+    let (new_points, touching_seeds) = {
+        let store_unlocked = store.read().unwrap();
 
         /* If necessary, spawn new points on edge of the newly obtained PRegion */
-        let depth_ok = if let Some(max_depth) = max_depth { depth < max_depth} else {true};
+        let depth_ok = if let Some(max_depth) = conf.max_iter { depth < max_depth} else {true};
         if pregion.radius > delta && depth_ok {
-            let new_points: Vec<Par> = spawn_valid_points(
-                &pregion,
-                &conf,
-                &*pregions_unlocked)
-                .collect();
-            Some(new_points)
+            spawn_valid_points(&pregion, conf, &store_unlocked, seed)
         }
-        else {None}
+        else {(Vec::new(), Vec::new())}
     };
 
-    /* Add obtained pregion to the Vec - this is the only access that requires write privileges */
+    /* Add obtained pregion to the store - this is the only access that requires write privileges */
     {
-        let mut pregions_unlocked = pregions.write().unwrap();
-        pregions_unlocked.push(pregion);
+        let mut store_unlocked = store.write().unwrap();
+        for other in touching_seeds {
+            store_unlocked.link(seed, other);
+        }
+        store_unlocked.push(pregion, seed);
     }
 
     /* Add new points to the breadth-first evaluation queue */
-    if let Some(new_points) = new_points {
-        if new_points.len() != 0 {
-            for point in new_points {
-                scope.spawn_fifo(move |s| get_region_parallel(
-                    s,
-                    conf,
-                    point,
-                    pregions,
-                    delta,
-                    log_space,
-                    depth+1,
-                    max_depth));
-            }
-        }
+    for point in new_points {
+        scope.spawn_fifo(move |s| get_region_parallel(
+            s,
+            context,
+            point,
+            seed,
+            depth+1,
+            Option::Some(radius)));
     }
 }
 
 
-pub fn get_region(conf: &RegionConfiguration, origin: Par) -> Region {
-    const VEC_PREALLOCATION_SIZE: usize = 10_000;
-
-    let delta = absolutize_delta(&conf.delta, &conf.limits);
-    let nu = nu::calculate_nu_single(&conf.contour_conf, conf.system.f_complex, origin);
-    let nu = nu + conf.nu_modifier; // In case we want to modify nu manually
-    let mut pregions = Arc::new(RwLock::new(Vec::with_capacity(VEC_PREALLOCATION_SIZE)));
-    let w_log_space: Vec<f64> = conf.get_log_space();
+/* Origins of the same nu share a pregion store; those whose pregions touch form one region */
+fn get_regions_with_nu(
+    conf: &RegionConfiguration,
+    origins: &[Par],
+    nu: i32,
+    grid: &FrequencyGrid,
+) -> Vec<Region>
+{
+    let context = RegionContext {
+        conf,
+        store: RwLock::new(PRegionStore::new(origins.len())),
+        delta: absolutize_delta(&conf.delta, &conf.limits, conf.scaling),
+        grid,
+    };
     let initial_depth = 1;
 
     /* The region fsals is parallelized in a breadth-first fashion */
-    info!("Searching for region around {:?} with nu {}", origin, nu);
+    info!("Searching for regions around {:?} with nu {}", origins, nu);
     rayon::scope_fifo(|s| {
-        get_region_parallel(
-            s,
-            conf,
-            origin,
-            &mut pregions,
-            delta,
-            &w_log_space,
-            initial_depth,
-            conf.max_iter,
-        );
+        for (seed, &origin) in origins.iter().enumerate() {
+            let context = &context;
+            s.spawn_fifo(move |s| get_region_parallel(
+                s,
+                context,
+                origin,
+                seed,
+                initial_depth,
+                Option::None));
+        }
     });
-    let pregions = Arc::try_unwrap(pregions).unwrap().into_inner().unwrap();
-    info!("Returning region around {:?} with {:?} pregions", origin, pregions.len());
+    let store = context.store.into_inner().unwrap();
+
+    store
+        .into_groups()
+        .into_iter()
+        .map(|(seeds, pregions)| {
+            let origins: Vec<Par> = seeds.iter().map(|&seed| origins[seed]).collect();
+            info!("Returning region around {:?} with {:?} pregions", origins, pregions.len());
+            Region { pregions, nu, origins }
+        })
+        .collect()
+}
 
-    Region { pregions, nu, origin }
+
+pub fn get_regions(conf: &RegionConfiguration, origins: &[Par]) -> Vec<Region> {
+    if conf.q < 1.0 {
+        panic!("Pregions must be q-balls with q in [1, inf]");
+    }
+    if !conf.uses_system_fractions() && conf.certified_minimum {
+        panic!("Certified minimization is supported only for unscaled disks and the imaginary axis boundary");
+    }
+
+    /* Zeros common to all parameters are not poles */
+    let nus: Vec<i32> = origins
+        .par_iter()
+        .map(|&origin| nu::calculate_nu_single(&conf.contour_conf, &conf.system, origin))
+        .map(|nu| nu + conf.nu_modifier)
+        .collect();
+
+    /* Group origins by nu, in order of appearance */
+    let mut groups: Vec<(i32, Vec<Par>)> = Vec::new();
+    for (&origin, &nu) in origins.iter().zip(nus.iter()) {
+        match groups.iter_mut().find(|(group_nu, _)| *group_nu == nu) {
+            Some((_, group)) => group.push(origin),
+            None => groups.push((nu, vec![origin])),
+        }
+    }
+
+    /* Frequency kernels do not depend on parameters, so all regions can share them */
+    let grid = conf.get_frequency_grid();
+
+    groups
+        .par_iter()
+        .flat_map(|(nu, group)| get_regions_with_nu(conf, group, *nu, &grid))
+        .collect()
 }
 
 
@@ -312,14 +440,16 @@ pub fn run_region(args: &Args) {
     let config = args2config(args);
 
     /* Determine maximal stability equivalence region surrounding each given origin */
-    let regions = config
-        .origins
-        .clone()
-        .into_par_iter()
-        .map(|origin| get_region(config, origin));
+    let origins = config.origins.get_origins(&config.system, &config.limits, &config.contour_conf);
+    let mut regions = get_regions(config, &origins);
+
+    /* Fill holes left between the regions, if requested */
+    if let Some(coverage) = &config.coverage {
+        coverage::extend_coverage(config, coverage, &mut regions);
+    }
 
     let results = RegionResult {
-        regions: regions.collect(),
+        regions,
         limits: &config.limits,
         parameters: config.system.parameters,
     };
@@ -328,4 +458,59 @@ pub fn run_region(args: &Args) {
     optimization::print_minmax_statistics();
 
     store_results(&results, &config);
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CERTIFIED_PAIRS: [(&str, &str); 2] = [
+        ("retarded1", "retarded1_certified"),
+        ("test_configuration", "test_configuration_certified"),
+    ];
+
+    #[test]
+    fn test_certified_jump_minimum() {
+        for (brent_name, certified_name) in CERTIFIED_PAIRS {
+            let brent_conf = &CONFIGURATONS[brent_name];
+            let certified_conf = &CONFIGURATONS[certified_name];
+            let grid = brent_conf.get_frequency_grid();
+
+            let origins = brent_conf.origins.get_origins(&brent_conf.system, &brent_conf.limits, &brent_conf.contour_conf);
+            for origin in origins {
+                let boundary = brent_conf.contour_conf.boundary;
+                let values = CompBatch::evaluate(&grid.w, |w| (brent_conf.system.f_complex)(boundary.point(w), origin));
+                let mut numerator = vec![0.0; grid.w.len()];
+                values.norms(&mut numerator);
+
+                /* The certified minimum is a lower bound, Brent's one is attained by a sample */
+                for eps in [1e-3, 1e-2, 1e-1] {
+                    let brent = find_jump_minimum(brent_conf, origin, eps, &numerator, &grid);
+                    let certified = find_jump_minimum(certified_conf, origin, eps, &numerator, &grid);
+                    assert!(certified <= brent,
+                        "{} at {:?}, eps={}: certified {} above Brent {}", certified_name, origin, eps, certified, brent);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_certified_radii() {
+        for (brent_name, certified_name) in CERTIFIED_PAIRS {
+            let brent_conf = &CONFIGURATONS[brent_name];
+            let certified_conf = &CONFIGURATONS[certified_name];
+            let grid = brent_conf.get_frequency_grid();
+            let delta = absolutize_delta(&brent_conf.delta, &brent_conf.limits, brent_conf.scaling);
+
+            let origins = brent_conf.origins.get_origins(&brent_conf.system, &brent_conf.limits, &brent_conf.contour_conf);
+            for origin in origins {
+                let brent = get_pregion(brent_conf, origin, false, delta, &grid, 1, Option::None);
+                let certified = get_pregion(certified_conf, origin, false, delta, &grid, 1, Option::None);
+
+                /* Certified radii are smaller, yet they must not collapse to the resolution */
+                assert!(certified.radius <= brent.radius + delta);
+                assert!(certified.radius > 0.5 * brent.radius);
+            }
+        }
+    }
+}
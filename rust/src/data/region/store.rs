@@ -0,0 +1,174 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::types::Par;
+use super::PRegion;
+
+
+/* Pregions shared by all seed origins of the same nu, bucketed for fast point queries.
+ * Each pregion lives on the level whose bucket size exceeds its bounding box, in the bucket
+ * containing its origin, so a point can only be covered by pregions from neighbouring buckets */
+pub struct PRegionStore {
+    pregions: Vec<PRegion>,
+    seeds: Vec<usize>,
+    buckets: HashMap<(i32, i64, i64), Vec<usize>>,
+    levels: BTreeSet<i32>,
+    links: Vec<usize>,
+}
+
+
+impl PRegionStore {
+    pub fn new(seed_count: usize) -> PRegionStore {
+        PRegionStore {
+            pregions: Vec::new(),
+            seeds: Vec::new(),
+            buckets: HashMap::new(),
+            levels: BTreeSet::new(),
+            links: (0..seed_count).collect(),
+        }
+    }
+
+    fn get_bucket(p: Par, level: i32) -> (i32, i64, i64) {
+        let size = f64::powi(2.0, level);
+        (level, (p.0 / size).floor() as i64, (p.1 / size).floor() as i64)
+    }
+
+    pub fn pregion_count(&self) -> usize {
+        self.pregions.len()
+    }
+
+    pub fn push(&mut self, pregion: PRegion, seed: usize) {
+        const MIN_SIZE: f64 = 1e-12;
+        let width = 2.0 * pregion.radius * f64::max(pregion.scaling.0, pregion.scaling.1);
+        let level = width.max(MIN_SIZE).log2().ceil() as i32;
+
+        self.levels.insert(level);
+        self.buckets
+            .entry(Self::get_bucket(pregion.origin, level))
+            .or_default()
+            .push(self.pregions.len());
+        self.pregions.push(pregion);
+        self.seeds.push(seed);
+    }
+
+    /* Seed of any pregion which contains given point and satisfies given predicate */
+    pub fn find_covering<F>(&self, p: Par, predicate: F) -> Option<usize>
+    where
+        F: Fn(&PRegion) -> bool
+    {
+        for &level in &self.levels {
+            let (_, i, j) = Self::get_bucket(p, level);
+            for bucket_i in i-1..=i+1 {
+                for bucket_j in j-1..=j+1 {
+                    let Some(indices) = self.buckets.get(&(level, bucket_i, bucket_j)) else {
+                        continue;
+                    };
+                    for &index in indices {
+                        let pregion = &self.pregions[index];
+                        if pregion.is_point_inside(p) && predicate(pregion) {
+                            return Some(self.seeds[index]);
+                        }
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    fn find_root(&self, seed: usize) -> usize {
+        let mut root = seed;
+        while self.links[root] != root {
+            root = self.links[root];
+        }
+        root
+    }
+
+    /* Mark pregions of two seeds as touching, i.e. belonging to the same region */
+    pub fn link(&mut self, a: usize, b: usize) {
+        let (root_a, root_b) = (self.find_root(a), self.find_root(b));
+        if root_a != root_b {
+            self.links[root_a.max(root_b)] = root_a.min(root_b);
+        }
+    }
+
+    /* Split pregions into groups of linked seeds, ordered by their first seed */
+    pub fn into_groups(self) -> Vec<(Vec<usize>, Vec<PRegion>)> {
+        let roots: Vec<usize> = (0..self.links.len()).map(|seed| self.find_root(seed)).collect();
+        let mut groups: Vec<(Vec<usize>, Vec<PRegion>)> = Vec::new();
+        let mut group_of_root: HashMap<usize, usize> = HashMap::new();
+
+        for (seed, &root) in roots.iter().enumerate() {
+            let group = *group_of_root.entry(root).or_insert_with(|| {
+                groups.push((Vec::new(), Vec::new()));
+                groups.len() - 1
+            });
+            groups[group].0.push(seed);
+        }
+
+        for (pregion, seed) in self.pregions.into_iter().zip(self.seeds) {
+            groups[group_of_root[&roots[seed]]].1.push(pregion);
+        }
+
+        groups
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_disk(origin: Par, radius: f64, depth: u32) -> PRegion {
+        PRegion { origin, radius, depth, q: 2.0, scaling: (1.0, 1.0) }
+    }
+
+    #[test]
+    fn test_find_covering() {
+        let mut store = PRegionStore::new(3);
+        store.push(get_disk((0.0, 0.0), 1.0, 1), 0);
+        store.push(get_disk((10.0, 10.0), 0.01, 2), 1);
+        store.push(get_disk((-5.0, 3.0), 40.0, 3), 2);
+
+        /* Levels are picked by size, so the store spans widely different buckets */
+        assert_eq!(store.pregion_count(), 3);
+        assert_eq!(store.levels.len(), 3);
+
+        assert_eq!(store.find_covering((0.5, 0.5), |preg| preg.depth == 1), Some(0));
+        assert_eq!(store.find_covering((10.005, 9.995), |preg| preg.depth < 3), Some(1));
+        assert_eq!(store.find_covering((30.0, -10.0), |_| true), Some(2));
+        assert_eq!(store.find_covering((0.5, 0.5), |preg| preg.depth > 1), Some(2));
+        assert_eq!(store.find_covering((0.5, 0.5), |preg| preg.depth == 2), None);
+        assert_eq!(store.find_covering((100.0, 100.0), |_| true), None);
+
+        /* The point lies in a different bucket than the origin of the covering pregion */
+        let mut store = PRegionStore::new(1);
+        store.push(get_disk((1.95, 1.95), 0.5, 1), 0);
+        assert_eq!(store.find_covering((2.2, 2.2), |_| true), Some(0));
+        assert_eq!(store.find_covering((1.7, 2.2), |_| true), Some(0));
+        assert_eq!(store.find_covering((2.5, 2.5), |_| true), None);
+    }
+
+    #[test]
+    fn test_into_groups() {
+        let mut store = PRegionStore::new(5);
+        for seed in 0..5 {
+            store.push(get_disk((seed as f64, 0.0), 0.1, 1), seed);
+            store.push(get_disk((seed as f64, 1.0), 0.1, 2), seed);
+        }
+
+        /* Links are transitive, symmetric and may repeat */
+        store.link(4, 2);
+        store.link(2, 0);
+        store.link(0, 4);
+        store.link(3, 3);
+
+        let groups = store.into_groups();
+        let seeds: Vec<Vec<usize>> = groups.iter().map(|(seeds, _)| seeds.clone()).collect();
+        assert_eq!(seeds, vec![vec![0, 2, 4], vec![1], vec![3]]);
+
+        for (seeds, pregions) in &groups {
+            assert_eq!(pregions.len(), 2 * seeds.len());
+            assert!(pregions.iter().all(|preg| seeds.contains(&(preg.origin.0 as usize))));
+        }
+    }
+}
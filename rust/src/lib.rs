@@ -0,0 +1,105 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+pub mod nu;
+pub mod roots;
+pub mod spectral_abscissa;
+pub mod crossing_curves;
+pub mod path;
+pub mod certify_box;
+pub mod comparison;
+pub mod data;
+pub mod systems;
+pub mod types;
+pub mod utils;
+
+use clap::{Parser, Subcommand};
+use std::fmt;
+use std::fmt::Display;
+use std::{str::FromStr};
+use log::LevelFilter;
+
+
+#[derive(Parser)]
+#[clap(author = "Vukan Turkulov <vukant@gmail.com>")]
+#[clap(about = "Framework for stability analysis of linear systems")]
+#[clap(name = "fsals")]
+pub struct Args {
+    /// Name of the system which the program should analyze
+    #[clap(short, long)]
+    pub configuration: Option<String>,
+
+    /// Algorithm which should be run on the given system
+    #[clap(short, long)]
+    pub algorithm: Option<Algorithm>,
+
+    /// Logging Level
+    #[clap(short, long, default_value_t = LevelFilter::Info)]
+    pub loglevel: LevelFilter,
+
+    /// Parallelize code execution
+    #[clap(short, long)]
+    pub parallel: bool,
+
+    /// Save verbose data as output
+    #[clap(short, long)]
+    pub verbose_data: bool,
+
+    /// Main command that should be run
+    #[clap(subcommand)]
+    pub command: Command,
+}
+
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Determine number of unstable poles for the given system using Cauchy's argument principle
+    Nu,
+    /// Locate unstable zeros of the characteristic function along with their multiplicities
+    Roots,
+    /// Calculate the real part of the rightmost characteristic root over a parametric grid
+    SpectralAbscissa,
+    /// Trace parametric curves on which a characteristic root crosses the imaginary axis
+    CrossingCurves,
+    /// Calculate stability crossing curves of a two-delay system using the Gu (2005) method
+    Gu2005,
+    /// Certify that the number of unstable poles is constant along a piecewise linear path
+    Path,
+    /// Certify that the number of unstable poles is constant over a box or a convex polygon
+    CertifyBox,
+    /// Run the specified algorithm
+    Data,
+    /// Run a custom snippet of Rust code
+    Custom,
+}
+
+
+#[derive(Debug)]
+pub enum Algorithm {
+    /// Line fsals algorithm
+    Line,
+    /// Region fsals algorithm
+    Region,
+}
+
+
+impl FromStr for Algorithm {
+    type Err = String;
+    fn from_str(input: &str) -> Result<Algorithm, Self::Err> {
+        match input {
+            "line"  => Ok(Algorithm::Line),
+            "region"  => Ok(Algorithm::Region),
+            _      => Err(String::from("unknown algorithm"))
+        }
+    }
+}
+
+
+impl Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+       match *self {
+           Algorithm::Line => write!(f, "line"),
+           Algorithm::Region => write!(f, "region"),
+       }
+    }
+}
@@ -1,93 +1,11 @@
-#![allow(dead_code)]
-#![allow(unused_imports)]
-
-mod nu;
-mod data;
-mod systems;
-mod types;
-mod utils;
-
-use clap::{Parser, Subcommand};
-use std::fmt;
-use std::fmt::Display;
-use std::{str::FromStr};
-use log::{info, LevelFilter};
+use clap::Parser;
+use log::info;
 use std::time::Instant;
 use std::panic;
 use std::process;
 
-
-#[derive(Parser)]
-#[clap(author = "Vukan Turkulov <vukant@gmail.com>")]
-#[clap(about = "Framework for stability analysis of linear systems")]
-#[clap(name = "fsals")]
-pub struct Args {
-    /// Name of the system which the program should analyze
-    #[clap(short, long)]
-    configuration: Option<String>,
-
-    /// Algorithm which should be run on the given system
-    #[clap(short, long)]
-    algorithm: Option<Algorithm>,
-
-    /// Logging Level
-    #[clap(short, long, default_value_t = LevelFilter::Info)]
-    loglevel: LevelFilter,
-
-    /// Parallelize code execution
-    #[clap(short, long)]
-    parallel: bool,
-
-    /// Save verbose data as output
-    #[clap(short, long)]
-    verbose_data: bool,
-
-    /// Main command that should be run
-    #[clap(subcommand)]
-    command: Command,
-}
-
-
-#[derive(Subcommand, Debug)]
-pub enum Command {
-    /// Determine number of unstable poles for the given system using Cauchy's argument principle
-    Nu,
-    /// Run the specified algorithm
-    Data,
-    /// Run a custom snippet of Rust code
-    Custom,
-}
-
-
-#[derive(Debug)]
-pub enum Algorithm {
-    /// Line fsals algorithm
-    Line,
-    /// Region fsals algorithm
-    Region,
-}
-
-
-impl FromStr for Algorithm {
-    type Err = String;
-    fn from_str(input: &str) -> Result<Algorithm, Self::Err> {
-        match input {
-            "line"  => Ok(Algorithm::Line),
-            "region"  => Ok(Algorithm::Region),
-            _      => Err(String::from("unknown algorithm"))
-        }
-    }
-}
-
-
-impl Display for Algorithm {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-       match *self {
-           Algorithm::Line => write!(f, "line"),
-           Algorithm::Region => write!(f, "region"),
-       }
-    }
-}
+use fsals::{Args, Command};
+use fsals::{nu, roots, spectral_abscissa, crossing_curves, path, certify_box, comparison, data};
 
 
 fn print_args_verbose(args: &Args) {
@@ -133,6 +51,12 @@ fn main() {
 
     match args.command {
         Command::Nu => nu::run(&args),
+        Command::Roots => roots::run(&args),
+        Command::SpectralAbscissa => spectral_abscissa::run(&args),
+        Command::CrossingCurves => crossing_curves::run(&args),
+        Command::Gu2005 => comparison::run_gu2005(&args),
+        Command::Path => path::run(&args),
+        Command::CertifyBox => certify_box::run(&args),
         Command::Data => data::run(&args),
         Command::Custom => info!("Placeholder for running custom commands"),
     };
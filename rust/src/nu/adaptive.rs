@@ -0,0 +1,143 @@
+use log::{debug, info};
+use std::collections::HashMap;
+use rayon::prelude::*;
+
+use crate::types::Par;
+use super::{evaluate_point, NuConfiguration, NuPointResult};
+
+
+/* Leaf of the quadtree; nu is None if the corners of the cell disagree even at maximal depth */
+#[derive(Debug, serde::Serialize)]
+pub struct NuCell {
+    p_min: Par,
+    p_max: Par,
+    nu: Option<i32>,
+    depth: u32,
+}
+
+
+/* Cell given by its lower left corner and its size, in units of the finest lattice */
+#[derive(Debug, Clone, Copy)]
+struct LatticeCell {
+    i: u64,
+    j: u64,
+    size: u64,
+    depth: u32,
+}
+
+
+impl LatticeCell {
+    fn corners(&self) -> [(u64, u64); 4] {
+        let (i, j, size) = (self.i, self.j, self.size);
+        [(i, j), (i + size, j), (i, j + size), (i + size, j + size)]
+    }
+
+    fn children(&self) -> [LatticeCell; 4] {
+        let half = self.size / 2;
+        let child = |i, j| LatticeCell { i, j, size: half, depth: self.depth + 1 };
+        [
+            child(self.i, self.j),
+            child(self.i + half, self.j),
+            child(self.i, self.j + half),
+            child(self.i + half, self.j + half),
+        ]
+    }
+}
+
+
+/* Mapping between the finest lattice and the parametric space */
+struct Lattice<'a> {
+    conf: &'a NuConfiguration,
+    size: u64,
+}
+
+
+impl<'a> Lattice<'a> {
+    fn point(&self, node: (u64, u64)) -> Par {
+        let limits = &self.conf.limits;
+        let p1 = limits.p1_min + (limits.p1_max - limits.p1_min) * node.0 as f64 / self.size as f64;
+        let p2 = limits.p2_min + (limits.p2_max - limits.p2_min) * node.1 as f64 / self.size as f64;
+        (p1, p2)
+    }
+
+    fn leaf(&self, cell: &LatticeCell, nu: Option<i32>) -> NuCell {
+        NuCell {
+            p_min: self.point((cell.i, cell.j)),
+            p_max: self.point((cell.i + cell.size, cell.j + cell.size)),
+            nu,
+            depth: cell.depth,
+        }
+    }
+}
+
+
+/* Common nu of all corners, if they agree and are all reliable */
+fn get_common_nu(cell: &LatticeCell, points: &HashMap<(u64, u64), NuPointResult>) -> Option<i32> {
+    let corners: Vec<&NuPointResult> = cell.corners().iter().map(|node| &points[node]).collect();
+    let nu = corners[0].nu;
+
+    match corners.iter().all(|point| point.nu == nu && point.reliable) {
+        true => Some(nu),
+        false => Option::None,
+    }
+}
+
+
+/* Start from the grid of the configuration, and subdivide only the cells whose corners
+ * have differing nu; islands smaller than a grid cell remain undetected */
+pub fn calculate_nu_adaptive(conf: &'static NuConfiguration, max_depth: u32)
+    -> (Vec<NuPointResult>, Vec<NuCell>)
+{
+    if conf.grid_step < 2 {
+        panic!("Adaptive nu requires grid_step of at least 2, got {}", conf.grid_step);
+    }
+    let cell_size = 1u64 << max_depth;
+    let grid_cells = conf.grid_step as u64 - 1;
+    let lattice = Lattice { conf, size: grid_cells * cell_size };
+
+    let mut points: HashMap<(u64, u64), NuPointResult> = HashMap::new();
+    let mut leaves: Vec<NuCell> = Vec::new();
+    let mut cells: Vec<LatticeCell> = (0..grid_cells)
+        .flat_map(|j| (0..grid_cells).map(move |i| (i, j)))
+        .map(|(i, j)| LatticeCell { i: i * cell_size, j: j * cell_size, size: cell_size, depth: 0 })
+        .collect();
+
+    while !cells.is_empty() {
+        /* Evaluate corners which were not evaluated at previous depths */
+        let mut missing: Vec<(u64, u64)> = cells
+            .iter()
+            .flat_map(|cell| cell.corners())
+            .filter(|node| !points.contains_key(node))
+            .collect();
+        missing.sort_unstable();
+        missing.dedup();
+
+        let evaluated: Vec<((u64, u64), NuPointResult)> = missing
+            .into_par_iter()
+            .map(|node| (node, evaluate_point(conf, lattice.point(node))))
+            .collect();
+        debug!("Evaluated {} new points for {} cells", evaluated.len(), cells.len());
+        points.extend(evaluated);
+
+        let mut next_cells = Vec::new();
+        for cell in cells.iter() {
+            let nu = get_common_nu(cell, &points);
+
+            if nu.is_none() && cell.depth < max_depth {
+                next_cells.extend(cell.children());
+            } else {
+                leaves.push(lattice.leaf(cell, nu));
+            }
+        }
+        cells = next_cells;
+    }
+
+    info!("Adaptive nu evaluated {} points, instead of {} on a uniform grid of the same resolution",
+        points.len(), (lattice.size + 1).pow(2));
+
+    let mut nodes: Vec<(u64, u64)> = points.keys().cloned().collect();
+    nodes.sort_unstable_by_key(|node| (node.1, node.0));
+    let point_results = nodes.iter().map(|node| points.remove(node).unwrap()).collect();
+
+    (point_results, leaves)
+}
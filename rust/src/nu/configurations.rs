@@ -1,5 +1,6 @@
 use lazy_static::lazy_static;
 use std::collections::HashMap;
+use std::f64::consts::PI;
 
 use crate::systems::distributed_delay1;
 use crate::systems::finite_rod;
@@ -18,13 +19,53 @@ use crate::systems::dopid2;
 use crate::systems::dopid3;
 use crate::systems::test_system;
 
-use crate::types::{Limits, System};
+use crate::types::{Comp, Limits, System};
 
 
+/* Boundary of the D-region in which zeros are considered stable (left of the boundary) */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StabilityBoundary {
+    /* Re s = 0, i.e. ordinary asymptotic stability */
+    ImaginaryAxis,
+    /* Re s = -alpha, i.e. guaranteed decay rate alpha */
+    ShiftedAxis(f64),
+    /* Rays at angle phi from the negative real axis, i.e. guaranteed damping; meaningful only
+     * for finitely many zeros outside the sector, which does not hold for delay systems */
+    Sector(f64),
+}
+
+
+impl StabilityBoundary {
+    /* Point of the upper half of the boundary at frequency w (modulus of s for sectors) */
+    pub fn point(&self, w: f64) -> Comp {
+        match *self {
+            StabilityBoundary::ImaginaryAxis => Comp::new(0.0, w),
+            StabilityBoundary::ShiftedAxis(alpha) => Comp::new(-alpha, w),
+            StabilityBoundary::Sector(phi) => Comp::from_polar(w, PI - phi),
+        }
+    }
+
+    /* Whether the contour encloses a part of the non-positive real axis, where the principal
+     * branches of ln s and s^a are discontinuous; the origin itself is bypassed only by the
+     * Bromwich contour and the sector contour, the latter however enclosing the negative real
+     * axis for phi <= 0 */
+    pub fn encloses_branch_cut(&self) -> bool {
+        match *self {
+            StabilityBoundary::ImaginaryAxis => false,
+            StabilityBoundary::ShiftedAxis(alpha) => alpha > 0.0,
+            StabilityBoundary::Sector(phi) => phi <= 0.0,
+        }
+    }
+}
+
+
+#[derive(Clone)]
 pub struct ContourConfiguration {
-    pub w_min: f64,
-    pub w_max: f64,
-    pub steps: usize,
+    pub indentation_radius: f64,     // Radius of semicircles bypassing imaginary axis singularities
+    pub singular_points: Vec<f64>,   // Positive w of singularities on the imaginary axis besides 0
+    pub w_max: f64,                  // Radius of the large semicircle closing the contour
+    pub steps: usize,                // Initial point count of each contour piece
+    pub boundary: StabilityBoundary, // Boundary of the D-region, along which f is examined
 }
 
 
@@ -33,6 +74,7 @@ pub struct NuConfiguration {
     pub system: System,
     pub limits: Limits,
     pub grid_step: usize,
+    pub adaptive_depth: Option<u32>, // Maximal number of quadtree subdivisions of grid cells
     pub contour_conf: ContourConfiguration,
 }
 
@@ -48,9 +90,57 @@ lazy_static! {
                 name: "retarded1",
                 system: retarded1::SYSTEM,
                 contour_conf: ContourConfiguration {
-                    w_min: 1e-3,
+                    indentation_radius: 1e-3,
+                    singular_points: vec![],
+                    w_max: 1e5,
+                    steps: 10_000usize,
+                    boundary: StabilityBoundary::ImaginaryAxis,
+                },
+                limits: Limits {
+                    p1_min: 0.0,
+                    p1_max: 2.6,
+                    p2_min: 0.0,
+                    p2_max: 3.3,
+                },
+                grid_step: 40usize,
+                adaptive_depth: Option::None,
+            },
+        );
+
+        configs.insert(
+            "retarded1_adaptive",
+            NuConfiguration {
+                name: "retarded1_adaptive",
+                system: retarded1::SYSTEM,
+                contour_conf: ContourConfiguration {
+                    indentation_radius: 1e-3,
+                    singular_points: vec![],
                     w_max: 1e5,
                     steps: 10_000usize,
+                    boundary: StabilityBoundary::ImaginaryAxis,
+                },
+                limits: Limits {
+                    p1_min: 0.0,
+                    p1_max: 2.6,
+                    p2_min: 0.0,
+                    p2_max: 3.3,
+                },
+                grid_step: 11usize,
+                adaptive_depth: Some(5),
+            },
+        );
+
+        configs.insert(
+            "retarded1_decay",
+            NuConfiguration {
+                name: "retarded1_decay",
+                system: retarded1::SYSTEM,
+                contour_conf: ContourConfiguration {
+                    indentation_radius: 1e-3,
+                    singular_points: vec![],
+                    w_max: 1e3,
+                    steps: 10_000usize,
+                    boundary: StabilityBoundary::ShiftedAxis(0.1),
                 },
                 limits: Limits {
                     p1_min: 0.0,
@@ -59,6 +149,7 @@ lazy_static! {
                     p2_max: 3.3,
                 },
                 grid_step: 40usize,
+                adaptive_depth: Option::None,
             },
         );
 
@@ -68,9 +159,11 @@ lazy_static! {
                 name: "retarded2",
                 system: retarded2::SYSTEM,
                 contour_conf: ContourConfiguration {
-                    w_min: 1e-3,
+                    indentation_radius: 1e-3,
+                    singular_points: vec![],
                     w_max: 1e5,
                     steps: 10_000usize,
+                    boundary: StabilityBoundary::ImaginaryAxis,
                 },
                 limits: Limits {
                     p1_min: 0.0,
@@ -79,6 +172,7 @@ lazy_static! {
                     p2_max: 5.0,
                 },
                 grid_step: 40usize,
+                adaptive_depth: Option::None,
             },
         );
 
@@ -88,9 +182,11 @@ lazy_static! {
                 name: "distributed_delay1",
                 system: distributed_delay1::SYSTEM,
                 contour_conf: ContourConfiguration {
-                    w_min: 1e-3,
+                    indentation_radius: 1e-3,
+                    singular_points: vec![],
                     w_max: 1e5,
                     steps: 10_000usize,
+                    boundary: StabilityBoundary::ImaginaryAxis,
                 },
                 limits: Limits {
                     p1_min: 0.0,
@@ -99,6 +195,7 @@ lazy_static! {
                     p2_max: 0.4,
                 },
                 grid_step: 40usize,
+                adaptive_depth: Option::None,
             },
         );
 
@@ -108,9 +205,11 @@ lazy_static! {
                 name: "semi_infinite_rod",
                 system: semi_infinite_rod::SYSTEM,
                 contour_conf: ContourConfiguration {
-                    w_min: 1e-3,
+                    indentation_radius: 1e-3,
+                    singular_points: vec![],
                     w_max: 1e5,
                     steps: 10_000usize,
+                    boundary: StabilityBoundary::ImaginaryAxis,
                 },
                 limits: Limits {
                     p1_min: 0.0,
@@ -119,6 +218,7 @@ lazy_static! {
                     p2_max: 70_000.0,
                 },
                 grid_step: 40usize,
+                adaptive_depth: Option::None,
             },
         );
 
@@ -128,9 +228,11 @@ lazy_static! {
                 name: "finite_rod",
                 system: finite_rod::SYSTEM,
                 contour_conf: ContourConfiguration {
-                    w_min: 1e-3,
+                    indentation_radius: 1e-3,
+                    singular_points: vec![],
                     w_max: 1e2,
                     steps: 10_000usize,
+                    boundary: StabilityBoundary::ImaginaryAxis,
                 },
                 limits: Limits {
                     p1_min: 0.0,
@@ -139,6 +241,7 @@ lazy_static! {
                     p2_max: 70_000.0,
                 },
                 grid_step: 40usize,
+                adaptive_depth: Option::None,
             },
         );
 
@@ -148,9 +251,11 @@ lazy_static! {
                 name: "pde_complex_k_sigma",
                 system: pde_complex_k_sigma::SYSTEM,
                 contour_conf: ContourConfiguration {
-                    w_min: 1e-3,
+                    indentation_radius: 1e-3,
+                    singular_points: vec![],
                     w_max: 1e5,
                     steps: 10_000usize,
+                    boundary: StabilityBoundary::ImaginaryAxis,
                 },
                 limits: Limits {
                     p1_min: 0.0,
@@ -159,6 +264,7 @@ lazy_static! {
                     p2_max: 20.0,
                 },
                 grid_step: 40usize,
+                adaptive_depth: Option::None,
             },
         );
 
@@ -168,9 +274,11 @@ lazy_static! {
                 name: "pde_complex_tau_sigma",
                 system: pde_complex_tau_sigma::SYSTEM,
                 contour_conf: ContourConfiguration {
-                    w_min: 1e-3,
+                    indentation_radius: 1e-3,
+                    singular_points: vec![],
                     w_max: 1e5,
                     steps: 10_000usize,
+                    boundary: StabilityBoundary::ImaginaryAxis,
                 },
                 limits: Limits {
                     p1_min: 0.0,
@@ -179,6 +287,7 @@ lazy_static! {
                     p2_max: 20.0,
                 },
                 grid_step: 40usize,
+                adaptive_depth: Option::None,
             },
         );
 
@@ -188,9 +297,11 @@ lazy_static! {
                 name: "pde_complex_beta_sigma",
                 system: pde_complex_beta_sigma::SYSTEM,
                 contour_conf: ContourConfiguration {
-                    w_min: 1e-3,
+                    indentation_radius: 1e-3,
+                    singular_points: vec![],
                     w_max: 1e5,
                     steps: 10_000usize,
+                    boundary: StabilityBoundary::ImaginaryAxis,
                 },
                 limits: Limits {
                     p1_min: 0.0,
@@ -199,6 +310,7 @@ lazy_static! {
                     p2_max: 2.0,
                 },
                 grid_step: 40usize,
+                adaptive_depth: Option::None,
             },
         );
 
@@ -208,9 +320,11 @@ lazy_static! {
                 name: "telegrapher_x_k",
                 system: telegrapher_x_k::SYSTEM,
                 contour_conf: ContourConfiguration {
-                    w_min: 1e-3,
+                    indentation_radius: 1e-3,
+                    singular_points: vec![],
                     w_max: 1e5,
                     steps: 10_000usize,
+                    boundary: StabilityBoundary::ImaginaryAxis,
                 },
                 limits: Limits {
                     p1_min: 0.0,
@@ -219,6 +333,7 @@ lazy_static! {
                     p2_max: 8.0,
                 },
                 grid_step: 40usize,
+                adaptive_depth: Option::None,
             },
         );
 
@@ -228,9 +343,11 @@ lazy_static! {
                 name: "telegrapher_alpha_gamma",
                 system: telegrapher_alpha_gamma::SYSTEM,
                 contour_conf: ContourConfiguration {
-                    w_min: 1e-3,
+                    indentation_radius: 1e-3,
+                    singular_points: vec![],
                     w_max: 1e5,
                     steps: 10_000usize,
+                    boundary: StabilityBoundary::ImaginaryAxis,
                 },
                 limits: Limits {
                     p1_min: 0.1,
@@ -239,6 +356,7 @@ lazy_static! {
                     p2_max: 0.9,
                 },
                 grid_step: 40usize,
+                adaptive_depth: Option::None,
             },
         );
 
@@ -248,9 +366,11 @@ lazy_static! {
                 name: "telegrapher_standard",
                 system: telegrapher_standard::SYSTEM,
                 contour_conf: ContourConfiguration {
-                    w_min: 1e-3,
+                    indentation_radius: 1e-3,
+                    singular_points: vec![],
                     w_max: 1e5,
                     steps: 10_000usize,
+                    boundary: StabilityBoundary::ImaginaryAxis,
                 },
                 limits: Limits {
                     p1_min: 0.0,
@@ -259,6 +379,7 @@ lazy_static! {
                     p2_max: 1.5,
                 },
                 grid_step: 20usize,
+                adaptive_depth: Option::None,
             },
         );
 
@@ -268,9 +389,11 @@ lazy_static! {
                 name: "ln_system1",
                 system: ln_system1::SYSTEM,
                 contour_conf: ContourConfiguration {
-                    w_min: 1e-3,
+                    indentation_radius: 1e-3,
+                    singular_points: vec![],
                     w_max: 1e5,
                     steps: 10_000usize,
+                    boundary: StabilityBoundary::ImaginaryAxis,
                 },
                 limits: Limits {
                     p1_min: 0.0,
@@ -279,6 +402,7 @@ lazy_static! {
                     p2_max: 20.0,
                 },
                 grid_step: 20usize,
+                adaptive_depth: Option::None,
             },
         );
 
@@ -288,9 +412,11 @@ lazy_static! {
                 name: "ln_system1_negative",
                 system: ln_system1::SYSTEM,
                 contour_conf: ContourConfiguration {
-                    w_min: 1e-3,
+                    indentation_radius: 1e-3,
+                    singular_points: vec![],
                     w_max: 1e5,
                     steps: 10_000usize,
+                    boundary: StabilityBoundary::ImaginaryAxis,
                 },
                 limits: Limits {
                     p1_min: -20.0,
@@ -299,6 +425,7 @@ lazy_static! {
                     p2_max: 20.0,
                 },
                 grid_step: 20usize,
+                adaptive_depth: Option::None,
             },
         );
         
@@ -309,9 +436,11 @@ lazy_static! {
                 name: "dopid1",
                 system: dopid1::SYSTEM,
                 contour_conf: ContourConfiguration {
-                    w_min: 1e-3,
+                    indentation_radius: 1e-3,
+                    singular_points: vec![],
                     w_max: 1e5,
                     steps: 100_000usize,
+                    boundary: StabilityBoundary::ImaginaryAxis,
                 },
                 limits: Limits {
                     p1_min: 0.0,
@@ -320,6 +449,7 @@ lazy_static! {
                     p2_max: 1000.0,
                 },
                 grid_step: 20usize,
+                adaptive_depth: Option::None,
             },
         );
 
@@ -329,9 +459,11 @@ lazy_static! {
                 name: "dopid2",
                 system: dopid2::SYSTEM,
                 contour_conf: ContourConfiguration {
-                    w_min: 1e-3,
+                    indentation_radius: 1e-3,
+                    singular_points: vec![],
                     w_max: 1e5,
                     steps: 100_000usize,
+                    boundary: StabilityBoundary::ImaginaryAxis,
                 },
                 limits: Limits {
                     p1_min: 0.0,
@@ -340,6 +472,7 @@ lazy_static! {
                     p2_max: 3.0,
                 },
                 grid_step: 20usize,
+                adaptive_depth: Option::None,
             },
         );
 
@@ -350,9 +483,11 @@ lazy_static! {
                 name: "dopid3",
                 system: dopid3::SYSTEM,
                 contour_conf: ContourConfiguration {
-                    w_min: 1e-3,
+                    indentation_radius: 1e-3,
+                    singular_points: vec![],
                     w_max: 1e5,
                     steps: 10_000usize,
+                    boundary: StabilityBoundary::ImaginaryAxis,
                 },
                 limits: Limits {
                     p1_min: 0.0,
@@ -361,6 +496,7 @@ lazy_static! {
                     p2_max: 10.0,
                 },
                 grid_step: 30usize,
+                adaptive_depth: Option::None,
             },
         );
 
@@ -371,9 +507,34 @@ lazy_static! {
                 name: "test_configuration",
                 system: test_system::SYSTEM,
                 contour_conf: ContourConfiguration {
-                    w_min: 1e-3,
+                    indentation_radius: 1e-3,
+                    singular_points: vec![],
+                    w_max: 1e2,
+                    steps: 1_000usize,
+                    boundary: StabilityBoundary::ImaginaryAxis,
+                },
+                limits: Limits {
+                    p1_min: 0.0,
+                    p1_max: 1.2,
+                    p2_min: 0.0,
+                    p2_max: 1.2,
+                },
+                grid_step: 20usize,
+                adaptive_depth: Option::None,
+            },
+        );
+
+        configs.insert(
+            "test_configuration_damping",
+            NuConfiguration {
+                name: "test_configuration_damping",
+                system: test_system::SYSTEM,
+                contour_conf: ContourConfiguration {
+                    indentation_radius: 1e-3,
+                    singular_points: vec![],
                     w_max: 1e2,
                     steps: 1_000usize,
+                    boundary: StabilityBoundary::Sector(PI/4.0),
                 },
                 limits: Limits {
                     p1_min: 0.0,
@@ -382,6 +543,7 @@ lazy_static! {
                     p2_max: 1.2,
                 },
                 grid_step: 20usize,
+                adaptive_depth: Option::None,
             },
         );
 
@@ -1,6 +1,7 @@
 mod configurations;
+mod adaptive;
 
-use log::{debug, info};
+use log::{debug, info, warn};
 use std::collections::HashMap;
 use lazy_static::lazy_static;
 use iter_num_tools::{log_space, lin_space, grid_space};
@@ -12,103 +13,434 @@ use std::fs;
 use crate::Args;
 use crate::types::{Comp, Par, System, Limits};
 use crate::utils::storage;
-pub use configurations::{ContourConfiguration,NuConfiguration,CONFIGURATONS};
+pub use configurations::{ContourConfiguration, NuConfiguration, StabilityBoundary, CONFIGURATONS};
 
 
 #[derive(Debug, serde::Serialize)]
 pub struct NuPointResult {
     p: Par,
     nu: i32,
+    min_norm: f64,
+    reliable: bool,
 }
 
 
 #[derive(Debug, serde::Serialize)]
 pub struct NuResult {
     point_results: Vec<NuPointResult>,
+    cells: Vec<adaptive::NuCell>,
     limits: &'static Limits,
     parameters: (&'static str, &'static str),
 }
 
 
-fn get_bromwhich_contour(conf: &ContourConfiguration) -> impl Iterator<Item=Comp>  + '_ {
-    const SAFETY_OFFSET: f64 = 1e-3; // Nudge the contour a bit to the right
+/* Outcome of the argument principle, along with an indicator of its trustworthiness */
+#[derive(Debug, Clone, Copy)]
+pub struct NuEstimate {
+    pub nu: i32,
+    pub min_norm: f64,
+    pub reliable: bool,
+}
 
-    let freq = log_space(conf.w_min..=conf.w_max, conf.steps);
-    let imag_positive = freq.map(|w| Comp::new(SAFETY_OFFSET, w));
-    let freq = log_space(conf.w_min..=conf.w_max, conf.steps);
-    let imag_negative = freq.map(|w| Comp::new(SAFETY_OFFSET, -w)).rev();
-    let angles = lin_space(-PI/2.0..=PI/2.0, conf.steps).rev();
-    let semicircle = angles.map(move |theta| Comp::from_polar(conf.w_max, theta));
 
-    let contour = imag_positive;
-    let contour = contour.chain(semicircle);
-    let contour = contour.chain(imag_negative);
+/* Zeros enclosed by a contour, described through their power sums */
+#[derive(Debug, Clone)]
+pub struct ContourMoments {
+    pub zeros: i32,
+    pub power_sums: Vec<Comp>,
+    pub reliable: bool,
+}
 
-    contour
+
+/* Largest allowed change of arg f between two consecutive contour points */
+const MAX_ANGLE_STEP: f64 = PI / 8.0;
+
+/* Maximal number of bisections of a single initial contour step */
+const MAX_REFINEMENT_DEPTH: u32 = 24;
+
+/* Maximal number of samples along the contour, relative to the number of initial samples;
+ * once exhausted, fast rotating steps are accepted as they are and flagged unreliable */
+const MAX_REFINEMENT_FACTOR: usize = 16;
+
+/* Estimated distance of a zero from the contour below which windings are not trusted */
+const ROOT_DISTANCE_TOLERANCE: f64 = 1e-9;
+
+/* Maximal deviation of the real winding number from an integer */
+const WINDING_TOLERANCE: f64 = 0.25;
+
+
+/* A smooth part of the contour, parametrized by t in [0, 1] */
+pub enum ContourPiece {
+    /* Points on the straight line from one point to another */
+    Segment { from: Comp, to: Comp },
+    /* Points center + r*e^(j*phi), with r changing logarithmically from r_from to r_to */
+    Radial { center: Comp, phi: f64, r_from: f64, r_to: f64 },
+    /* Points center + radius*e^(j*theta), with theta changing linearly */
+    Arc { center: Comp, radius: f64, theta_from: f64, theta_to: f64 },
 }
 
 
-pub fn calculate_nu_single(
-    contour_conf: &ContourConfiguration,
-    f: fn(Comp, Par) -> Comp,
-    p: Par
-) -> i32
-{
-    let contour = get_bromwhich_contour(contour_conf);
-    let image = contour.map(|s| f(s, p));
-    let angles: Vec<f64> = image.map(|s| s.arg()).collect();
+impl ContourPiece {
+    fn point(&self, t: f64) -> Comp {
+        match *self {
+            ContourPiece::Segment { from, to } => from + (to - from) * t,
+            ContourPiece::Radial { center, phi, r_from, r_to } => {
+                let r = r_from * (r_to / r_from).powf(t);
+                center + Comp::from_polar(r, phi)
+            },
+            ContourPiece::Arc { center, radius, theta_from, theta_to } => {
+                let theta = theta_from + (theta_to - theta_from) * t;
+                center + Comp::from_polar(radius, theta)
+            },
+        }
+    }
+}
 
-    let mut integral: f64 = 0.0;
 
-    for i in 0..angles.len()-1 {
-        let diff = angles[i+1] - angles[i];
-        if diff > 0.0 {
-            if diff < PI {
-                integral += diff;
-            }
-            else {
-                integral -= 2.0*PI - diff;
+/* A point on the contour along with the value of the characteristic function in it */
+#[derive(Clone, Copy)]
+struct ContourSample {
+    t: f64,
+    s: Comp,
+    value: Comp,
+}
+
+
+/* Change of argument from one value to another; values are normalized first, since dividing
+ * them directly overflows for |f| above roughly 1e154 */
+fn get_angle_change(from: Comp, to: Comp) -> f64 {
+    (to.unscale(to.norm()) * from.unscale(from.norm()).conj()).arg()
+}
+
+
+/* Accumulated state of the argument principle along the contour */
+struct WindingTracker<'a> {
+    f: &'a dyn Fn(Comp) -> Comp,
+    integral: f64,
+    min_norm: f64,
+    reliable: bool,
+    moments: Vec<Comp>,
+    moment_center: Comp,
+    moment_scale: f64,
+    samples_left: usize,
+}
+
+
+impl<'a> WindingTracker<'a> {
+    fn sample(&mut self, piece: &ContourPiece, t: f64) -> ContourSample {
+        let s = piece.point(t);
+        let value = (self.f)(s);
+        self.samples_left = self.samples_left.saturating_sub(1);
+
+        if value.is_nan() {
+            panic!("NaN value detected");
+        }
+        self.min_norm = f64::min(self.min_norm, value.norm());
+
+        ContourSample { t, s, value }
+    }
+
+    fn accept_step(&mut self, start: ContourSample, end: ContourSample, angle: f64) {
+        /* Linearize f to estimate how far its zero lies from the step */
+        let slope = (end.value - start.value).norm() / (end.s - start.s).norm();
+        let root_distance = f64::min(start.value.norm(), end.value.norm()) / slope;
+
+        if angle.abs() > MAX_ANGLE_STEP || root_distance < ROOT_DISTANCE_TOLERANCE {
+            self.reliable = false;
+        }
+        self.integral += angle;
+
+        /* Stieltjes sums of z^k d(log f), with z being the normalized midpoint of the step */
+        if !self.moments.is_empty() {
+            let log_change = Comp::new((end.value.norm() / start.value.norm()).ln(), angle);
+            let z = (0.5 * (start.s + end.s) - self.moment_center) / self.moment_scale;
+            let mut z_power = Comp::new(1.0, 0.0);
+
+            for moment in self.moments.iter_mut() {
+                *moment += z_power * log_change;
+                z_power *= z;
             }
         }
-        else {
-            if diff.abs() < PI {
-                integral -= diff.abs();
-            } else {
-                integral += 2.0*PI - diff.abs();
+    }
+
+    fn step(&mut self, piece: &ContourPiece, start: ContourSample, end: ContourSample, depth: u32) {
+        let angle = get_angle_change(start.value, end.value);
+
+        if angle.abs() <= MAX_ANGLE_STEP || depth >= MAX_REFINEMENT_DEPTH || self.samples_left == 0 {
+            self.accept_step(start, end, angle);
+            return;
+        }
+
+        /* Image rotates too fast; bisect the step */
+        let middle = self.sample(piece, 0.5 * (start.t + end.t));
+        self.step(piece, start, middle, depth + 1);
+        self.step(piece, middle, end, depth + 1);
+    }
+
+    fn trace(&mut self, pieces: &[ContourPiece], steps: usize) {
+        let mut last_sample: Option<ContourSample> = Option::None;
+        self.samples_left = MAX_REFINEMENT_FACTOR * steps * pieces.len();
+
+        for piece in pieces {
+            let mut previous = self.sample(piece, 0.0);
+
+            /* Consecutive pieces are joined without refinement */
+            if let Some(last) = last_sample {
+                let angle = get_angle_change(last.value, previous.value);
+                self.accept_step(last, previous, angle);
+            }
+
+            for t in lin_space(0.0..=1.0, steps).skip(1) {
+                let current = self.sample(piece, t);
+                self.step(piece, previous, current, 0);
+                previous = current;
             }
+            last_sample = Some(previous);
         }
     }
+}
+
+
+/* Semicircle of the given radius bypassing j*w through the right half-plane, traversed clockwise */
+fn get_indentation(w: f64, radius: f64) -> ContourPiece {
+    ContourPiece::Arc {
+        center: Comp::new(0.0, w),
+        radius,
+        theta_from: -PI/2.0,
+        theta_to: PI/2.0,
+    }
+}
+
+
+/* Singular frequencies lying strictly inside the contour, sorted in ascending order */
+fn get_singular_points(conf: &ContourConfiguration) -> Vec<f64> {
+    let r = conf.indentation_radius;
+    let mut points: Vec<f64> = conf.singular_points
+        .iter()
+        .cloned()
+        .filter(|&w| w > 2.0*r && w < conf.w_max - 2.0*r)
+        .collect();
+    points.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    points.dedup_by(|a, b| *a - *b < 2.0*r);
+    points
+}
+
 
-    let windings = -integral / (2.0*PI);
+/* Imaginary axis segments between the indentations, each one described by its end frequencies */
+fn get_axis_segments(conf: &ContourConfiguration, singular_points: &[f64]) -> Vec<(f64, f64)> {
+    let r = conf.indentation_radius;
+    let mut segments = Vec::new();
+    let mut w_from = r;
+
+    for &w in singular_points {
+        segments.push((w_from, w - r));
+        w_from = w + r;
+    }
+    segments.push((w_from, conf.w_max));
+    segments
+}
+
+
+/* Closed clockwise contour around the right half-plane, bypassing the origin and the given
+ * imaginary axis singularities (e.g. branch points) with small semicircles */
+fn get_bromwhich_contour(conf: &ContourConfiguration) -> Vec<ContourPiece> {
+    let r = conf.indentation_radius;
+    let origin = Comp::new(0.0, 0.0);
+    let singular_points = get_singular_points(conf);
+    let segments = get_axis_segments(conf, &singular_points);
+    let mut contour = vec![get_indentation(0.0, r)];
+
+    /* Upper half of the imaginary axis */
+    for (i, &(w_from, w_to)) in segments.iter().enumerate() {
+        contour.push(ContourPiece::Radial { center: origin, phi: PI/2.0, r_from: w_from, r_to: w_to });
+        if let Some(&w) = singular_points.get(i) {
+            contour.push(get_indentation(w, r));
+        }
+    }
+
+    contour.push(ContourPiece::Arc { center: origin, radius: conf.w_max, theta_from: PI/2.0, theta_to: -PI/2.0 });
+
+    /* Lower half of the imaginary axis, mirroring the upper one */
+    for (i, &(w_from, w_to)) in segments.iter().enumerate().rev() {
+        contour.push(ContourPiece::Radial { center: origin, phi: -PI/2.0, r_from: w_to, r_to: w_from });
+        if i > 0 {
+            contour.push(get_indentation(-singular_points[i-1], r));
+        }
+    }
+
+    contour
+}
+
+
+/* Bromwich contour moved left by alpha, enclosing Re s > -alpha */
+fn get_shifted_contour(conf: &ContourConfiguration, alpha: f64) -> Vec<ContourPiece> {
+    let offset = Comp::new(-alpha, 0.0);
+
+    get_bromwhich_contour(conf)
+        .into_iter()
+        .map(|piece| match piece {
+            ContourPiece::Segment { from, to } => ContourPiece::Segment { from: from + offset, to: to + offset },
+            ContourPiece::Radial { center, phi, r_from, r_to } =>
+                ContourPiece::Radial { center: center + offset, phi, r_from, r_to },
+            ContourPiece::Arc { center, radius, theta_from, theta_to } =>
+                ContourPiece::Arc { center: center + offset, radius, theta_from, theta_to },
+        })
+        .collect()
+}
+
+
+/* Closed clockwise contour around |arg s| < pi - phi, bypassing the origin */
+fn get_sector_contour(conf: &ContourConfiguration, phi: f64) -> Vec<ContourPiece> {
+    let r = conf.indentation_radius;
+    let origin = Comp::new(0.0, 0.0);
+    let angle = PI - phi;
+
+    vec![
+        ContourPiece::Radial { center: origin, phi: angle, r_from: r, r_to: conf.w_max },
+        ContourPiece::Arc { center: origin, radius: conf.w_max, theta_from: angle, theta_to: -angle },
+        ContourPiece::Radial { center: origin, phi: -angle, r_from: conf.w_max, r_to: r },
+        ContourPiece::Arc { center: origin, radius: r, theta_from: -angle, theta_to: angle },
+    ]
+}
+
+
+/* Closed clockwise contour around the complement of the D-region; singularities other than
+ * the origin can only be bypassed on the imaginary axis, so f must be analytic elsewhere */
+fn get_boundary_contour(conf: &ContourConfiguration, system: &System) -> Vec<ContourPiece> {
+    if conf.boundary != StabilityBoundary::ImaginaryAxis && !conf.singular_points.is_empty() {
+        panic!("Singular points are supported only for the imaginary axis boundary");
+    }
+    if system.singular_origin && conf.boundary.encloses_branch_cut() {
+        panic!("Boundary {:?} encloses the branch point of {} at the origin", conf.boundary, system.name);
+    }
+
+    match conf.boundary {
+        StabilityBoundary::ImaginaryAxis => get_bromwhich_contour(conf),
+        StabilityBoundary::ShiftedAxis(alpha) => get_shifted_contour(conf, alpha),
+        StabilityBoundary::Sector(phi) => get_sector_contour(conf, phi),
+    }
+}
+
+
+pub fn calculate_nu_detailed(
+    contour_conf: &ContourConfiguration,
+    system: &System,
+    p: Par
+) -> NuEstimate
+{
+    let contour = get_boundary_contour(contour_conf, system);
+    let f_p = |s: Comp| (system.f_complex)(s, p);
+    let mut tracker = WindingTracker {
+        f: &f_p,
+        integral: 0.0,
+        min_norm: f64::INFINITY,
+        reliable: true,
+        moments: Vec::new(),
+        moment_center: Comp::new(0.0, 0.0),
+        moment_scale: 1.0,
+        samples_left: 0,
+    };
+    tracker.trace(&contour, contour_conf.steps);
+
+    let windings = -tracker.integral / (2.0*PI);
 
     if windings.is_nan() {
         panic!("NaN value detected");
     }
 
     debug!("Windings (real): {}", windings);
-    let windings = windings.round();
-    debug!("Windings (rounded): {}", windings);
+    let windings_rounded = windings.round();
+    debug!("Windings (rounded): {}", windings_rounded);
+
+    let reliable = tracker.reliable && (windings - windings_rounded).abs() < WINDING_TOLERANCE;
+
+    NuEstimate { nu: windings_rounded as i32, min_norm: tracker.min_norm, reliable }
+}
+
+
+/* Number of zeros inside a closed counterclockwise contour along with their power sums
+ * (sum of z_i^k for k in 0..=order, where z_i = (s_i - center) / scale) */
+pub fn calculate_moments(
+    contour: &[ContourPiece],
+    f: &dyn Fn(Comp) -> Comp,
+    steps: usize,
+    center: Comp,
+    scale: f64,
+    order: usize,
+) -> ContourMoments
+{
+    let mut tracker = WindingTracker {
+        f,
+        integral: 0.0,
+        min_norm: f64::INFINITY,
+        reliable: true,
+        moments: vec![Comp::new(0.0, 0.0); order + 1],
+        moment_center: center,
+        moment_scale: scale,
+        samples_left: 0,
+    };
+    tracker.trace(contour, steps);
+
+    let windings = tracker.integral / (2.0*PI);
+    let reliable = tracker.reliable && (windings - windings.round()).abs() < WINDING_TOLERANCE;
+    let power_sums = tracker.moments
+        .iter()
+        .map(|moment| moment / Comp::new(0.0, 2.0*PI))
+        .collect();
+
+    ContourMoments { zeros: windings.round() as i32, power_sums, reliable }
+}
+
+
+pub fn calculate_nu_single(
+    contour_conf: &ContourConfiguration,
+    system: &System,
+    p: Par
+) -> i32
+{
+    let estimate = calculate_nu_detailed(contour_conf, system, p);
+
+    if !estimate.reliable {
+        warn!("Unreliable nu={} at {:?}; min |f| on contour is {}", estimate.nu, p, estimate.min_norm);
+    }
+
+    estimate.nu
+}
+
+
+fn evaluate_point(conf: &NuConfiguration, p: Par) -> NuPointResult {
+    let estimate = calculate_nu_detailed(&conf.contour_conf, &conf.system, p);
 
-    windings as i32
+    NuPointResult {
+        p: p,
+        nu: estimate.nu,
+        min_norm: estimate.min_norm,
+        reliable: estimate.reliable,
+    }
 }
 
 
-fn calculate_nu(conf: &'static NuConfiguration, _parallel: bool) -> NuResult {
+pub fn calculate_nu(conf: &'static NuConfiguration, _parallel: bool) -> NuResult {
+    if let Some(max_depth) = conf.adaptive_depth {
+        let (point_results, cells) = adaptive::calculate_nu_adaptive(conf, max_depth);
+        return NuResult {
+            point_results,
+            cells,
+            limits: &conf.limits,
+            parameters: conf.system.parameters,
+        };
+    }
+
     let grid_min = [conf.limits.p1_min, conf.limits.p2_min];
     let grid_max = [conf.limits.p1_max, conf.limits.p2_max];
     let steps = [conf.grid_step, conf.grid_step];
     let grid: Vec<[f64; 2]> = grid_space(grid_min..=grid_max, steps).collect();
 
     let grid = grid.into_par_iter();
-    let results = grid.map(|p| {
-        let p = (p[0], p[1]);
-        NuPointResult {
-            p: p,
-            nu: calculate_nu_single(&conf.contour_conf, conf.system.f_complex, p)}
-        }
-    );
+    let results = grid.map(|p| evaluate_point(conf, (p[0], p[1])));
     let ret_val = NuResult {
         point_results: results.collect(),
+        cells: Vec::new(),
         limits: &conf.limits,
         parameters: conf.system.parameters,
     };
@@ -144,4 +476,104 @@ pub fn run(args: &Args) {
     /* Store results in file */
     let filename = get_nu_path(config_name);
     storage::store_results(results, &filename);
-}
\ No newline at end of file
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::{ln_system1, test_system};
+
+    /* Zero at s = 0.5; |e^(400s)| reaches about 1e173 on the closing semicircle */
+    fn f_fast_growing(s: Comp, p: Par) -> Comp {
+        (s - p.0) * (p.1 * s).exp()
+    }
+
+    #[test]
+    fn test_calculate_nu_fast_growing() {
+        let contour_conf = ContourConfiguration {
+            indentation_radius: 1e-3,
+            singular_points: Vec::new(),
+            w_max: 1.0,
+            steps: 2000,
+            boundary: StabilityBoundary::ImaginaryAxis,
+        };
+
+        /* Combining consecutive values without normalizing them first overflows to NaN here */
+        let system = System { f_complex: f_fast_growing, ..test_system::SYSTEM };
+        let estimate = calculate_nu_detailed(&contour_conf, &system, (0.5, 400.0));
+        assert_eq!(estimate.nu, 1);
+        assert!(estimate.reliable);
+        assert!(estimate.min_norm.is_finite() && estimate.min_norm > 0.0);
+    }
+
+    fn get_contour_conf(boundary: StabilityBoundary) -> ContourConfiguration {
+        ContourConfiguration {
+            indentation_radius: 1e-3,
+            singular_points: Vec::new(),
+            w_max: 10.0,
+            steps: 2000,
+            boundary,
+        }
+    }
+
+    fn get_nu(boundary: StabilityBoundary, system: &System, p: Par) -> i32 {
+        let estimate = calculate_nu_detailed(&get_contour_conf(boundary), system, p);
+        assert!(estimate.reliable);
+        estimate.nu
+    }
+
+    #[test]
+    fn test_calculate_nu_shifted_axis() {
+        let system = test_system::SYSTEM;
+
+        /* Real zeros 0.7775 and -0.9775 */
+        let p = (0.2, 0.2);
+        assert_eq!(get_nu(StabilityBoundary::ImaginaryAxis, &system, p), 1);
+        assert_eq!(get_nu(StabilityBoundary::ShiftedAxis(0.1), &system, p), 1);
+        assert_eq!(get_nu(StabilityBoundary::ShiftedAxis(1.0), &system, p), 2);
+
+        /* Complex zeros -0.5 +- 0.866j */
+        let p = (1.0, 1.0);
+        assert_eq!(get_nu(StabilityBoundary::ImaginaryAxis, &system, p), 0);
+        assert_eq!(get_nu(StabilityBoundary::ShiftedAxis(0.4), &system, p), 0);
+        assert_eq!(get_nu(StabilityBoundary::ShiftedAxis(0.6), &system, p), 2);
+
+        /* Shifting right excludes the unstable zero 0.7775 */
+        assert_eq!(get_nu(StabilityBoundary::ShiftedAxis(-0.9), &system, (0.2, 0.2)), 0);
+    }
+
+    #[test]
+    fn test_calculate_nu_sector() {
+        let system = test_system::SYSTEM;
+
+        /* Complex zeros -0.5 +- 0.866j at 120 degrees from the positive real axis */
+        let p = (1.0, 1.0);
+        assert_eq!(get_nu(StabilityBoundary::Sector(PI/4.0), &system, p), 2);
+        assert_eq!(get_nu(StabilityBoundary::Sector(PI/2.5), &system, p), 0);
+
+        /* Real zeros 0.7775 and -0.9775, the latter lying outside every sector with phi > 0 */
+        assert_eq!(get_nu(StabilityBoundary::Sector(PI/4.0), &system, (0.2, 0.2)), 1);
+    }
+
+    #[test]
+    fn test_branch_cut_boundaries() {
+        assert!(!StabilityBoundary::ImaginaryAxis.encloses_branch_cut());
+        assert!(!StabilityBoundary::ShiftedAxis(-0.1).encloses_branch_cut());
+        assert!(StabilityBoundary::ShiftedAxis(0.1).encloses_branch_cut());
+        assert!(!StabilityBoundary::Sector(PI/4.0).encloses_branch_cut());
+        assert!(StabilityBoundary::Sector(0.0).encloses_branch_cut());
+
+        /* The sector contour bypasses the origin, so the branch point of ln s stays outside */
+        let estimate = calculate_nu_detailed(
+            &get_contour_conf(StabilityBoundary::Sector(PI/4.0)), &ln_system1::SYSTEM, (1.0, 1.0));
+        assert!(estimate.min_norm.is_finite());
+    }
+
+    #[test]
+    #[should_panic(expected = "encloses the branch point")]
+    fn test_shifted_axis_rejects_branch_point() {
+        calculate_nu_detailed(
+            &get_contour_conf(StabilityBoundary::ShiftedAxis(0.1)), &ln_system1::SYSTEM, (1.0, 1.0));
+    }
+}
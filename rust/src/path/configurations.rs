@@ -0,0 +1,51 @@
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+
+use crate::types::Par;
+
+
+pub struct PathConfiguration {
+    pub name: &'static str,                  // Config name
+    pub line_configuration: &'static str,    // Line configuration providing system and settings
+    pub vertices: Vec<Par>,                  // Piecewise linear (possibly densely sampled) path
+}
+
+
+lazy_static! {
+    pub static ref CONFIGURATIONS: HashMap<&'static str, PathConfiguration> = {
+        let mut configs = HashMap::new();
+
+        configs.insert("retarded1", PathConfiguration {
+            name: "retarded1",
+            line_configuration: "retarded1",
+            vertices: vec![
+                (0.25, 0.50),
+                (0.40, 1.00),
+                (0.30, 1.80),
+                (0.60, 2.40),
+                (1.20, 2.20),
+            ],
+        });
+
+        configs.insert("distributed_delay1", PathConfiguration {
+            name: "distributed_delay1",
+            line_configuration: "distributed_delay1",
+            vertices: (0..=20)
+                .map(|i| 0.5 * i as f64)
+                .map(|tau| (1.0 + tau, 0.2 - 0.015 * tau))
+                .collect(),
+        });
+
+        configs.insert("test_configuration", PathConfiguration {
+            name: "test_configuration",
+            line_configuration: "test_configuration",
+            vertices: vec![
+                (0.2, 0.2),
+                (0.4, 0.8),
+                (1.0, 0.6),
+            ],
+        });
+
+        configs
+    };
+}
@@ -0,0 +1,128 @@
+mod configurations;
+
+use log::{info, warn};
+
+use crate::Args;
+use crate::data::line::{self, LineConfiguration};
+use crate::nu;
+use crate::types::{Limits, Par};
+use crate::utils::storage;
+pub use configurations::{PathConfiguration, CONFIGURATIONS};
+
+
+#[derive(Debug, serde::Serialize)]
+pub struct PathSegmentResult {
+    from: Par,
+    to: Par,
+    length: f64,
+    certified_length: f64,
+}
+
+
+#[derive(Debug, serde::Serialize)]
+pub struct PathResult {
+    vertices: Vec<Par>,
+    segments: Vec<PathSegmentResult>,
+    nu: i32,
+    failure: Option<Par>,  // First point at which certification fails; None if the path is certified
+    limits: &'static Limits,
+    parameters: (&'static str, &'static str),
+}
+
+
+/* Certify segments one after another, until the first one which cannot be fully certified */
+fn certify_path(conf: &PathConfiguration, line_conf: &'static LineConfiguration) -> PathResult {
+    let log_space = line_conf.get_log_space();
+    let start = conf.vertices[0];
+    let nu = nu::calculate_nu_single(&line_conf.contour_conf, &line_conf.system, start);
+    let mut segments = Vec::new();
+    let mut failure = Option::None;
+
+    for (&from, &to) in conf.vertices.iter().zip(conf.vertices.iter().skip(1)) {
+        let length = f64::hypot(to.0 - from.0, to.1 - from.1);
+        let certified_length = line::get_certified_length(line_conf, from, to, &log_space);
+        segments.push(PathSegmentResult { from, to, length, certified_length });
+
+        if certified_length < length {
+            let ratio = certified_length / length;
+            failure = Some((from.0 + ratio * (to.0 - from.0), from.1 + ratio * (to.1 - from.1)));
+            break;
+        }
+    }
+
+    match failure {
+        Some(p) => warn!("Path certification failed at {:?} (segment {})", p, segments.len()),
+        None => info!("Whole path certified to have nu={}", nu),
+    }
+
+    PathResult {
+        vertices: conf.vertices.clone(),
+        segments,
+        nu,
+        failure,
+        limits: &line_conf.limits,
+        parameters: line_conf.system.parameters,
+    }
+}
+
+
+pub fn run(args: &Args) {
+    /* Certify the path */
+    let config_name = args.configuration
+        .as_ref()
+        .expect("path requires configuration to be specified");
+    let config = CONFIGURATIONS.get(config_name.as_str()).expect("Unknown configuration");
+    let line_conf = line::CONFIGURATONS
+        .get(config.line_configuration)
+        .expect("Unknown line configuration");
+    let results = certify_path(config, line_conf);
+
+    /* Store results in file */
+    let filename = storage::get_filepath("path", "temp_data", "pathdata", config_name);
+    storage::store_results(results, &filename);
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_path_result(vertices: Vec<Par>) -> PathResult {
+        let conf = PathConfiguration { name: "test", line_configuration: "test_configuration", vertices };
+        let line_conf = line::CONFIGURATONS.get(conf.line_configuration).unwrap();
+        certify_path(&conf, line_conf)
+    }
+
+    #[test]
+    fn test_certify_stable_path() {
+        /* test_system is stable for a > 0 and a^2 + b > 1 */
+        let result = get_path_result(vec![(0.9, 0.9), (0.8, 0.7), (0.95, 0.5)]);
+        assert_eq!(result.nu, 0);
+        assert!(result.failure.is_none());
+        assert_eq!(result.segments.len(), 2);
+        for segment in result.segments.iter() {
+            assert!(segment.certified_length >= segment.length);
+        }
+    }
+
+    #[test]
+    fn test_certify_crossing_path() {
+        /* The second segment (a, b) = (0.7 - 0.2t, 0.9 - 0.4t) crosses the real crossing curve
+         * b = 1 - a^2 where 0.04t^2 - 0.68t + 0.39 = 0 */
+        let result = get_path_result(vec![(0.9, 0.9), (0.7, 0.9), (0.5, 0.5), (0.2, 0.2)]);
+        let t = (0.68 - f64::sqrt(0.68f64.powi(2) - 4.0 * 0.04 * 0.39)) / 0.08;
+        let crossing = (0.7 - 0.2 * t, 0.9 - 0.4 * t);
+        assert_eq!(result.nu, 0);
+        assert_eq!(result.segments.len(), 2);
+        assert!(result.segments[0].certified_length >= result.segments[0].length);
+
+        /* The failing segment brackets the crossing, and certification stops close to it */
+        let segment = &result.segments[1];
+        let crossing_length = f64::hypot(crossing.0 - segment.from.0, crossing.1 - segment.from.1);
+        assert!(crossing_length > 0.0 && crossing_length < segment.length);
+        assert!((segment.certified_length - crossing_length).abs() < 1e-3);
+
+        let failure = result.failure.expect("Crossing path must not be certified");
+        assert!(f64::hypot(failure.0 - crossing.0, failure.1 - crossing.1) < 1e-3);
+    }
+}
@@ -0,0 +1,211 @@
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+
+use crate::nu::{ContourConfiguration, StabilityBoundary};
+use crate::systems::distributed_delay1;
+use crate::systems::dopid3;
+use crate::systems::ln_system1;
+use crate::systems::retarded1;
+use crate::systems::retarded2;
+use crate::systems::test_system;
+use crate::types::{Par, System};
+
+
+/* Rectangle in the complex plane in which zeros are searched for */
+#[derive(Debug, Clone, Copy, serde::Serialize)]
+pub struct SearchBox {
+    pub re_min: f64,
+    pub re_max: f64,
+    pub im_min: f64,
+    pub im_max: f64,
+}
+
+
+/* Precision of locating zeros inside a search box */
+pub struct ZeroSearchSettings {
+    pub edge_steps: usize,                   // Initial point count of each edge of a box
+    pub min_box_size: f64,                   // Boxes smaller than this are not subdivided
+    pub newton_tolerance: f64,               // Relative step size at which Newton stops
+}
+
+
+pub struct RootsConfiguration {
+    pub name: &'static str,
+    pub system: System,
+    pub points: Vec<Par>,                    // Parametric points whose zeros are located
+    pub contour_conf: ContourConfiguration,  // Contour used for counting all unstable zeros
+    pub search_box: SearchBox,               // Rectangle in which the zeros are located
+    pub search_settings: ZeroSearchSettings,
+}
+
+
+/* Global collection of all roots configurations */
+lazy_static! {
+    pub static ref CONFIGURATIONS: HashMap<&'static str, RootsConfiguration> = {
+        let mut configs = HashMap::new();
+
+        configs.insert(
+            "retarded1",
+            RootsConfiguration {
+                name: "retarded1",
+                system: retarded1::SYSTEM,
+                points: vec![(0.5, 0.5), (1.0, 3.0), (2.0, 2.0)],
+                contour_conf: ContourConfiguration {
+                    indentation_radius: 1e-3,
+                    singular_points: vec![],
+                    w_max: 1e5,
+                    steps: 10_000usize,
+                    boundary: StabilityBoundary::ImaginaryAxis,
+                },
+                search_box: SearchBox {
+                    re_min: 1e-3,
+                    re_max: 10.0,
+                    im_min: -50.0,
+                    im_max: 50.0,
+                },
+                search_settings: ZeroSearchSettings {
+                    edge_steps: 1_000usize,
+                    min_box_size: 1e-6,
+                    newton_tolerance: 1e-12,
+                },
+            },
+        );
+
+        configs.insert(
+            "retarded2",
+            RootsConfiguration {
+                name: "retarded2",
+                system: retarded2::SYSTEM,
+                points: vec![(1.0, 1.0), (2.5, 2.5), (4.0, 4.0)],
+                contour_conf: ContourConfiguration {
+                    indentation_radius: 1e-3,
+                    singular_points: vec![],
+                    w_max: 1e5,
+                    steps: 10_000usize,
+                    boundary: StabilityBoundary::ImaginaryAxis,
+                },
+                search_box: SearchBox {
+                    re_min: 1e-3,
+                    re_max: 10.0,
+                    im_min: -50.0,
+                    im_max: 50.0,
+                },
+                search_settings: ZeroSearchSettings {
+                    edge_steps: 1_000usize,
+                    min_box_size: 1e-6,
+                    newton_tolerance: 1e-12,
+                },
+            },
+        );
+
+        configs.insert(
+            "distributed_delay1",
+            RootsConfiguration {
+                name: "distributed_delay1",
+                system: distributed_delay1::SYSTEM,
+                points: vec![(5.0, 0.1), (10.0, 0.2), (15.0, 0.3)],
+                contour_conf: ContourConfiguration {
+                    indentation_radius: 1e-3,
+                    singular_points: vec![],
+                    w_max: 1e5,
+                    steps: 10_000usize,
+                    boundary: StabilityBoundary::ImaginaryAxis,
+                },
+                search_box: SearchBox {
+                    re_min: 1e-3,
+                    re_max: 20.0,
+                    im_min: -100.0,
+                    im_max: 100.0,
+                },
+                search_settings: ZeroSearchSettings {
+                    edge_steps: 1_000usize,
+                    min_box_size: 1e-6,
+                    newton_tolerance: 1e-12,
+                },
+            },
+        );
+
+        configs.insert(
+            "ln_system1",
+            RootsConfiguration {
+                name: "ln_system1",
+                system: ln_system1::SYSTEM,
+                points: vec![(2.0, 2.0), (10.0, 10.0), (18.0, 5.0)],
+                contour_conf: ContourConfiguration {
+                    indentation_radius: 1e-3,
+                    singular_points: vec![],
+                    w_max: 1e5,
+                    steps: 10_000usize,
+                    boundary: StabilityBoundary::ImaginaryAxis,
+                },
+                search_box: SearchBox {
+                    re_min: 1e-3,
+                    re_max: 20.0,
+                    im_min: -50.0,
+                    im_max: 50.0,
+                },
+                search_settings: ZeroSearchSettings {
+                    edge_steps: 1_000usize,
+                    min_box_size: 1e-6,
+                    newton_tolerance: 1e-12,
+                },
+            },
+        );
+
+        configs.insert(
+            "dopid3",
+            RootsConfiguration {
+                name: "dopid3",
+                system: dopid3::SYSTEM,
+                points: vec![(1.0, 1.0), (5.0, 5.0)],
+                contour_conf: ContourConfiguration {
+                    indentation_radius: 1e-3,
+                    singular_points: vec![],
+                    w_max: 1e5,
+                    steps: 10_000usize,
+                    boundary: StabilityBoundary::ImaginaryAxis,
+                },
+                search_box: SearchBox {
+                    re_min: 1e-3,
+                    re_max: 10.0,
+                    im_min: -50.0,
+                    im_max: 50.0,
+                },
+                search_settings: ZeroSearchSettings {
+                    edge_steps: 1_000usize,
+                    min_box_size: 1e-6,
+                    newton_tolerance: 1e-12,
+                },
+            },
+        );
+
+        configs.insert(
+            "test_configuration",
+            RootsConfiguration {
+                name: "test_configuration",
+                system: test_system::SYSTEM,
+                points: vec![(0.2, 0.2), (1.0, 1.0)],
+                contour_conf: ContourConfiguration {
+                    indentation_radius: 1e-3,
+                    singular_points: vec![],
+                    w_max: 1e2,
+                    steps: 1_000usize,
+                    boundary: StabilityBoundary::ImaginaryAxis,
+                },
+                search_box: SearchBox {
+                    re_min: 1e-3,
+                    re_max: 10.0,
+                    im_min: -10.0,
+                    im_max: 10.0,
+                },
+                search_settings: ZeroSearchSettings {
+                    edge_steps: 1_000usize,
+                    min_box_size: 1e-6,
+                    newton_tolerance: 1e-12,
+                },
+            },
+        );
+
+        configs
+    };
+}
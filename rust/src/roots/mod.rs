@@ -0,0 +1,538 @@
+mod configurations;
+
+use log::{debug, info, warn};
+use rayon::prelude::*;
+
+use crate::Args;
+use crate::nu::{self, ContourMoments, ContourPiece};
+use crate::types::{Comp, Par};
+use crate::utils::storage;
+pub use configurations::{RootsConfiguration, SearchBox, ZeroSearchSettings, CONFIGURATIONS};
+
+
+#[derive(Debug, serde::Serialize)]
+pub struct Root {
+    re: f64,
+    im: f64,
+    multiplicity: u32,
+    residual: f64,
+}
+
+
+#[derive(Debug, serde::Serialize)]
+pub struct RootsPointResult {
+    p: Par,
+    nu: i32,
+    roots: Vec<Root>,
+    reliable: bool,
+}
+
+
+#[derive(Debug, serde::Serialize)]
+pub struct RootsResult {
+    point_results: Vec<RootsPointResult>,
+    search_box: SearchBox,
+    parameters: (&'static str, &'static str),
+}
+
+
+/* Boxes enclosing more zeros than this are subdivided before solving for the zeros */
+const MAX_MOMENT_ORDER: usize = 3;
+
+/* Maximal number of box subdivisions */
+const MAX_SUBDIVISION_DEPTH: u32 = 60;
+
+/* Boxes are not split in half, lest zeros of symmetric systems fall onto the edges */
+const SPLIT_RATIO: f64 = 0.4837;
+
+/* Relative (to box size) distance below which zero estimates are considered the same zero */
+const CLUSTER_TOLERANCE: f64 = 1e-3;
+
+/* Relative distance below which polished zeros are considered the same zero */
+const MERGE_TOLERANCE: f64 = 1e-6;
+
+const NEWTON_MAX_ITERATIONS: u32 = 100;
+const DURAND_KERNER_ITERATIONS: u32 = 500;
+const DERIVATIVE_STEP: f64 = 1e-7;
+
+
+/* Approximate location of a zero, along with the number of zeros clustered in it */
+#[derive(Debug, Clone, Copy)]
+pub struct ZeroEstimate {
+    pub s: Comp,
+    pub multiplicity: u32,
+}
+
+
+impl SearchBox {
+    fn center(&self) -> Comp {
+        Comp::new(0.5 * (self.re_min + self.re_max), 0.5 * (self.im_min + self.im_max))
+    }
+
+    fn size(&self) -> f64 {
+        f64::max(self.re_max - self.re_min, self.im_max - self.im_min)
+    }
+
+    pub fn contains(&self, s: Comp) -> bool {
+        s.re >= self.re_min && s.re <= self.re_max && s.im >= self.im_min && s.im <= self.im_max
+    }
+
+    /* Counterclockwise boundary of the box */
+    fn get_contour(&self) -> Vec<ContourPiece> {
+        let corners = [
+            Comp::new(self.re_min, self.im_min),
+            Comp::new(self.re_max, self.im_min),
+            Comp::new(self.re_max, self.im_max),
+            Comp::new(self.re_min, self.im_max),
+        ];
+
+        (0..corners.len())
+            .map(|i| ContourPiece::Segment { from: corners[i], to: corners[(i+1) % corners.len()] })
+            .collect()
+    }
+
+    /* Split the box across its longer side */
+    fn split(&self) -> (SearchBox, SearchBox) {
+        let mut first = *self;
+        let mut second = *self;
+
+        if self.re_max - self.re_min >= self.im_max - self.im_min {
+            let re_split = self.re_min + SPLIT_RATIO * (self.re_max - self.re_min);
+            first.re_max = re_split;
+            second.re_min = re_split;
+        } else {
+            let im_split = self.im_min + SPLIT_RATIO * (self.im_max - self.im_min);
+            first.im_max = im_split;
+            second.im_min = im_split;
+        }
+
+        (first, second)
+    }
+}
+
+
+fn derivative(f: &dyn Fn(Comp) -> Comp, s: Comp) -> Comp {
+    let h = DERIVATIVE_STEP * f64::max(1.0, s.norm());
+    (f(s + h) - f(s - h)) / (2.0 * h)
+}
+
+
+/* Convert power sums p_1..p_n of n numbers into coefficients of their monic polynomial,
+ * starting from the highest power (Newton's identities) */
+fn power_sums_to_polynomial(power_sums: &[Comp], n: usize) -> Vec<Comp> {
+    let mut elementary = vec![Comp::new(1.0, 0.0)];
+
+    for k in 1..=n {
+        let mut sum = Comp::new(0.0, 0.0);
+        for i in 1..=k {
+            let sign = if i % 2 == 1 { 1.0 } else { -1.0 };
+            sum += sign * elementary[k-i] * power_sums[i];
+        }
+        elementary.push(sum / k as f64);
+    }
+
+    elementary
+        .iter()
+        .enumerate()
+        .map(|(k, e)| if k % 2 == 0 { *e } else { -*e })
+        .collect()
+}
+
+
+/* Find all roots of a monic polynomial using the Durand-Kerner method */
+fn polynomial_roots(coefficients: &[Comp]) -> Vec<Comp> {
+    let n = coefficients.len() - 1;
+    let evaluate = |z: Comp| coefficients.iter().fold(Comp::new(0.0, 0.0), |acc, c| acc * z + c);
+    let seed = Comp::new(0.4, 0.9);
+    let mut roots: Vec<Comp> = (0..n).map(|i| seed.powu(i as u32)).collect();
+
+    for _ in 0..DURAND_KERNER_ITERATIONS {
+        for i in 0..n {
+            let mut denominator = Comp::new(1.0, 0.0);
+            for j in 0..n {
+                if i != j {
+                    denominator *= roots[i] - roots[j];
+                }
+            }
+            let correction = evaluate(roots[i]) / denominator;
+            roots[i] -= correction;
+        }
+    }
+
+    roots
+}
+
+
+/* Group close zero estimates into a single estimate with the corresponding multiplicity */
+fn cluster_estimates(estimates: &[Comp], tolerance: f64) -> Vec<ZeroEstimate> {
+    let mut clusters: Vec<(Comp, u32)> = Vec::new();
+
+    for s in estimates {
+        match clusters.iter_mut().find(|(sum, count)| (sum / *count as f64 - s).norm() < tolerance) {
+            Some((sum, count)) => {
+                *sum += s;
+                *count += 1;
+            },
+            None => clusters.push((*s, 1)),
+        }
+    }
+
+    clusters
+        .iter()
+        .map(|(sum, count)| ZeroEstimate { s: sum / *count as f64, multiplicity: *count })
+        .collect()
+}
+
+
+/* Merge estimates which converged to the same zero, accumulating their multiplicities */
+fn merge_estimates(estimates: &[ZeroEstimate]) -> Vec<ZeroEstimate> {
+    let mut merged: Vec<ZeroEstimate> = Vec::new();
+
+    for estimate in estimates {
+        let tolerance = MERGE_TOLERANCE * f64::max(1.0, estimate.s.norm());
+        match merged.iter_mut().find(|other| (other.s - estimate.s).norm() < tolerance) {
+            Some(other) => other.multiplicity += estimate.multiplicity,
+            None => merged.push(*estimate),
+        }
+    }
+
+    merged
+}
+
+
+/* Polish a zero estimate using Newton's method, modified for zeros with multiplicity */
+pub fn polish_zero(f: &dyn Fn(Comp) -> Comp, estimate: ZeroEstimate, tolerance: f64) -> Option<Comp> {
+    let mut s = estimate.s;
+
+    for _ in 0..NEWTON_MAX_ITERATIONS {
+        let step = estimate.multiplicity as f64 * f(s) / derivative(f, s);
+
+        if step.is_nan() {
+            return Option::None;
+        }
+        s -= step;
+
+        if step.norm() < tolerance * f64::max(1.0, s.norm()) {
+            return Some(s);
+        }
+    }
+
+    Option::None
+}
+
+
+/* Estimate zeros inside a box enclosing only a few of them */
+fn solve_box(
+    f: &dyn Fn(Comp) -> Comp,
+    search_box: &SearchBox,
+    moments: &ContourMoments,
+    settings: &ZeroSearchSettings,
+) -> Vec<ZeroEstimate>
+{
+    let n = moments.zeros as usize;
+    let center = search_box.center();
+    let scale = 0.5 * search_box.size();
+
+    let coefficients = power_sums_to_polynomial(&moments.power_sums, n);
+    let normalized_roots = polynomial_roots(&coefficients);
+    let roots: Vec<Comp> = normalized_roots.iter().map(|z| center + scale * z).collect();
+    let clusters = cluster_estimates(&roots, CLUSTER_TOLERANCE * scale);
+
+    clusters
+        .iter()
+        .map(|estimate| {
+            match polish_zero(f, *estimate, settings.newton_tolerance) {
+                Some(s) if search_box.contains(s) => ZeroEstimate { s, ..*estimate },
+                _ => {
+                    warn!("Newton failed to polish zero estimate {}", estimate.s);
+                    *estimate
+                },
+            }
+        })
+        .collect()
+}
+
+
+/* Count zeros inside the box, along with their normalized power sums */
+pub fn count_zeros(
+    f: &dyn Fn(Comp) -> Comp,
+    search_box: &SearchBox,
+    settings: &ZeroSearchSettings,
+) -> ContourMoments
+{
+    let contour = search_box.get_contour();
+
+    nu::calculate_moments(
+        &contour,
+        f,
+        settings.edge_steps,
+        search_box.center(),
+        0.5 * search_box.size(),
+        MAX_MOMENT_ORDER,
+    )
+}
+
+
+/* Recursively subdivide the box until its zeros can be found from its moments */
+fn locate_zeros_recursive(
+    f: &dyn Fn(Comp) -> Comp,
+    search_box: &SearchBox,
+    settings: &ZeroSearchSettings,
+    depth: u32,
+    reliable: &mut bool,
+) -> Vec<ZeroEstimate>
+{
+    let moments = count_zeros(f, search_box, settings);
+    debug!("Box {:?} at depth {} contains {} zeros", search_box, depth, moments.zeros);
+
+    if !moments.reliable {
+        warn!("Unreliable zero count {} in box {:?}", moments.zeros, search_box);
+        *reliable = false;
+    }
+
+    if moments.zeros <= 0 {
+        return Vec::new();
+    }
+
+    let zeros = moments.zeros as usize;
+    let tiny_box = search_box.size() < settings.min_box_size || depth >= MAX_SUBDIVISION_DEPTH;
+
+    if zeros <= MAX_MOMENT_ORDER {
+        return solve_box(f, search_box, &moments, settings);
+    }
+    if tiny_box {
+        warn!("Box {:?} cannot be subdivided further; it contains {} zeros", search_box, zeros);
+        return vec![ZeroEstimate { s: search_box.center(), multiplicity: zeros as u32 }];
+    }
+
+    let (first, second) = search_box.split();
+    let mut estimates = locate_zeros_recursive(f, &first, settings, depth + 1, reliable);
+    estimates.extend(locate_zeros_recursive(f, &second, settings, depth + 1, reliable));
+    estimates
+}
+
+
+/* Locate all zeros inside the box; the flag indicates whether all zero counts were trusted */
+pub fn locate_zeros(
+    f: &dyn Fn(Comp) -> Comp,
+    search_box: &SearchBox,
+    settings: &ZeroSearchSettings,
+) -> (Vec<ZeroEstimate>, bool)
+{
+    let mut reliable = true;
+    let estimates = locate_zeros_recursive(f, search_box, settings, 0, &mut reliable);
+
+    (merge_estimates(&estimates), reliable)
+}
+
+
+pub fn calculate_roots_single(conf: &RootsConfiguration, p: Par) -> RootsPointResult {
+    let f = |s: Comp| (conf.system.f_complex)(s, p);
+    let nu_estimate = nu::calculate_nu_detailed(&conf.contour_conf, &conf.system, p);
+    let (estimates, boxes_reliable) = locate_zeros(&f, &conf.search_box, &conf.search_settings);
+    let reliable = nu_estimate.reliable && boxes_reliable;
+    let mut roots: Vec<Root> = estimates
+        .iter()
+        .filter(|estimate| {
+            let finite = estimate.s.is_finite();
+            if !finite {
+                warn!("Dropping non-finite zero estimate {} at {:?}", estimate.s, p);
+            }
+            finite
+        })
+        .map(|estimate| Root {
+            re: estimate.s.re,
+            im: estimate.s.im,
+            multiplicity: estimate.multiplicity,
+            residual: f(estimate.s).norm(),
+        })
+        .collect();
+    roots.sort_by(|a, b| b.re.total_cmp(&a.re));
+
+    let found: u32 = roots.iter().map(|root| root.multiplicity).sum();
+    if found as i32 != nu_estimate.nu {
+        warn!("Located {} zeros at {:?}, but nu={}; some zeros lie outside the search box",
+            found, p, nu_estimate.nu);
+    }
+
+    info!("Located {} zeros at {:?} (nu={})", found, p, nu_estimate.nu);
+    for root in roots.iter() {
+        info!("  s = {} + {}j (multiplicity {}, |f(s)| = {:e})",
+            root.re, root.im, root.multiplicity, root.residual);
+    }
+
+    RootsPointResult { p, nu: nu_estimate.nu, roots, reliable }
+}
+
+
+fn calculate_roots(conf: &'static RootsConfiguration) -> RootsResult {
+    let point_results = conf.points
+        .par_iter()
+        .map(|p| calculate_roots_single(conf, *p))
+        .collect();
+
+    RootsResult {
+        point_results,
+        search_box: conf.search_box,
+        parameters: conf.system.parameters,
+    }
+}
+
+
+pub fn run(args: &Args) {
+    /* Locate zeros */
+    let config_name = args.configuration
+        .as_ref()
+        .expect("roots requires configuration to be specified");
+    let config = CONFIGURATIONS.get(config_name.as_str()).expect("Unknown configuration");
+    let results = calculate_roots(config);
+
+    /* Store results in file */
+    let filename = storage::get_filepath("roots", "temp_data", "rootsdata", config_name);
+    storage::store_results(results, &filename);
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::test_system;
+
+    const SETTINGS: ZeroSearchSettings = ZeroSearchSettings {
+        edge_steps: 1_000,
+        min_box_size: 1e-6,
+        newton_tolerance: 1e-12,
+    };
+
+    fn assert_comps_eq(x: Comp, y: Comp, assertion_eps: f64) {
+        assert!((x - y).norm() < assertion_eps, "{} != {}", x, y);
+    }
+
+    /* Zeros of test_system are (-a +- sqrt(4 - 4b - 3a^2)) / 2 */
+    fn get_test_system_zeros(p: Par) -> (Comp, Comp) {
+        let discriminant = Comp::new(4.0 - 4.0*p.1 - 3.0*p.0.powi(2), 0.0).sqrt();
+        ((-p.0 + discriminant) / 2.0, (-p.0 - discriminant) / 2.0)
+    }
+
+    fn sort_estimates(mut estimates: Vec<ZeroEstimate>) -> Vec<ZeroEstimate> {
+        estimates.sort_by(|a, b| b.s.re.total_cmp(&a.s.re).then(b.s.im.total_cmp(&a.s.im)));
+        estimates
+    }
+
+    #[test]
+    fn test_power_sums_to_polynomial() {
+        /* Zeros 1, 2, 3 have power sums 3, 6, 14, 36 */
+        let power_sums: Vec<Comp> = [3.0, 6.0, 14.0, 36.0].iter().map(|p| Comp::new(*p, 0.0)).collect();
+        let coefficients = power_sums_to_polynomial(&power_sums, 3);
+        for (c, expected) in coefficients.iter().zip([1.0, -6.0, 11.0, -6.0]) {
+            assert_comps_eq(*c, Comp::new(expected, 0.0), 1e-12);
+        }
+
+        /* Zeros +-j */
+        let power_sums = [Comp::new(2.0, 0.0), Comp::new(0.0, 0.0), Comp::new(-2.0, 0.0)];
+        let coefficients = power_sums_to_polynomial(&power_sums, 2);
+        for (c, expected) in coefficients.iter().zip([1.0, 0.0, 1.0]) {
+            assert_comps_eq(*c, Comp::new(expected, 0.0), 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_polynomial_roots() {
+        /* (z - 0.5j)(z + 0.5) */
+        let coefficients = [Comp::new(1.0, 0.0), Comp::new(0.5, -0.5), Comp::new(0.0, -0.25)];
+        let roots = polynomial_roots(&coefficients);
+        assert_eq!(roots.len(), 2);
+        assert!(roots.iter().any(|z| (z - Comp::new(0.0, 0.5)).norm() < 1e-12));
+        assert!(roots.iter().any(|z| (z - Comp::new(-0.5, 0.0)).norm() < 1e-12));
+
+        /* (z - 0.5)^2 (z + 0.5); the double root is found only to about the square root of precision,
+         * so its estimates are clustered */
+        let coefficients = [
+            Comp::new(1.0, 0.0), Comp::new(-0.5, 0.0), Comp::new(-0.25, 0.0), Comp::new(0.125, 0.0)];
+        let roots = polynomial_roots(&coefficients);
+        let clusters = cluster_estimates(&roots, CLUSTER_TOLERANCE);
+        assert_eq!(clusters.len(), 2);
+        for cluster in clusters {
+            match cluster.multiplicity {
+                2 => assert_comps_eq(cluster.s, Comp::new(0.5, 0.0), 1e-6),
+                _ => assert_comps_eq(cluster.s, Comp::new(-0.5, 0.0), 1e-12),
+            }
+        }
+    }
+
+    #[test]
+    fn test_polish_zero() {
+        let p = (0.2, 0.2);
+        let f = |s: Comp| (test_system::SYSTEM.f_complex)(s, p);
+        let (unstable, stable) = get_test_system_zeros(p);
+        let estimate = ZeroEstimate { s: unstable + 0.1, multiplicity: 1 };
+        assert_comps_eq(polish_zero(&f, estimate, 1e-12).unwrap(), unstable, 1e-10);
+        let estimate = ZeroEstimate { s: stable - 0.1, multiplicity: 1 };
+        assert_comps_eq(polish_zero(&f, estimate, 1e-12).unwrap(), stable, 1e-10);
+
+        /* Newton's method for a double zero converges quadratically only once modified */
+        let f = |s: Comp| (s - 1.0).powi(2) * (s + 2.0);
+        let estimate = ZeroEstimate { s: Comp::new(1.1, 0.05), multiplicity: 2 };
+        assert_comps_eq(polish_zero(&f, estimate, 1e-12).unwrap(), Comp::new(1.0, 0.0), 1e-6);
+    }
+
+    #[test]
+    fn test_split() {
+        let search_box = SearchBox { re_min: 0.0, re_max: 2.0, im_min: -0.5, im_max: 0.7 };
+        let (first, second) = search_box.split();
+        assert_eq!((first.re_min, first.re_max), (0.0, 2.0 * SPLIT_RATIO));
+        assert_eq!((second.re_min, second.re_max), (2.0 * SPLIT_RATIO, 2.0));
+        assert_eq!((first.im_min, first.im_max), (-0.5, 0.7));
+        assert_eq!((second.im_min, second.im_max), (-0.5, 0.7));
+
+        /* Taller boxes are split across the imaginary axis */
+        let (first, second) = second.split();
+        assert_eq!((first.im_min, first.im_max), (-0.5, -0.5 + SPLIT_RATIO * 1.2));
+        assert_eq!((second.im_min, second.im_max), (-0.5 + SPLIT_RATIO * 1.2, 0.7));
+        assert_eq!((first.re_min, first.re_max), (2.0 * SPLIT_RATIO, 2.0));
+    }
+
+    #[test]
+    fn test_locate_zeros() {
+        let search_box = SearchBox { re_min: -3.0, re_max: 3.0, im_min: -1.9, im_max: 2.1 };
+
+        /* Complex pair of test_system, (1 +- j) / 2 */
+        let p = (-1.0, 0.5);
+        let f = |s: Comp| (test_system::SYSTEM.f_complex)(s, p);
+        let (estimates, reliable) = locate_zeros(&f, &search_box, &SETTINGS);
+        let estimates = sort_estimates(estimates);
+        assert!(reliable);
+        assert_eq!(estimates.len(), 2);
+        assert_comps_eq(estimates[0].s, Comp::new(0.5, 0.5), 1e-9);
+        assert_comps_eq(estimates[1].s, Comp::new(0.5, -0.5), 1e-9);
+
+        /* Double zero, along with more zeros than a single box is solved for */
+        let f = |s: Comp| (s - 1.0).powi(2) * (s + 2.0) * (s - Comp::new(0.5, 1.0)) * (s - Comp::new(0.5, -1.0));
+        let (estimates, reliable) = locate_zeros(&f, &search_box, &SETTINGS);
+        let estimates = sort_estimates(estimates);
+        assert!(reliable);
+        let multiplicities: Vec<u32> = estimates.iter().map(|estimate| estimate.multiplicity).collect();
+        assert_eq!(multiplicities, vec![2, 1, 1, 1]);
+        assert_comps_eq(estimates[0].s, Comp::new(1.0, 0.0), 1e-6);
+        assert_comps_eq(estimates[1].s, Comp::new(0.5, 1.0), 1e-9);
+        assert_comps_eq(estimates[2].s, Comp::new(0.5, -1.0), 1e-9);
+        assert_comps_eq(estimates[3].s, Comp::new(-2.0, 0.0), 1e-9);
+    }
+
+    #[test]
+    fn test_calculate_roots_single() {
+        /* Only the larger real zero is unstable at the first point, none at the second */
+        let conf = &CONFIGURATIONS["test_configuration"];
+        let result = calculate_roots_single(conf, (0.2, 0.2));
+        let (unstable, _) = get_test_system_zeros((0.2, 0.2));
+        assert_eq!(result.nu, 1);
+        assert!(result.reliable);
+        assert_eq!(result.roots.len(), 1);
+        assert!((result.roots[0].re - unstable.re).abs() < 1e-9);
+        assert!(result.roots[0].im.abs() < 1e-9);
+
+        let result = calculate_roots_single(conf, (1.0, 1.0));
+        assert_eq!(result.nu, 0);
+        assert!(result.roots.is_empty());
+    }
+}
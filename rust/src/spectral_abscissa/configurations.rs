@@ -0,0 +1,161 @@
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+
+use crate::roots::{SearchBox, ZeroSearchSettings};
+use crate::systems::distributed_delay1;
+use crate::systems::ln_system1;
+use crate::systems::retarded1;
+use crate::systems::retarded2;
+use crate::systems::test_system;
+use crate::types::{Limits, System};
+
+
+pub struct SpectralAbscissaConfiguration {
+    pub name: &'static str,
+    pub system: System,
+    pub limits: Limits,
+    pub grid_step: usize,
+    pub search_box: SearchBox,               // Rectangle in which the rightmost zero is sought
+    pub search_settings: ZeroSearchSettings,
+}
+
+
+/* Global collection of all spectral abscissa configurations */
+lazy_static! {
+    pub static ref CONFIGURATIONS: HashMap<&'static str, SpectralAbscissaConfiguration> = {
+        let mut configs = HashMap::new();
+
+        configs.insert(
+            "retarded1",
+            SpectralAbscissaConfiguration {
+                name: "retarded1",
+                system: retarded1::SYSTEM,
+                limits: Limits {
+                    p1_min: 0.0,
+                    p1_max: 2.6,
+                    p2_min: 0.0,
+                    p2_max: 3.3,
+                },
+                grid_step: 40usize,
+                search_box: SearchBox {
+                    re_min: -3.0,
+                    re_max: 5.0,
+                    im_min: -20.0,
+                    im_max: 20.0,
+                },
+                search_settings: ZeroSearchSettings {
+                    edge_steps: 1_000usize,
+                    min_box_size: 1e-6,
+                    newton_tolerance: 1e-12,
+                },
+            },
+        );
+
+        configs.insert(
+            "retarded2",
+            SpectralAbscissaConfiguration {
+                name: "retarded2",
+                system: retarded2::SYSTEM,
+                limits: Limits {
+                    p1_min: 0.0,
+                    p1_max: 5.0,
+                    p2_min: 0.0,
+                    p2_max: 5.0,
+                },
+                grid_step: 40usize,
+                search_box: SearchBox {
+                    re_min: -3.0,
+                    re_max: 5.0,
+                    im_min: -20.0,
+                    im_max: 20.0,
+                },
+                search_settings: ZeroSearchSettings {
+                    edge_steps: 1_000usize,
+                    min_box_size: 1e-6,
+                    newton_tolerance: 1e-12,
+                },
+            },
+        );
+
+        configs.insert(
+            "distributed_delay1",
+            SpectralAbscissaConfiguration {
+                name: "distributed_delay1",
+                system: distributed_delay1::SYSTEM,
+                limits: Limits {
+                    p1_min: 0.0,
+                    p1_max: 20.0,
+                    p2_min: 0.0,
+                    p2_max: 0.4,
+                },
+                grid_step: 40usize,
+                search_box: SearchBox {
+                    re_min: -1.0,
+                    re_max: 5.0,
+                    im_min: -20.0,
+                    im_max: 20.0,
+                },
+                search_settings: ZeroSearchSettings {
+                    edge_steps: 1_000usize,
+                    min_box_size: 1e-6,
+                    newton_tolerance: 1e-12,
+                },
+            },
+        );
+
+        /* The logarithm has a branch cut along the negative real axis */
+        configs.insert(
+            "ln_system1",
+            SpectralAbscissaConfiguration {
+                name: "ln_system1",
+                system: ln_system1::SYSTEM,
+                limits: Limits {
+                    p1_min: 0.0,
+                    p1_max: 20.0,
+                    p2_min: 0.0,
+                    p2_max: 20.0,
+                },
+                grid_step: 20usize,
+                search_box: SearchBox {
+                    re_min: 1e-3,
+                    re_max: 20.0,
+                    im_min: -50.0,
+                    im_max: 50.0,
+                },
+                search_settings: ZeroSearchSettings {
+                    edge_steps: 1_000usize,
+                    min_box_size: 1e-6,
+                    newton_tolerance: 1e-12,
+                },
+            },
+        );
+
+        configs.insert(
+            "test_configuration",
+            SpectralAbscissaConfiguration {
+                name: "test_configuration",
+                system: test_system::SYSTEM,
+                limits: Limits {
+                    p1_min: 0.0,
+                    p1_max: 1.2,
+                    p2_min: 0.0,
+                    p2_max: 1.2,
+                },
+                grid_step: 20usize,
+                search_box: SearchBox {
+                    re_min: -5.0,
+                    re_max: 5.0,
+                    im_min: -5.0,
+                    im_max: 5.0,
+                },
+                search_settings: ZeroSearchSettings {
+                    edge_steps: 1_000usize,
+                    min_box_size: 1e-6,
+                    newton_tolerance: 1e-12,
+                },
+            },
+        );
+
+        configs
+    };
+}
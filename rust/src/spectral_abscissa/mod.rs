@@ -0,0 +1,265 @@
+mod configurations;
+
+use log::{debug, info, warn};
+use iter_num_tools::lin_space;
+use rayon::prelude::*;
+
+use crate::Args;
+use crate::roots::{self, SearchBox, ZeroEstimate};
+use crate::types::{Comp, Limits, Par};
+use crate::utils::storage;
+pub use configurations::{SpectralAbscissaConfiguration, CONFIGURATIONS};
+
+
+#[derive(Debug, serde::Serialize)]
+pub struct SpectralAbscissaPointResult {
+    p: Par,
+    abscissa: Option<f64>,   // None if there are no zeros inside the search box
+    frequency: Option<f64>,  // Imaginary part of the rightmost zero
+    seeded: bool,
+    reliable: bool,
+}
+
+
+#[derive(Debug, serde::Serialize)]
+pub struct SpectralAbscissaResult {
+    point_results: Vec<SpectralAbscissaPointResult>,
+    limits: &'static Limits,
+    search_box: SearchBox,
+    parameters: (&'static str, &'static str),
+}
+
+
+/* Width (relative to the search box) of the strip right of a seeded zero which is not searched */
+const SEED_MARGIN: f64 = 1e-4;
+
+
+/* Rightmost of the given zeros */
+fn get_rightmost(zeros: &[Comp]) -> Option<Comp> {
+    zeros.iter().cloned().fold(Option::None, |rightmost, s| match rightmost {
+        Some(r) if r.re >= s.re => Some(r),
+        _ => Some(s),
+    })
+}
+
+
+/* Rightmost zero within the box along with the reliability of the search */
+fn locate_rightmost(f: &dyn Fn(Comp) -> Comp, search_box: &SearchBox, conf: &SpectralAbscissaConfiguration)
+    -> (Option<Comp>, bool)
+{
+    let (estimates, reliable) = roots::locate_zeros(f, search_box, &conf.search_settings);
+    let zeros: Vec<Comp> = estimates.iter().map(|estimate| estimate.s).collect();
+
+    (get_rightmost(&zeros), reliable)
+}
+
+
+/* Confirm that the rightmost polished seed is the rightmost zero, or search further right */
+fn locate_rightmost_seeded(
+    f: &dyn Fn(Comp) -> Comp,
+    seed: Comp,
+    conf: &SpectralAbscissaConfiguration,
+) -> Option<(Option<Comp>, bool)>
+{
+    let search_box = &conf.search_box;
+    let margin = SEED_MARGIN * (search_box.re_max - search_box.re_min);
+    let right_box = SearchBox { re_min: seed.re + margin, ..*search_box };
+
+    if right_box.re_min >= right_box.re_max {
+        return Some((Some(seed), true));
+    }
+
+    let moments = roots::count_zeros(f, &right_box, &conf.search_settings);
+    if !moments.reliable {
+        return Option::None;
+    }
+    if moments.zeros == 0 {
+        return Some((Some(seed), true));
+    }
+
+    /* Some zeros lie right of the seed; find them */
+    let (rightmost, reliable) = locate_rightmost(f, &right_box, conf);
+    Some((rightmost.or(Some(seed)), reliable))
+}
+
+
+fn calculate_abscissa_single(conf: &SpectralAbscissaConfiguration, p: Par, seeds: &[Comp])
+    -> SpectralAbscissaPointResult
+{
+    let f = |s: Comp| (conf.system.f_complex)(s, p);
+    let tolerance = conf.search_settings.newton_tolerance;
+
+    /* Neighbouring zeros converge to nearby zeros of this point */
+    let polished: Vec<Comp> = seeds
+        .iter()
+        .filter_map(|s| roots::polish_zero(&f, ZeroEstimate { s: *s, multiplicity: 1 }, tolerance))
+        .filter(|s| conf.search_box.contains(*s))
+        .collect();
+
+    let seeded_result = get_rightmost(&polished)
+        .and_then(|seed| locate_rightmost_seeded(&f, seed, conf));
+    let seeded = seeded_result.is_some();
+
+    let (rightmost, reliable) = match seeded_result {
+        Some(result) => result,
+        None => locate_rightmost(&f, &conf.search_box, conf),
+    };
+
+    if !reliable {
+        warn!("Unreliable spectral abscissa at {:?}", p);
+    }
+    debug!("Rightmost zero at {:?} is {:?} (seeded: {})", p, rightmost, seeded);
+
+    SpectralAbscissaPointResult {
+        p,
+        abscissa: rightmost.map(|s| s.re),
+        frequency: rightmost.map(|s| s.im),
+        seeded,
+        reliable,
+    }
+}
+
+
+/* Rightmost zeros of the previous row around the given column */
+fn get_seeds(previous_row: &[SpectralAbscissaPointResult], column: usize) -> Vec<Comp> {
+    let first = column.saturating_sub(1);
+    let last = usize::min(column + 1, previous_row.len().saturating_sub(1));
+
+    previous_row
+        .get(first..=last)
+        .unwrap_or(&[])
+        .iter()
+        .filter_map(|result| match (result.abscissa, result.frequency) {
+            (Some(re), Some(im)) => Some(Comp::new(re, im)),
+            _ => Option::None,
+        })
+        .collect()
+}
+
+
+/* Rows are computed one after another, so that each row is seeded by the previous one */
+fn calculate_spectral_abscissa(conf: &'static SpectralAbscissaConfiguration) -> SpectralAbscissaResult {
+    let p1_values: Vec<f64> = lin_space(conf.limits.p1_min..=conf.limits.p1_max, conf.grid_step).collect();
+    let p2_values: Vec<f64> = lin_space(conf.limits.p2_min..=conf.limits.p2_max, conf.grid_step).collect();
+    let mut point_results: Vec<SpectralAbscissaPointResult> = Vec::with_capacity(conf.grid_step.pow(2));
+    let mut row_start = 0;
+
+    for (row, p2) in p2_values.iter().enumerate() {
+        let previous_row = &point_results[row_start..];
+        let row_results: Vec<SpectralAbscissaPointResult> = p1_values
+            .par_iter()
+            .enumerate()
+            .map(|(column, p1)| calculate_abscissa_single(conf, (*p1, *p2), &get_seeds(previous_row, column)))
+            .collect();
+
+        info!("Calculated spectral abscissa for row {}/{}", row + 1, conf.grid_step);
+        row_start = point_results.len();
+        point_results.extend(row_results);
+    }
+
+    let seeded = point_results.iter().filter(|result| result.seeded).count();
+    info!("Spectral abscissa of {}/{} points obtained from neighbouring seeds", seeded, point_results.len());
+
+    SpectralAbscissaResult {
+        point_results,
+        limits: &conf.limits,
+        search_box: conf.search_box,
+        parameters: conf.system.parameters,
+    }
+}
+
+
+pub fn run(args: &Args) {
+    /* Calculate spectral abscissa */
+    let config_name = args.configuration
+        .as_ref()
+        .expect("spectral-abscissa requires configuration to be specified");
+    let config = CONFIGURATIONS.get(config_name.as_str()).expect("Unknown configuration");
+    let results = calculate_spectral_abscissa(config);
+
+    /* Store results in file */
+    let filename = storage::get_filepath("spectral_abscissa", "temp_data", "sadata", config_name);
+    storage::store_results(results, &filename);
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roots::ZeroSearchSettings;
+    use crate::systems::test_system;
+
+    /* Rightmost zero of test_system, (-a + sqrt(4 - 4b - 3a^2)) / 2, with nonnegative imaginary part */
+    fn get_test_system_rightmost(p: Par) -> Comp {
+        let discriminant = Comp::new(4.0 - 4.0*p.1 - 3.0*p.0.powi(2), 0.0).sqrt();
+        (-p.0 + discriminant) / 2.0
+    }
+
+    /* Double zeros, e.g. at a=0, b=1, are located only to about the square root of precision */
+    fn assert_rightmost(result: &SpectralAbscissaPointResult, p: Par) {
+        let rightmost = get_test_system_rightmost(p);
+        assert!(result.reliable);
+        assert!((result.abscissa.unwrap() - rightmost.re).abs() < 1e-6,
+            "Abscissa {:?} at {:?}, expected {}", result.abscissa, p, rightmost.re);
+        assert!((result.frequency.unwrap().abs() - rightmost.im).abs() < 1e-6,
+            "Frequency {:?} at {:?}, expected {}", result.frequency, p, rightmost.im);
+    }
+
+    #[test]
+    fn test_calculate_abscissa_single() {
+        let conf = &CONFIGURATIONS["test_configuration"];
+
+        /* Real and complex rightmost zeros, both stable and unstable */
+        for p in [(0.2, 0.2), (0.0, 0.5), (0.6, 0.9), (1.0, 1.0), (0.0, 1.2)] {
+            let unseeded = calculate_abscissa_single(conf, p, &[]);
+            assert!(!unseeded.seeded);
+            assert_rightmost(&unseeded, p);
+
+            /* Seeds from a neighbouring point, and a seed converging to the other zero */
+            let neighbour = get_test_system_rightmost((p.0 + 0.05, p.1 + 0.05));
+            let other = Comp::new(-p.0, 0.0) - get_test_system_rightmost(p);
+            let seeded = calculate_abscissa_single(conf, p, &[neighbour, other.conj()]);
+            assert!(seeded.seeded);
+            assert_rightmost(&seeded, p);
+        }
+    }
+
+    #[test]
+    fn test_get_seeds() {
+        let get_result = |abscissa: Option<f64>| SpectralAbscissaPointResult {
+            p: (0.0, 0.0),
+            abscissa,
+            frequency: abscissa.map(|re| -re),
+            seeded: false,
+            reliable: true,
+        };
+        let row = vec![get_result(Some(1.0)), get_result(None), get_result(Some(3.0)), get_result(Some(4.0))];
+
+        assert_eq!(get_seeds(&row, 0), vec![Comp::new(1.0, -1.0)]);
+        assert_eq!(get_seeds(&row, 2), vec![Comp::new(3.0, -3.0), Comp::new(4.0, -4.0)]);
+        assert_eq!(get_seeds(&row, 3), vec![Comp::new(3.0, -3.0), Comp::new(4.0, -4.0)]);
+        assert_eq!(get_seeds(&[], 0), vec![]);
+    }
+
+    #[test]
+    fn test_seeded_matches_unseeded() {
+        let conf: &'static SpectralAbscissaConfiguration = Box::leak(Box::new(SpectralAbscissaConfiguration {
+            name: "test_configuration",
+            system: test_system::SYSTEM,
+            limits: Limits { p1_min: 0.0, p1_max: 1.2, p2_min: 0.0, p2_max: 1.2 },
+            grid_step: 7,
+            search_box: SearchBox { re_min: -5.0, re_max: 5.0, im_min: -5.0, im_max: 5.0 },
+            search_settings: ZeroSearchSettings { edge_steps: 1_000, min_box_size: 1e-6, newton_tolerance: 1e-12 },
+        }));
+
+        /* Rows but the first are mostly seeded, yet every point agrees with a search from scratch */
+        let result = calculate_spectral_abscissa(conf);
+        let seeded_count = result.point_results.iter().filter(|result| result.seeded).count();
+        assert!(seeded_count >= 3 * (result.point_results.len() - conf.grid_step) / 4);
+        for seeded in result.point_results.iter() {
+            let unseeded = calculate_abscissa_single(conf, seeded.p, &[]);
+            assert_rightmost(seeded, seeded.p);
+            assert_rightmost(&unseeded, seeded.p);
+        }
+    }
+}
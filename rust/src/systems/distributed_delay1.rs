@@ -1,4 +1,5 @@
 use crate::types::{Comp, Par, System};
+use crate::utils::batch::KernelTable;
 
 
 fn f_complex(s: Comp, p: Par) -> Comp {
@@ -20,11 +21,36 @@ fn line_denominator(w: f64, p: Par, angle: f64, _th_min: f64, th_max: f64) -> f6
 }
 
 
-pub fn region_fraction_precalculated_numerator<'a>(
-    numerator: &'a [f64],
-    w_logspace: &'a [f64],
+pub fn line_fraction_precalculated_numerator(
+    numerator: &[f64],
+    w_logspace: &[f64],
     origin: Par,
-    eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    angle: f64,
+    _th_min: f64,
+    th_max: f64,
+    out: &mut [f64])
+{
+    let tau0 = origin.0;
+    let k0 = origin.1;
+    let c1 = f64::cos(angle);
+    let c2 = f64::sin(angle);
+    let shift_powi = (tau0*c2 + k0*c1 + th_max).powi(2);
+
+    for ((out, num), w) in out.iter_mut().zip(numerator).zip(w_logspace) {
+        let term1 = f64::abs(c2 * w);
+        let term2 = f64::sqrt(shift_powi + (c1*w).powi(2));
+        *out = num / (term1 + term2);
+    }
+}
+
+
+pub fn region_fraction_precalculated_numerator(
+    numerator: &[f64],
+    w_logspace: &[f64],
+    _kernels: &KernelTable,
+    origin: Par,
+    eps: f64,
+    out: &mut [f64])
 {
     let p1min = origin.0 - eps;
     let p1max = origin.0 + eps;
@@ -35,22 +61,19 @@ pub fn region_fraction_precalculated_numerator<'a>(
     let p2max_powi = p2max.powi(2);
     let p1max_times_exp_term = p1max * exp_term;
 
-    let fraction_iter = numerator
-        .iter()
-        .zip(w_logspace.iter()).map(move |(num, w)| {
-            let gradient_p1 = exp_term * f64::sqrt(w.powi(2) + p2max_powi);
-            let gradient_p2 = w + p1max_times_exp_term;
-            num / (f64::sqrt(gradient_p1.powi(2) + gradient_p2.powi(2)))
-    });
-
-    Box::new(fraction_iter)
+    for ((out, num), w) in out.iter_mut().zip(numerator).zip(w_logspace) {
+        let gradient_p1 = exp_term * f64::sqrt(w.powi(2) + p2max_powi);
+        let gradient_p2 = w + p1max_times_exp_term;
+        *out = num / (f64::sqrt(gradient_p1.powi(2) + gradient_p2.powi(2)));
+    }
 }
 
 
-pub fn region_fraction<'a>(
-    w_linspace: &'a [f64],
+pub fn region_fraction(
+    w_linspace: &[f64],
     origin: Par,
-    eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    eps: f64,
+    out: &mut [f64])
 {
     let p1min = origin.0 - eps;
     let p1max = origin.0 + eps;
@@ -61,19 +84,41 @@ pub fn region_fraction<'a>(
     let p2max_powi = p2max.powi(2);
     let p1max_times_exp_term = p1max * exp_term;
 
-    let fraction_iter = w_linspace
+    for (out, w) in out.iter_mut().zip(w_linspace) {
+        let s = Comp::new(0.0, *w);
+        let tau = origin.0;
+        let k = origin.1;
+        let num = (s.powi(2) + s*k + 1.0 - (-tau*(s+k)).exp()).norm();
+        let gradient_p1 = exp_term * f64::sqrt(w.powi(2) + p2max_powi);
+        let gradient_p2 = w + p1max_times_exp_term;
+        *out = num / (f64::sqrt(gradient_p1.powi(2) + gradient_p2.powi(2)));
+    }
+}
+
+
+/* Bounds of |df/dp1| and |df/dp2| at any s (e.g. off the imaginary axis) over |q_i - p_i| <= eps_i */
+fn region_gradient(s: Comp, p: Par, eps: Par) -> (f64, f64) {
+    let (tau0, k0) = p;
+    let (tau_eps, k_eps) = eps;
+
+    /* |e^(-tau(s+k))| is bilinear in (tau, k) in the exponent, so it peaks in a corner */
+    let corners = [
+        (tau0 - tau_eps, k0 - k_eps),
+        (tau0 - tau_eps, k0 + k_eps),
+        (tau0 + tau_eps, k0 - k_eps),
+        (tau0 + tau_eps, k0 + k_eps),
+    ];
+    let exp_max = corners
         .iter()
-        .map(move |w| {
-            let s = Comp::new(0.0, *w);
-            let tau = origin.0;
-            let k = origin.1;
-            let num = (s.powi(2) + s*k + 1.0 - (-tau*(s+k)).exp()).norm();
-            let gradient_p1 = exp_term * f64::sqrt(w.powi(2) + p2max_powi);
-            let gradient_p2 = w + p1max_times_exp_term;
-            num / (f64::sqrt(gradient_p1.powi(2) + gradient_p2.powi(2)))
-    });
-
-    Box::new(fraction_iter)
+        .map(|(tau, k)| f64::exp(-tau * (s.re + k)))
+        .fold(0.0, f64::max);
+    let tau_max = tau0.abs() + tau_eps;
+    let k_max = k0.abs() + k_eps;
+
+    let gradient_p1 = (s.norm() + k_max) * exp_max;
+    let gradient_p2 = s.norm() + tau_max * exp_max;
+
+    (gradient_p1, gradient_p2)
 }
 
 
@@ -81,8 +126,16 @@ pub const SYSTEM: System = System {
     name: "distributed_delay1",
     f_complex,
     parameters: (r"\tau", r"k"),
+    singular_origin: false,
     region_fraction_precalculated_numerator: Option::Some(region_fraction_precalculated_numerator),
     region_fraction: Option::Some(region_fraction),
+    frequency_kernels: Option::None,
     line_denominator: Option::Some(line_denominator),
+    line_fraction_precalculated_numerator: Option::Some(line_fraction_precalculated_numerator),
     region_denominator: Option::None,
+    region_fraction_lipschitz: Option::None,
+    line_fraction_lipschitz: Option::None,
+    region_fraction_tails: Option::None,
+    line_fraction_tails: Option::None,
+    region_gradient: Option::Some(region_gradient),
 };
\ No newline at end of file
@@ -94,8 +94,16 @@ pub const SYSTEM: System = System {
     name: "dopid1",
     f_complex,
     parameters: (r"v_p", r"v_i"),
+    singular_origin: true,
     region_fraction_precalculated_numerator: Option::None,
     region_fraction: Option::None,
+    frequency_kernels: Option::None,
     line_denominator: Option::None,
+    line_fraction_precalculated_numerator: Option::None,
     region_denominator: Option::None,
+    region_fraction_lipschitz: Option::None,
+    line_fraction_lipschitz: Option::None,
+    region_fraction_tails: Option::None,
+    line_fraction_tails: Option::None,
+    region_gradient: Option::None,
 };
\ No newline at end of file
@@ -3,7 +3,8 @@
  * It is not possible to run the data/figure algorithms on dopid2,
  * since the system violates assumption #5.
  */
-use crate::types::{Comp, Par, System};
+use crate::types::{Comp, FrequencyKernels, Par, System};
+use crate::utils::batch::KernelTable;
 
 
 fn f_complex(s: Comp, p: Par) -> Comp {
@@ -34,62 +35,72 @@ fn f_complex(s: Comp, p: Par) -> Comp {
 // }
 
 
-pub fn region_fraction_precalculated_numerator<'a>(
-    numerator: &'a [f64],
-    w_logspace: &'a [f64],
+/* Norm of the first denominator term, and the real and imaginary part of the second term
+ * without the power of s+1 */
+fn frequency_kernels(w: f64, kernels: &mut [f64]) {
+    let s = Comp::new(0.0, w);
+    let s_ln = s.ln();
+
+    let denom2 = s * s_ln.powi(2) * (s+1.0).ln();
+
+    kernels[0] = (s * s_ln - s + 1.0).norm();
+    kernels[1] = denom2.re;
+    kernels[2] = denom2.im;
+}
+
+
+pub fn region_fraction_precalculated_numerator(
+    numerator: &[f64],
+    w_logspace: &[f64],
+    kernel_table: &KernelTable,
     origin: Par,
-    eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    eps: f64,
+    out: &mut [f64])
 {
     let _vp = origin.0;
     let nu = origin.1;
     let nu_max = nu + eps;
 
-    let fraction_iter = numerator
-        .iter()
-        .zip(w_logspace.iter()).map(move |(num, w)| {
-            let s = Comp::new(0.0, *w);
-            let s_ln = s.ln();
+    assert_eq!(kernel_table.rows().len(), w_logspace.len());
+    let fractions = out.iter_mut().zip(numerator).zip(w_logspace).zip(kernel_table.rows());
+    for (((out, num), w), kernels) in fractions {
+        let s = Comp::new(0.0, *w);
 
-            let denom1 = s * s_ln - s + 1.0;
-            let denom2 = s * s_ln.powi(2) * (s+1.0).ln() * (s+1.0).powf(nu_max);
-            let denom = (denom1.norm().powi(2) + denom2.norm().powi(2)).sqrt();
+        let denom1 = kernels[0];
+        let denom2 = Comp::new(kernels[1], kernels[2]) * (s+1.0).powf(nu_max);
+        let denom = (denom1.powi(2) + denom2.norm().powi(2)).sqrt();
 
-            num / denom
-    });
-
-    Box::new(fraction_iter)
+        *out = num / denom;
+    }
 }
 
 
-pub fn region_fraction<'a>(
-    w_linspace: &'a [f64],
+pub fn region_fraction(
+    w_linspace: &[f64],
     origin: Par,
-    eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    eps: f64,
+    out: &mut [f64])
 {
     let vp = origin.0;
     let nu = origin.1;
     let nu_max = nu + eps;
 
-    let fraction_iter = w_linspace
-        .iter()
-        .map(move |w| {
-            let s = Comp::new(0.0, *w);
-            let s_ln = s.ln();
-            
-            let term1 = s * s_ln.powu(2) * (s+1.0).powf(nu);
-            let term2 = (vp*s-1.0) * s_ln;
-            let term3 = (1.0-vp) * (s-1.0);
-
-            let num = (term1 + term2 + term3).norm();
+    for (out, w) in out.iter_mut().zip(w_linspace) {
+        let s = Comp::new(0.0, *w);
+        let s_ln = s.ln();
+        
+        let term1 = s * s_ln.powu(2) * (s+1.0).powf(nu);
+        let term2 = (vp*s-1.0) * s_ln;
+        let term3 = (1.0-vp) * (s-1.0);
 
-            let denom1 = s * s_ln - s + 1.0;
-            let denom2 = s * s_ln.powu(2) * (s+1.0).ln() * (s+1.0).powf(nu_max);
-            let denom = (denom1.norm().powi(2) + denom2.norm().powi(2)).sqrt();
+        let num = (term1 + term2 + term3).norm();
 
-            num / denom
-    });
+        let denom1 = s * s_ln - s + 1.0;
+        let denom2 = s * s_ln.powu(2) * (s+1.0).ln() * (s+1.0).powf(nu_max);
+        let denom = (denom1.norm().powi(2) + denom2.norm().powi(2)).sqrt();
 
-    Box::new(fraction_iter)
+        *out = num / denom;
+    }
 }
 
 
@@ -97,8 +108,16 @@ pub const SYSTEM: System = System {
     name: "dopid2",
     f_complex,
     parameters: (r"v_p", r"\nu"),
+    singular_origin: true,
     region_fraction_precalculated_numerator: Option::Some(region_fraction_precalculated_numerator),
     region_fraction: Option::Some(region_fraction),
+    frequency_kernels: Option::Some(FrequencyKernels { count: 3, evaluate: frequency_kernels }),
     line_denominator: Option::None,
+    line_fraction_precalculated_numerator: Option::None,
     region_denominator: Option::None,
+    region_fraction_lipschitz: Option::None,
+    line_fraction_lipschitz: Option::None,
+    region_fraction_tails: Option::None,
+    line_fraction_tails: Option::None,
+    region_gradient: Option::None,
 };
\ No newline at end of file
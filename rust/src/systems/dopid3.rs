@@ -1,4 +1,5 @@
-use crate::types::{Comp, Par, System};
+use crate::types::{Comp, FrequencyKernels, Par, System};
+use crate::utils::batch::KernelTable;
 
 const NU: u32 = 2;
 const VI: u32 = 1;
@@ -32,61 +33,69 @@ fn f_complex(s: Comp, p: Par) -> Comp {
 // }
 
 
-pub fn region_fraction_precalculated_numerator<'a>(
-    numerator: &'a [f64],
-    w_logspace: &'a [f64],
+/* Norm of the first denominator term, and the real and imaginary part of the logarithm of s */
+fn frequency_kernels(w: f64, kernels: &mut [f64]) {
+    let s = Comp::new(0.0, w);
+    let s_ln = s.ln();
+
+    kernels[0] = (s * s_ln - s + 1.0).norm();
+    kernels[1] = s_ln.re;
+    kernels[2] = s_ln.im;
+}
+
+
+pub fn region_fraction_precalculated_numerator(
+    numerator: &[f64],
+    w_logspace: &[f64],
+    kernel_table: &KernelTable,
     origin: Par,
-    _eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    _eps: f64,
+    out: &mut [f64])
 {
     let vp = origin.0;
     let _tau = origin.1;
 
-    let fraction_iter = numerator
-        .iter()
-        .zip(w_logspace.iter()).map(move |(num, w)| {
-            let s = Comp::new(0.0, *w);
-            let s_ln = s.ln();
-
-            let denom1 = (s * s_ln - s + 1.0).norm();
-            let denom2 = w * ((vp*s - (VI as f64)) * s_ln + (((VI as f64)-vp)) * (s-1.0)).norm();
-            let denom = (denom1.powi(2) + denom2.powi(2)).sqrt();
+    assert_eq!(kernel_table.rows().len(), w_logspace.len());
+    let fractions = out.iter_mut().zip(numerator).zip(w_logspace).zip(kernel_table.rows());
+    for (((out, num), w), kernels) in fractions {
+        let s = Comp::new(0.0, *w);
+        let s_ln = Comp::new(kernels[1], kernels[2]);
 
-            num / denom
-    });
+        let denom1 = kernels[0];
+        let denom2 = w * ((vp*s - (VI as f64)) * s_ln + (((VI as f64)-vp)) * (s-1.0)).norm();
+        let denom = (denom1.powi(2) + denom2.powi(2)).sqrt();
 
-    Box::new(fraction_iter)
+        *out = num / denom;
+    }
 }
 
 
-pub fn region_fraction<'a>(
-    w_linspace: &'a [f64],
+pub fn region_fraction(
+    w_linspace: &[f64],
     origin: Par,
-    _eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    _eps: f64,
+    out: &mut [f64])
 {
     let vp = origin.0;
     let tau = origin.1;
 
-    let fraction_iter = w_linspace
-        .iter()
-        .map(move |w| {
-            let s = Comp::new(0.0, *w);
-            let s_ln = s.ln();
-            
-            let term1 = s * s_ln.powu(2) * (s+1.0).powu(VI);
-            let term2_1 = (vp*s-(VI as f64)) * s_ln;
-            let term2_2 = ((VI as f64)-vp) * (s-1.0);
-            let term2 = (-s*tau).exp() * (term2_1 + term2_2);
-            
-            let num = (term1 + term2).norm();
-
-            let denom1 = (s * s_ln - s + 1.0).norm();
-            let denom2 = w * ((vp*s - (VI as f64)) * s_ln + (((VI as f64)-vp)) * (s-1.0)).norm();
-            let denom = (denom1.powi(2) + denom2.powi(2)).sqrt();
-
-            num / denom
-    });
-
-    Box::new(fraction_iter)
+    for (out, w) in out.iter_mut().zip(w_linspace) {
+        let s = Comp::new(0.0, *w);
+        let s_ln = s.ln();
+        
+        let term1 = s * s_ln.powu(2) * (s+1.0).powu(VI);
+        let term2_1 = (vp*s-(VI as f64)) * s_ln;
+        let term2_2 = ((VI as f64)-vp) * (s-1.0);
+        let term2 = (-s*tau).exp() * (term2_1 + term2_2);
+        
+        let num = (term1 + term2).norm();
+
+        let denom1 = (s * s_ln - s + 1.0).norm();
+        let denom2 = w * ((vp*s - (VI as f64)) * s_ln + (((VI as f64)-vp)) * (s-1.0)).norm();
+        let denom = (denom1.powi(2) + denom2.powi(2)).sqrt();
+
+        *out = num / denom;
+    }
 }
 
 
@@ -94,8 +103,16 @@ pub const SYSTEM: System = System {
     name: "dopid3",
     f_complex,
     parameters: (r"v_p", r"\tau"),
+    singular_origin: true,
     region_fraction_precalculated_numerator: Option::Some(region_fraction_precalculated_numerator),
     region_fraction: Option::Some(region_fraction),
+    frequency_kernels: Option::Some(FrequencyKernels { count: 3, evaluate: frequency_kernels }),
     line_denominator: Option::None,
+    line_fraction_precalculated_numerator: Option::None,
     region_denominator: Option::None,
+    region_fraction_lipschitz: Option::None,
+    line_fraction_lipschitz: Option::None,
+    region_fraction_tails: Option::None,
+    line_fraction_tails: Option::None,
+    region_gradient: Option::None,
 };
\ No newline at end of file
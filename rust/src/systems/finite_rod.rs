@@ -1,4 +1,5 @@
-use crate::types::{Comp, Par, System};
+use crate::types::{Comp, FrequencyKernels, Par, System};
+use crate::utils::batch::KernelTable;
 
 
 const LAMBDA: f64 = 237.0;
@@ -41,35 +42,65 @@ fn line_denominator(w: f64, p: Par, angle: f64, th_min: f64, th_max: f64) -> f64
 }
 
 
-pub fn region_fraction_precalculated_numerator<'a>(
-    numerator: &'a [f64],
-    w_logspace: &'a [f64],
+pub fn line_fraction_precalculated_numerator(
+    numerator: &[f64],
+    w_logspace: &[f64],
     origin: Par,
-    eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    angle: f64,
+    th_min: f64,
+    th_max: f64,
+    out: &mut [f64])
 {
+    let (c1, c2) = (f64::cos(angle), f64::sin(angle));
     let k = origin.1;
-    let k_max = k + eps;
-    let k_max_powi = k_max.powi(2);
+    let k_max = f64::max(k + th_min * c1, k + th_max * c1);
+    let c1_k_max = c1 * k_max;
+    let c2_powi = c2.powi(2);
+
+    for ((out, num), w) in out.iter_mut().zip(numerator).zip(w_logspace) {
+        let t1 = (LMX * (Comp::new(0.0, *w) / SIGMA).sqrt()).sinh().norm();
+        let t2 = (c2_powi + (c1_k_max * w).powi(2)).sqrt();
+        *out = num / (t1 * t2);
+    }
+}
+
+
+/* Norm of the hyperbolic sine factor of the denominator */
+fn frequency_kernels(w: f64, kernels: &mut [f64]) {
     let lmxoss: f64 = LMX / SIGMA.sqrt();
     let coeff1: Comp = lmxoss * Comp::new(1.0, 1.0) / f64::sqrt(2.0);
+    kernels[0] = (coeff1 * w.sqrt()).sinh().norm();
+}
 
-    let fraction_iter = numerator
-        .iter()
-        .zip(w_logspace.iter()).map(move |(num, w)| {
-            let t1 = (coeff1 * w.sqrt()).sinh().norm();
-            let t2 = (k_max_powi * w.powi(2) + 1.0).sqrt();
-            let denom = t1 * t2;
-            num / denom
-    });
 
-    Box::new(fraction_iter)
+pub fn region_fraction_precalculated_numerator(
+    numerator: &[f64],
+    w_logspace: &[f64],
+    kernel_table: &KernelTable,
+    origin: Par,
+    eps: f64,
+    out: &mut [f64])
+{
+    let k = origin.1;
+    let k_max = k + eps;
+    let k_max_powi = k_max.powi(2);
+
+    assert_eq!(kernel_table.rows().len(), w_logspace.len());
+    let fractions = out.iter_mut().zip(numerator).zip(w_logspace).zip(kernel_table.rows());
+    for (((out, num), w), kernels) in fractions {
+        let t1 = kernels[0];
+        let t2 = (k_max_powi * w.powi(2) + 1.0).sqrt();
+        let denom = t1 * t2;
+        *out = num / denom;
+    }
 }
 
 
-pub fn region_fraction<'a>(
-    w_linspace: &'a [f64],
+pub fn region_fraction(
+    w_linspace: &[f64],
     origin: Par,
-    eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    eps: f64,
+    out: &mut [f64])
 {
     let lmxoss: f64 = LMX / SIGMA.sqrt();
     let coeff1: Comp = lmxoss * Comp::new(1.0, 1.0) / f64::sqrt(2.0);
@@ -80,22 +111,18 @@ pub fn region_fraction<'a>(
     let k_max_powi = k_max.powi(2);
     let z = Comp::new(1.0, 1.0) / (2.0 * SIGMA).sqrt();
 
-    let fraction_iter = w_linspace
-        .iter()
-        .map(move |w| {
-            let w_sqrt = w.sqrt();
-            let z_w_sqrt = w_sqrt * z;
-            let term1 = LAMBDA * z_w_sqrt * (L * z_w_sqrt).cosh();
-            let term2 = k * Comp::exp(-Comp::new(0.0, *w) * tau) * (LMX * z_w_sqrt).sinh();
-            let num = (term1 + term2).norm();
-
-            let t1 = (coeff1 * w_sqrt).sinh().norm();
-            let t2 = (k_max_powi * w.powi(2) + 1.0).sqrt();
-            let denom = t1 * t2;
-            num / denom
-    });
-
-    Box::new(fraction_iter)
+    for (out, w) in out.iter_mut().zip(w_linspace) {
+        let w_sqrt = w.sqrt();
+        let z_w_sqrt = w_sqrt * z;
+        let term1 = LAMBDA * z_w_sqrt * (L * z_w_sqrt).cosh();
+        let term2 = k * Comp::exp(-Comp::new(0.0, *w) * tau) * (LMX * z_w_sqrt).sinh();
+        let num = (term1 + term2).norm();
+
+        let t1 = (coeff1 * w_sqrt).sinh().norm();
+        let t2 = (k_max_powi * w.powi(2) + 1.0).sqrt();
+        let denom = t1 * t2;
+        *out = num / denom;
+    }
 }
 
 
@@ -103,8 +130,16 @@ pub const SYSTEM: System = System {
     name: "finite_rod",
     f_complex,
     parameters: (r"\tau", r"k"),
+    singular_origin: true,
     line_denominator: Option::Some(line_denominator),
+    line_fraction_precalculated_numerator: Option::Some(line_fraction_precalculated_numerator),
     region_denominator: Option::None,
     region_fraction_precalculated_numerator: Option::Some(region_fraction_precalculated_numerator),
     region_fraction: Option::Some(region_fraction),
+    frequency_kernels: Option::Some(FrequencyKernels { count: 1, evaluate: frequency_kernels }),
+    region_fraction_lipschitz: Option::None,
+    line_fraction_lipschitz: Option::None,
+    region_fraction_tails: Option::None,
+    line_fraction_tails: Option::None,
+    region_gradient: Option::None,
 };
\ No newline at end of file
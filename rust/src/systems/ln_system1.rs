@@ -1,4 +1,5 @@
-use crate::types::{Comp, Par, System};
+use crate::types::{Comp, FrequencyKernels, Par, System};
+use crate::utils::batch::KernelTable;
 
 
 const TAU: f64 = 2.0;
@@ -32,58 +33,83 @@ fn line_denominator(w: f64, _p: Par, angle: f64, _th_min: f64, _th_max: f64) ->
 }
 
 
-pub fn region_fraction_precalculated_numerator<'a>(
-    numerator: &'a [f64],
-    w_logspace: &'a [f64],
+pub fn line_fraction_precalculated_numerator(
+    numerator: &[f64],
+    w_logspace: &[f64],
     _origin: Par,
-    _eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    angle: f64,
+    _th_min: f64,
+    _th_max: f64,
+    out: &mut [f64])
 {
-    let fraction_iter = numerator
-        .iter()
-        .zip(w_logspace.iter()).map(move |(num, w)| {
-            let s = Comp::new(0.0, *w);
-            let s_ln = s.ln();
-            let s_minus_1 = s - 1.0;
+    let (c1, c2) = (f64::cos(angle), f64::sin(angle));
 
-            let term1 = (-s * s_ln + s_minus_1).norm();
-            let term2 = (s_ln - s_minus_1).norm();
-            let denom = (term1.powi(2) + term2.powi(2)).sqrt();
+    for ((out, num), w) in out.iter_mut().zip(numerator).zip(w_logspace) {
+        let s = Comp::new(0.0, *w);
+        let s_ln = s.ln();
 
-            num / denom
-    });
+        let term1 = -s * s_ln * c1;
+        let term2 = s * (c1 - c2);
+        let term3 = c2 * s_ln;
+        let term4 = c2 - c1;
 
-    Box::new(fraction_iter)
+        *out = num / (term1 + term2 + term3 + term4).norm();
+    }
 }
 
 
-pub fn region_fraction<'a>(
-    w_linspace: &'a [f64],
-    origin: Par,
-    _eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+/* The denominator does not depend on parameters at all */
+fn frequency_kernels(w: f64, kernels: &mut [f64]) {
+    let s = Comp::new(0.0, w);
+    let s_ln = s.ln();
+    let s_minus_1 = s - 1.0;
+
+    let term1 = (-s * s_ln + s_minus_1).norm();
+    let term2 = (s_ln - s_minus_1).norm();
+    kernels[0] = (term1.powi(2) + term2.powi(2)).sqrt();
+}
+
+
+pub fn region_fraction_precalculated_numerator(
+    numerator: &[f64],
+    w_logspace: &[f64],
+    kernel_table: &KernelTable,
+    _origin: Par,
+    _eps: f64,
+    out: &mut [f64])
 {
-    let fraction_iter = w_linspace
-        .iter()
-        .map(move |w| {
-            let s = Comp::new(0.0, *w);
+    assert_eq!(kernel_table.rows().len(), w_logspace.len());
+    for ((out, num), kernels) in out.iter_mut().zip(numerator).zip(kernel_table.rows()) {
+        *out = num / kernels[0];
+    }
+}
 
-            let kp = origin.0;
-            let ki = origin.1;
-            let s_ln = s.ln();
-            let kp_s = kp * s;
 
-            let term1 = s_ln.powi(2) * s * (s+1.0);
-            let bracket = s_ln * (-kp_s + ki) + (-ki*s + kp_s + ki - kp);
-            let term2 = (-s*TAU).exp() * bracket;
-            let num = (term1 - term2).norm();
+pub fn region_fraction(
+    w_linspace: &[f64],
+    origin: Par,
+    _eps: f64,
+    out: &mut [f64])
+{
+    for (out, w) in out.iter_mut().zip(w_linspace) {
+        let s = Comp::new(0.0, *w);
+
+        let kp = origin.0;
+        let ki = origin.1;
+        let s_ln = s.ln();
+        let kp_s = kp * s;
 
-            let term1 = (-s * s_ln + s - 1.0).norm();
-            let term2 = (s_ln - s + 1.0).norm();
-            let denom = (term1.powi(2) + term2.powi(2)).sqrt();
+        let term1 = s_ln.powi(2) * s * (s+1.0);
+        let bracket = s_ln * (-kp_s + ki) + (-ki*s + kp_s + ki - kp);
+        let term2 = (-s*TAU).exp() * bracket;
+        let num = (term1 - term2).norm();
 
-            num / denom
-    });
+        let term1 = (-s * s_ln + s - 1.0).norm();
+        let term2 = (s_ln - s + 1.0).norm();
+        let denom = (term1.powi(2) + term2.powi(2)).sqrt();
 
-    Box::new(fraction_iter)
+        *out = num / denom;
+    }
 }
 
 
@@ -91,8 +117,16 @@ pub const SYSTEM: System = System {
     name: "ln_system1",
     f_complex,
     parameters: (r"k_p", r"k_i"),
+    singular_origin: true,
     region_fraction_precalculated_numerator: Option::Some(region_fraction_precalculated_numerator),
     region_fraction: Option::Some(region_fraction),
+    frequency_kernels: Option::Some(FrequencyKernels { count: 1, evaluate: frequency_kernels }),
     line_denominator: Option::Some(line_denominator),
+    line_fraction_precalculated_numerator: Option::Some(line_fraction_precalculated_numerator),
     region_denominator: Option::None,
+    region_fraction_lipschitz: Option::None,
+    line_fraction_lipschitz: Option::None,
+    region_fraction_tails: Option::None,
+    line_fraction_tails: Option::None,
+    region_gradient: Option::None,
 };
\ No newline at end of file
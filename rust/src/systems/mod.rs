@@ -13,4 +13,78 @@ pub mod ln_system1;
 pub mod dopid1;
 pub mod dopid2;
 pub mod dopid3;
-pub mod test_system;
\ No newline at end of file
+pub mod test_system;
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use crate::types::{Comp, Par, System};
+    use super::*;
+
+    /* Batch line fractions must agree with the numerator divided by the scalar line denominator */
+    fn assert_line_fractions_match(system: System, p: Par) {
+        let line_denominator = system.line_denominator.unwrap();
+        let line_fraction = system.line_fraction_precalculated_numerator.unwrap();
+        let w: Vec<f64> = iter_num_tools::log_space(1e-3..=1e5, 200).collect();
+        let numerator: Vec<f64> = w.iter().map(|w| (system.f_complex)(Comp::new(0.0, *w), p).norm()).collect();
+
+        for angle in [0.3, PI / 2.0, 3.7] {
+            for (th_min, th_max) in [(0.0, 1e-3), (0.1, 0.3), (0.5, 2.0)] {
+                let mut batch = vec![0.0; w.len()];
+                line_fraction(&numerator, &w, p, angle, th_min, th_max, &mut batch);
+
+                for ((w, num), batch) in w.iter().zip(&numerator).zip(&batch) {
+                    let single = num / line_denominator(*w, p, angle, th_min, th_max);
+                    /* Both overflow the same way at high frequencies of some systems */
+                    let both_nan = single.is_nan() && batch.is_nan();
+                    let matches = both_nan || single == *batch || (single - batch).abs() <= 1e-9 * single.abs();
+                    assert!(
+                        matches,
+                        "{}: {} != {} at w={}, angle={}, th=({}, {})",
+                        system.name, batch, single, w, angle, th_min, th_max);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn line_fraction_retarded1() {
+        assert_line_fractions_match(retarded1::SYSTEM, (1e-2, 1e-2));
+    }
+
+    #[test]
+    fn line_fraction_distributed_delay1() {
+        assert_line_fractions_match(distributed_delay1::SYSTEM, (1.0, 0.4));
+    }
+
+    #[test]
+    fn line_fraction_semi_infinite_rod() {
+        assert_line_fractions_match(semi_infinite_rod::SYSTEM, (1.0, 0.5e4));
+    }
+
+    #[test]
+    fn line_fraction_finite_rod() {
+        assert_line_fractions_match(finite_rod::SYSTEM, (17.0, 22_000.0));
+    }
+
+    #[test]
+    fn line_fraction_pde_complex_k_sigma() {
+        assert_line_fractions_match(pde_complex_k_sigma::SYSTEM, (0.5, 0.5));
+    }
+
+    #[test]
+    fn line_fraction_pde_complex_tau_sigma() {
+        assert_line_fractions_match(pde_complex_tau_sigma::SYSTEM, (1.0, 1.0));
+    }
+
+    #[test]
+    fn line_fraction_telegrapher_standard() {
+        assert_line_fractions_match(telegrapher_standard::SYSTEM, (0.05, 1.3));
+    }
+
+    #[test]
+    fn line_fraction_ln_system1() {
+        assert_line_fractions_match(ln_system1::SYSTEM, (0.9, 0.9));
+    }
+}
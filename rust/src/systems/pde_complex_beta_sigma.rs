@@ -1,4 +1,5 @@
-use crate::types::{Comp, Par, System};
+use crate::types::{Comp, FrequencyKernels, Par, System};
+use crate::utils::batch::KernelTable;
 
 const K: f64 = 3.0;
 const L: u32 = 3;
@@ -60,11 +61,19 @@ fn line_denominator(w: f64, p: Par, angle: f64, th_min: f64, th_max: f64) -> f64
 }
 
 
-pub fn region_fraction_precalculated_numerator<'a>(
-    numerator: &'a [f64],
-    w_logspace: &'a [f64],
+/* Norm of the logarithm prefix of the beta gradient */
+fn frequency_kernels(w: f64, kernels: &mut [f64]) {
+    kernels[0] = (Comp::new(0.0, w)).ln().norm();
+}
+
+
+pub fn region_fraction_precalculated_numerator(
+    numerator: &[f64],
+    w_logspace: &[f64],
+    kernel_table: &KernelTable,
     origin: Par,
-    eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    eps: f64,
+    out: &mut [f64])
 {
     let safeguard = 1e-30;
     let (beta, sigma) = (origin.0, origin.1);
@@ -77,49 +86,48 @@ pub fn region_fraction_precalculated_numerator<'a>(
     let a = [1.0, 3.0, 3.0, 1.0]; // Assuming L = 3
     let coeff1 = -f64::sqrt(2.0 * sigma_min)/2.0;
 
-    let fraction_iter = numerator
-        .iter()
-        .zip(w_logspace.iter()).map(move |(num, w)| {
-            let beta_worst_max = if *w <= 1.0 {beta_min} else {beta_max};
-            let beta_worst_min = if *w <= 1.0 {beta_max} else {beta_min};
-
-            let ln_pref = (Comp::new(0.0, *w)).ln().norm();
-            let exp_part = (coeff1 * f64::sqrt(*w)).exp();
-
-            // Check below
-            let mut sum1 = 0.0;
-            let mut sum2 = 0.0;
-
-            for i in 0..(L+1) {
-                let exponent = ((i*L) as f64 + beta_worst_max * (L-i) as f64) / L as f64;
-                sum1 += a[i as usize] as f64 * (1.0-i as f64/L as f64) * w.powf(exponent);
-            }
-
-            for i in 0..(L+1) {
-                let exponent = i as f64 * (L as f64-beta_worst_min) / L as f64;
-                sum2 += a[i as usize] as f64 * (i as f64/L as f64) * w.powf(exponent);
-            }
-            let gradient_beta = ln_pref * (sum1 + K * exp_part * sum2);
-
-            let mut sum_sigma = 0.0;
-            for i in 0..(L+1) {
-                let exponent = (L as f64 -beta_worst_min) * (i as f64 / L as f64);
-                sum_sigma += a[i as usize] as f64 * w.powf(exponent);
-            }
-
-            let gradient_sigma = K/2.0 * exp_part * f64::sqrt(w/sigma_min) * sum_sigma;
-            let result = num / (f64::sqrt(gradient_beta.powi(2) + gradient_sigma.powi(2)));
-            result
-    });
-
-    Box::new(fraction_iter)
+    assert_eq!(kernel_table.rows().len(), w_logspace.len());
+    let fractions = out.iter_mut().zip(numerator).zip(w_logspace).zip(kernel_table.rows());
+    for (((out, num), w), kernels) in fractions {
+        let beta_worst_max = if *w <= 1.0 {beta_min} else {beta_max};
+        let beta_worst_min = if *w <= 1.0 {beta_max} else {beta_min};
+
+        let ln_pref = kernels[0];
+        let exp_part = (coeff1 * f64::sqrt(*w)).exp();
+
+        // Check below
+        let mut sum1 = 0.0;
+        let mut sum2 = 0.0;
+
+        for i in 0..(L+1) {
+            let exponent = ((i*L) as f64 + beta_worst_max * (L-i) as f64) / L as f64;
+            sum1 += a[i as usize] as f64 * (1.0-i as f64/L as f64) * w.powf(exponent);
+        }
+
+        for i in 0..(L+1) {
+            let exponent = i as f64 * (L as f64-beta_worst_min) / L as f64;
+            sum2 += a[i as usize] as f64 * (i as f64/L as f64) * w.powf(exponent);
+        }
+        let gradient_beta = ln_pref * (sum1 + K * exp_part * sum2);
+
+        let mut sum_sigma = 0.0;
+        for i in 0..(L+1) {
+            let exponent = (L as f64 -beta_worst_min) * (i as f64 / L as f64);
+            sum_sigma += a[i as usize] as f64 * w.powf(exponent);
+        }
+
+        let gradient_sigma = K/2.0 * exp_part * f64::sqrt(w/sigma_min) * sum_sigma;
+        let result = num / (f64::sqrt(gradient_beta.powi(2) + gradient_sigma.powi(2)));
+        *out = result;
+    }
 }
 
 
-pub fn region_fraction<'a>(
-    w_linspace: &'a [f64],
+pub fn region_fraction(
+    w_linspace: &[f64],
     origin: Par,
-    eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    eps: f64,
+    out: &mut [f64])
 {
     let safeguard = 1e-30;
     let (beta, sigma) = (origin.0, origin.1);
@@ -131,44 +139,40 @@ pub fn region_fraction<'a>(
     // Helper variables
     let a = [1.0, 3.0, 3.0, 1.0]; // Assuming L = 3
     let coeff1 = -f64::sqrt(2.0 * sigma_min)/2.0;
-    let fraction_iter = w_linspace
-        .iter()
-        .map(move |w| {
-            let num = f_complex(Comp::new(0.0, *w), origin).norm();
-            let beta_worst_max = if *w <= 1.0 {beta_min} else {beta_max};
-            let beta_worst_min = if *w <= 1.0 {beta_max} else {beta_min};
-
-            let ln_pref = (Comp::new(0.0, *w)).ln().norm();
-            let exp_part = (coeff1 * f64::sqrt(*w)).exp();
-
-            // Check below
-            let mut sum1 = 0.0;
-            let mut sum2 = 0.0;
-
-            for i in 0..(L+1) {
-                let exponent = ((i*L) as f64 + beta_worst_max * (L-i) as f64) / L as f64;
-                sum1 += a[i as usize] as f64 * (1.0-i as f64/L as f64) * w.powf(exponent);
-            }
-
-            for i in 0..(L+1) {
-                let exponent = i as f64 * (L as f64-beta_worst_min) / L as f64;
-                sum2 += a[i as usize] as f64 * (i as f64/L as f64) * w.powf(exponent);
-            }
-            let gradient_beta = ln_pref * (sum1 + K * exp_part * sum2);
-
-            let mut sum_sigma = 0.0;
-            for i in 0..(L+1) {
-                let exponent = (L as f64 -beta_worst_min) * (i as f64 / L as f64);
-                sum_sigma += a[i as usize] as f64 * w.powf(exponent);
-            }
-
-            let gradient_sigma = K/2.0 * exp_part * f64::sqrt(w/sigma_min) * sum_sigma;
-
-            let result = num / (f64::sqrt(gradient_beta.powi(2) + gradient_sigma.powi(2)));
-            result
-    });
-
-    Box::new(fraction_iter)
+    for (out, w) in out.iter_mut().zip(w_linspace) {
+        let num = f_complex(Comp::new(0.0, *w), origin).norm();
+        let beta_worst_max = if *w <= 1.0 {beta_min} else {beta_max};
+        let beta_worst_min = if *w <= 1.0 {beta_max} else {beta_min};
+
+        let ln_pref = (Comp::new(0.0, *w)).ln().norm();
+        let exp_part = (coeff1 * f64::sqrt(*w)).exp();
+
+        // Check below
+        let mut sum1 = 0.0;
+        let mut sum2 = 0.0;
+
+        for i in 0..(L+1) {
+            let exponent = ((i*L) as f64 + beta_worst_max * (L-i) as f64) / L as f64;
+            sum1 += a[i as usize] as f64 * (1.0-i as f64/L as f64) * w.powf(exponent);
+        }
+
+        for i in 0..(L+1) {
+            let exponent = i as f64 * (L as f64-beta_worst_min) / L as f64;
+            sum2 += a[i as usize] as f64 * (i as f64/L as f64) * w.powf(exponent);
+        }
+        let gradient_beta = ln_pref * (sum1 + K * exp_part * sum2);
+
+        let mut sum_sigma = 0.0;
+        for i in 0..(L+1) {
+            let exponent = (L as f64 -beta_worst_min) * (i as f64 / L as f64);
+            sum_sigma += a[i as usize] as f64 * w.powf(exponent);
+        }
+
+        let gradient_sigma = K/2.0 * exp_part * f64::sqrt(w/sigma_min) * sum_sigma;
+
+        let result = num / (f64::sqrt(gradient_beta.powi(2) + gradient_sigma.powi(2)));
+        *out = result;
+    }
 }
 
 
@@ -176,10 +180,18 @@ pub const SYSTEM: System = System {
     name: "pde_complex_beta_sigma",
     f_complex,
     parameters: (r"\beta", r"\sigma"),
+    singular_origin: true,
     line_denominator: Option::Some(line_denominator),
+    line_fraction_precalculated_numerator: Option::None,
     region_denominator: Option::None,
     region_fraction_precalculated_numerator: Option::Some(region_fraction_precalculated_numerator),
     region_fraction: Option::Some(region_fraction),
+    frequency_kernels: Option::Some(FrequencyKernels { count: 1, evaluate: frequency_kernels }),
+    region_fraction_lipschitz: Option::None,
+    line_fraction_lipschitz: Option::None,
+    region_fraction_tails: Option::None,
+    line_fraction_tails: Option::None,
+    region_gradient: Option::None,
 };
 
 
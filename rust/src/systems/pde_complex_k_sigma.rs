@@ -1,4 +1,5 @@
 use crate::types::{Comp, Par, System};
+use crate::utils::batch::KernelTable;
 
 
 fn f_complex(s: Comp, p: Par) -> Comp {
@@ -31,6 +32,39 @@ fn line_denominator(w: f64, p: Par, angle: f64, th_min: f64, th_max: f64) -> f64
 }
 
 
+pub fn line_fraction_precalculated_numerator(
+    numerator: &[f64],
+    w_logspace: &[f64],
+    origin: Par,
+    angle: f64,
+    th_min: f64,
+    th_max: f64,
+    out: &mut [f64])
+{
+    let c1 = f64::cos(angle);
+    let c2 = f64::sin(angle);
+
+    let k = origin.0;
+    let sigma = origin.1;
+
+    let k_abmx = f64::max(f64::abs(k+th_min*c1), f64::abs(k+th_max*c1));
+    let sigma_abmn = f64::min((sigma+th_min*c2).abs(), (sigma+th_max*c2).abs());
+    let c2_k_abmx = c2.abs() * k_abmx;
+
+    for ((out, num), w) in out.iter_mut().zip(numerator).zip(w_logspace) {
+        let e1max = (-f64::sqrt(2.0*w)/2.0).exp();
+        let root = if sigma_abmn == 0.0 {
+            f64::INFINITY
+        }
+        else {
+            f64::sqrt(w/sigma_abmn)
+        };
+        let x1 = c1.abs() + c2_k_abmx * root;
+        *out = num / (e1max * x1);
+    }
+}
+
+
 fn region_denominator(w: f64, origin: Par, eps: f64) -> f64 {
     let k = origin.0;
     let sigma = origin.1;
@@ -44,11 +78,13 @@ fn region_denominator(w: f64, origin: Par, eps: f64) -> f64 {
 }
 
 
-pub fn region_fraction_precalculated_numerator<'a>(
-    numerator: &'a [f64],
-    w_logspace: &'a [f64],
+pub fn region_fraction_precalculated_numerator(
+    numerator: &[f64],
+    w_logspace: &[f64],
+    _kernels: &KernelTable,
     origin: Par,
-    eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    eps: f64,
+    out: &mut [f64])
 {
     let safeguard = 1e-20;
     let k = origin.0;
@@ -59,23 +95,20 @@ pub fn region_fraction_precalculated_numerator<'a>(
     let prefix_g_sigma = 0.5 * kmax / sigma_min.sqrt();
     let coeff2 = -(sigma_min/2.0).sqrt();
 
-    let fraction_iter = numerator
-        .iter()
-        .zip(w_logspace.iter()).map(move |(num, w)| {
-            let w_sqrt = w.sqrt();
-            let gradient_k = (w_sqrt * coeff1).exp();
-            let gradient_sigma = prefix_g_sigma * w_sqrt * (coeff2 * w_sqrt).exp();
-            num / (f64::sqrt(gradient_k.powi(2) + gradient_sigma.powi(2)))
-    });
-
-    Box::new(fraction_iter)
+    for ((out, num), w) in out.iter_mut().zip(numerator).zip(w_logspace) {
+        let w_sqrt = w.sqrt();
+        let gradient_k = (w_sqrt * coeff1).exp();
+        let gradient_sigma = prefix_g_sigma * w_sqrt * (coeff2 * w_sqrt).exp();
+        *out = num / (f64::sqrt(gradient_k.powi(2) + gradient_sigma.powi(2)));
+    }
 }
 
 
-pub fn region_fraction<'a>(
-    w_linspace: &'a [f64],
+pub fn region_fraction(
+    w_linspace: &[f64],
     origin: Par,
-    eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    eps: f64,
+    out: &mut [f64])
 {
     let safeguard = 1e-20;
     let k = origin.0;
@@ -86,18 +119,14 @@ pub fn region_fraction<'a>(
     let prefix_g_sigma = 0.5 * kmax / sigma_min.sqrt();
     let coeff2 = -(sigma_min/2.0).sqrt();
 
-    let fraction_iter = w_linspace
-        .iter()
-        .map(move |w| {
-            let s = Comp::new(0.0, *w);
-            let num = f_complex(s, origin).norm();
-            let w_sqrt = w.sqrt();
-            let gradient_k = (w_sqrt * coeff1).exp();
-            let gradient_sigma = prefix_g_sigma * w_sqrt * (coeff2 * w_sqrt).exp();
-            num / (f64::sqrt(gradient_k.powi(2) + gradient_sigma.powi(2)))
-    });
-
-    Box::new(fraction_iter)
+    for (out, w) in out.iter_mut().zip(w_linspace) {
+        let s = Comp::new(0.0, *w);
+        let num = f_complex(s, origin).norm();
+        let w_sqrt = w.sqrt();
+        let gradient_k = (w_sqrt * coeff1).exp();
+        let gradient_sigma = prefix_g_sigma * w_sqrt * (coeff2 * w_sqrt).exp();
+        *out = num / (f64::sqrt(gradient_k.powi(2) + gradient_sigma.powi(2)));
+    }
 }
 
 
@@ -105,8 +134,16 @@ pub const SYSTEM: System = System {
     name: "pde_complex_k_sigma",
     f_complex,
     parameters: (r"k", r"\sigma"),
+    singular_origin: true,
     line_denominator: Option::Some(line_denominator),
+    line_fraction_precalculated_numerator: Option::Some(line_fraction_precalculated_numerator),
     region_denominator: Option::Some(region_denominator),
     region_fraction_precalculated_numerator: Option::Some(region_fraction_precalculated_numerator),
     region_fraction: Option::Some(region_fraction),
+    frequency_kernels: Option::None,
+    region_fraction_lipschitz: Option::None,
+    line_fraction_lipschitz: Option::None,
+    region_fraction_tails: Option::None,
+    line_fraction_tails: Option::None,
+    region_gradient: Option::None,
 };
\ No newline at end of file
@@ -1,4 +1,5 @@
 use crate::types::{Comp, Par, System};
+use crate::utils::batch::KernelTable;
 
 const K: f64 = 1.0;
 
@@ -23,33 +24,56 @@ fn line_denominator(w: f64, p: Par, angle: f64, th_min: f64, th_max: f64) -> f64
 }
 
 
-pub fn region_fraction_precalculated_numerator<'a>(
-    numerator: &'a [f64],
-    w_logspace: &'a [f64],
+pub fn line_fraction_precalculated_numerator(
+    numerator: &[f64],
+    w_logspace: &[f64],
     origin: Par,
-    eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    angle: f64,
+    th_min: f64,
+    th_max: f64,
+    out: &mut [f64])
+{
+    let safeguard: f64 = 1e-30;
+    let sigma0 = origin.1;
+    let (c1, c2) = (f64::cos(angle), f64::sin(angle));
+    let (c1_abs, c2_abs) = (c1.abs(), c2.abs());
+
+    let sigma_min = f64::max(f64::min(sigma0 + c2*th_min,  sigma0 + c2*th_max), safeguard);
+
+    for ((out, num), w) in out.iter_mut().zip(numerator).zip(w_logspace) {
+        let y1 = K * (-(w*sigma_min/2.0).sqrt()).exp();
+        let y2 = 0.5*(w/sigma_min).sqrt()*c2_abs + w*c1_abs;
+        *out = num / (y1 * y2);
+    }
+}
+
+
+pub fn region_fraction_precalculated_numerator(
+    numerator: &[f64],
+    w_logspace: &[f64],
+    _kernels: &KernelTable,
+    origin: Par,
+    eps: f64,
+    out: &mut [f64])
 {
     let sigma = origin.1;
     let safeguard = 1e-30;
     let sigma_min = f64::max(sigma - eps, safeguard);
     let coeff1 = -(sigma_min/2.0).sqrt();
     let coeff2 = 1.0 / (4.0 * sigma_min);
-    let fraction_iter = numerator
-        .iter()
-        .zip(w_logspace.iter()).map(move |(num, w)| {
-            let x = (coeff1 * w.sqrt()).exp();
-            let denominator = K * x * (w * (w + coeff2)).sqrt();
-            num / denominator
-    });
-
-    Box::new(fraction_iter)
+    for ((out, num), w) in out.iter_mut().zip(numerator).zip(w_logspace) {
+        let x = (coeff1 * w.sqrt()).exp();
+        let denominator = K * x * (w * (w + coeff2)).sqrt();
+        *out = num / denominator;
+    }
 }
 
 
-pub fn region_fraction<'a>(
-    w_linspace: &'a [f64],
+pub fn region_fraction(
+    w_linspace: &[f64],
     origin: Par,
-    eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    eps: f64,
+    out: &mut [f64])
 {
     let sigma = origin.1;
     let safeguard = 1e-30;
@@ -57,17 +81,13 @@ pub fn region_fraction<'a>(
     let coeff1 = -(sigma_min/2.0).sqrt();
     let coeff2 = 1.0 / (4.0 * sigma_min);
 
-    let fraction_iter = w_linspace
-        .iter()
-        .map(move |w| {
-            let num = f_complex(Comp::new(0.0, *w), origin).norm();
-            let w_sqrt = w.sqrt();
-            let x = (coeff1 * w_sqrt).exp();
-            let denominator = K * x * w_sqrt * (w + coeff2).sqrt();
-            num / denominator
-    });
-
-    Box::new(fraction_iter)
+    for (out, w) in out.iter_mut().zip(w_linspace) {
+        let num = f_complex(Comp::new(0.0, *w), origin).norm();
+        let w_sqrt = w.sqrt();
+        let x = (coeff1 * w_sqrt).exp();
+        let denominator = K * x * w_sqrt * (w + coeff2).sqrt();
+        *out = num / denominator;
+    }
 }
 
 
@@ -75,8 +95,16 @@ pub const SYSTEM: System = System {
     name: "pde_complex_tau_sigma",
     f_complex,
     parameters: (r"\tau", r"\sigma"),
+    singular_origin: true,
     line_denominator: Option::Some(line_denominator),
+    line_fraction_precalculated_numerator: Option::Some(line_fraction_precalculated_numerator),
     region_denominator: Option::None,
     region_fraction_precalculated_numerator: Option::Some(region_fraction_precalculated_numerator),
     region_fraction: Option::Some(region_fraction),
+    frequency_kernels: Option::None,
+    region_fraction_lipschitz: Option::None,
+    line_fraction_lipschitz: Option::None,
+    region_fraction_tails: Option::None,
+    line_fraction_tails: Option::None,
+    region_gradient: Option::None,
 };
\ No newline at end of file
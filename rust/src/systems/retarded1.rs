@@ -1,4 +1,5 @@
 use crate::types::{Comp, Par, System};
+use crate::utils::batch::KernelTable;
 
 
 fn f_complex(s: Comp, p: Par) -> Comp {
@@ -16,39 +17,141 @@ fn line_denominator(w: f64, _p: Par, _angle: f64, _th_min: f64, _th_max: f64) ->
 }
 
 
-pub fn region_fraction_precalculated_numerator<'a>(
-    numerator: &'a [f64],
-    w_logspace: &'a [f64],
+pub fn line_fraction_precalculated_numerator(
+    numerator: &[f64],
+    w_logspace: &[f64],
     _origin: Par,
-    _eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    _angle: f64,
+    _th_min: f64,
+    _th_max: f64,
+    out: &mut [f64])
 {
-    let fraction_iter = numerator
-        .iter()
-        .zip(w_logspace.iter()).map(move |(num, w)| {
-            let gradient_p1 = 2.0 * w.powi(2);
-            let gradient_p2 = w;
-            num / (f64::sqrt(gradient_p1.powi(2) + gradient_p2.powi(2)))
-    });
+    let safeguard = 1e-10;
+
+    for ((out, num), w) in out.iter_mut().zip(numerator).zip(w_logspace) {
+        *out = num / (2.0*w.powi(2) + w + safeguard);
+    }
+}
+
+
+/* Bounds on |f(jw, p)| and |df(jw, p)/dw| valid for every frequency below w_max */
+fn numerator_bounds(w_max: f64, p: Par) -> (f64, f64) {
+    let tau1 = p.0.abs();
+    let tau2 = p.1.abs();
+
+    let norm_bound = (w_max + 1.0).powi(2);
+    let derivative_bound = 2.0*w_max + 2.0 + 2.0*w_max*tau1 + tau2;
 
-    Box::new(fraction_iter)
+    (norm_bound, derivative_bound)
 }
 
 
-pub fn region_fraction<'a>(
-    w_linspace: &'a [f64],
+fn line_fraction_lipschitz(w_min: f64, w_max: f64, p: Par, angle: f64, th_min: f64, th_max: f64) -> f64 {
+    let p0 = (p.0 + th_min*f64::cos(angle), p.1 + th_min*f64::sin(angle));
+    let (norm_bound, derivative_bound) = numerator_bounds(w_max, p0);
+
+    /* The denominator is increasing, as is its derivative */
+    let denom_min = line_denominator(w_min, p, angle, th_min, th_max);
+    let denom_derivative_max = 4.0*w_max + 1.0;
+
+    derivative_bound / denom_min + norm_bound * denom_derivative_max / denom_min.powi(2)
+}
+
+
+fn region_fraction_lipschitz(w_min: f64, w_max: f64, p: Par, _eps: f64) -> f64 {
+    let (norm_bound, derivative_bound) = numerator_bounds(w_max, p);
+
+    /* The denominator is increasing, as is its derivative */
+    let denom_min = w_min * f64::sqrt(4.0*w_min.powi(2) + 1.0);
+    let denom_derivative_max = (8.0*w_max.powi(2) + 1.0) / f64::sqrt(4.0*w_max.powi(2) + 1.0);
+
+    derivative_bound / denom_min + norm_bound * denom_derivative_max / denom_min.powi(2)
+}
+
+
+/* Lower bounds of |f(jw, p)| for all frequencies below w_min and above w_max, respectively */
+fn numerator_tails(w_min: f64, w_max: f64) -> (f64, f64) {
+    let low_tail = f64::max(0.0, 1.0 - w_min.powi(2) - 2.0*w_min);
+    let high_tail = f64::max(0.0, w_max.powi(2) - 2.0*w_max - 1.0);
+
+    (low_tail, high_tail)
+}
+
+
+fn line_fraction_tails(w_min: f64, w_max: f64, p: Par, angle: f64, th_min: f64, th_max: f64) -> (f64, f64) {
+    let (num_low, num_high) = numerator_tails(w_min, w_max);
+
+    /* Both tails are monotone once the high tail numerator is positive */
+    let low_tail = num_low / line_denominator(w_min, p, angle, th_min, th_max);
+    let high_tail = num_high / line_denominator(w_max, p, angle, th_min, th_max);
+
+    (low_tail, high_tail)
+}
+
+
+fn region_fraction_tails(w_min: f64, w_max: f64, _p: Par, _eps: f64) -> (f64, f64) {
+    let (num_low, num_high) = numerator_tails(w_min, w_max);
+    let denominator = |w: f64| w * f64::sqrt(4.0*w.powi(2) + 1.0);
+
+    /* Both tails are monotone once the high tail numerator is positive */
+    let low_tail = num_low / denominator(w_min);
+    let high_tail = num_high / denominator(w_max);
+
+    (low_tail, high_tail)
+}
+
+
+pub fn region_fraction_precalculated_numerator(
+    numerator: &[f64],
+    w_logspace: &[f64],
+    _kernels: &KernelTable,
+    _origin: Par,
+    _eps: f64,
+    out: &mut [f64])
+{
+    for ((out, num), w) in out.iter_mut().zip(numerator).zip(w_logspace) {
+        let gradient_p1 = 2.0 * w.powi(2);
+        let gradient_p2 = w;
+        *out = num / (f64::sqrt(gradient_p1.powi(2) + gradient_p2.powi(2)));
+    }
+}
+
+
+pub fn region_fraction(
+    w_linspace: &[f64],
     origin: Par,
-    _eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    _eps: f64,
+    out: &mut [f64])
 {
-    let fraction_iter = w_linspace
-        .iter()
-        .map(move |w| {
-            let num = f_complex(Comp::new(0.0, *w), origin).norm();
-            let gradient_p1 = 2.0 * w.powi(2);
-            let gradient_p2 = w;
-            num / (f64::sqrt(gradient_p1.powi(2) + gradient_p2.powi(2)))
-    });
+    for (out, w) in out.iter_mut().zip(w_linspace) {
+        let num = f_complex(Comp::new(0.0, *w), origin).norm();
+        let gradient_p1 = 2.0 * w.powi(2);
+        let gradient_p2 = w;
+        *out = num / (f64::sqrt(gradient_p1.powi(2) + gradient_p2.powi(2)));
+    }
+}
+
+
+/* Bounds of |df/dp1| and |df/dp2| at any s (e.g. off the imaginary axis) over |q_i - p_i| <= eps_i */
+fn region_gradient(s: Comp, p: Par, eps: Par) -> (f64, f64) {
+    let sigma = s.re;
+    let exp_max = |tau: f64, eps: f64| f64::exp(-sigma*tau + sigma.abs()*eps);
+
+    let gradient_p1 = 2.0 * s.norm_sqr() * exp_max(p.0, eps.0);
+    let gradient_p2 = s.norm() * exp_max(p.1, eps.1);
+
+    (gradient_p1, gradient_p2)
+}
+
+
+/* Characteristic function divided by s^2, i.e. 1 + a1(s)e^(-s*tau1) + a2(s)e^(-s*tau2) */
+pub fn gu2005_a1(s: Comp) -> Comp {
+    2.0 / s
+}
+
 
-    Box::new(fraction_iter)
+pub fn gu2005_a2(s: Comp) -> Comp {
+    1.0 / s.powi(2)
 }
 
 
@@ -56,8 +159,16 @@ pub const SYSTEM: System = System {
     name: "retarded1",
     f_complex,
     parameters: (r"\tau_1", r"\tau_2"),
+    singular_origin: false,
     region_fraction_precalculated_numerator: Option::Some(region_fraction_precalculated_numerator),
     region_fraction: Option::Some(region_fraction),
+    frequency_kernels: Option::None,
     line_denominator: Option::Some(line_denominator),
+    line_fraction_precalculated_numerator: Option::Some(line_fraction_precalculated_numerator),
     region_denominator: Option::None,
+    region_fraction_lipschitz: Option::Some(region_fraction_lipschitz),
+    line_fraction_lipschitz: Option::Some(line_fraction_lipschitz),
+    region_fraction_tails: Option::Some(region_fraction_tails),
+    line_fraction_tails: Option::Some(line_fraction_tails),
+    region_gradient: Option::Some(region_gradient),
 };
\ No newline at end of file
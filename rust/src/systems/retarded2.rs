@@ -1,4 +1,5 @@
 use crate::types::{Comp, Par, System};
+use crate::utils::batch::KernelTable;
 
 
 fn f_complex(s: Comp, p: Par) -> Comp {
@@ -21,37 +22,55 @@ fn line_denominator(_w: f64, _p: Par, _angle: f64, _th_min: f64, _th_max: f64) -
 }
 
 
-pub fn region_fraction_precalculated_numerator<'a>(
-    numerator: &'a [f64],
-    w_logspace: &'a [f64],
+pub fn region_fraction_precalculated_numerator(
+    numerator: &[f64],
+    w_logspace: &[f64],
+    _kernels: &KernelTable,
     _origin: Par,
-    _eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    _eps: f64,
+    out: &mut [f64])
 {
-    let fraction_iter = numerator
-        .iter()
-        .zip(w_logspace.iter()).map(move |(num, w)| {
-            let denom = w * ((w + 2.0).powi(2) + 5.0).sqrt();
-            num / denom
-    });
-
-    Box::new(fraction_iter)
+    for ((out, num), w) in out.iter_mut().zip(numerator).zip(w_logspace) {
+        let denom = w * ((w + 2.0).powi(2) + 5.0).sqrt();
+        *out = num / denom;
+    }
 }
 
 
-pub fn region_fraction<'a>(
-    w_linspace: &'a [f64],
+pub fn region_fraction(
+    w_linspace: &[f64],
     origin: Par,
-    _eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    _eps: f64,
+    out: &mut [f64])
 {
-    let fraction_iter = w_linspace
-        .iter()
-        .map(move |w| {
-            let num = f_complex(Comp::new(0.0, *w), origin).norm();
-            let denom = w * ((w + 2.0).powi(2) + 5.0).sqrt();
-            num / denom
-    });
-
-    Box::new(fraction_iter)
+    for (out, w) in out.iter_mut().zip(w_linspace) {
+        let num = f_complex(Comp::new(0.0, *w), origin).norm();
+        let denom = w * ((w + 2.0).powi(2) + 5.0).sqrt();
+        *out = num / denom;
+    }
+}
+
+
+/* Characteristic function divided by s^2+1, i.e. 1 + a1(s)e^(-s*tau1) + a2(s)e^(-s*tau2) */
+pub fn gu2005_a1(s: Comp) -> Comp {
+    (s + 2.0) / (s.powi(2) + 1.0)
+}
+
+
+pub fn gu2005_a2(s: Comp) -> Comp {
+    f64::sqrt(5.0) / (s.powi(2) + 1.0)
+}
+
+
+/* Bounds of |df/dp1| and |df/dp2| at any s (e.g. off the imaginary axis) over |q_i - p_i| <= eps_i */
+fn region_gradient(s: Comp, p: Par, eps: Par) -> (f64, f64) {
+    let sigma = s.re;
+    let exp_max = |tau: f64, eps: f64| f64::exp(-sigma*tau + sigma.abs()*eps);
+
+    let gradient_p1 = s.norm() * (s + 2.0).norm() * exp_max(p.0, eps.0);
+    let gradient_p2 = f64::sqrt(5.0) * s.norm() * exp_max(p.1, eps.1);
+
+    (gradient_p1, gradient_p2)
 }
 
 
@@ -59,8 +78,16 @@ pub const SYSTEM: System = System {
     name: "retarded2",
     f_complex,
     parameters: (r"\tau_1", r"\tau_2"),
+    singular_origin: false,
     region_fraction_precalculated_numerator: Option::Some(region_fraction_precalculated_numerator),
     region_fraction: Option::Some(region_fraction),
+    frequency_kernels: Option::None,
     line_denominator: Option::None,
+    line_fraction_precalculated_numerator: Option::None,
     region_denominator: Option::None,
+    region_fraction_lipschitz: Option::None,
+    line_fraction_lipschitz: Option::None,
+    region_fraction_tails: Option::None,
+    line_fraction_tails: Option::None,
+    region_gradient: Option::Some(region_gradient),
 };
\ No newline at end of file
@@ -1,4 +1,5 @@
-use crate::types::{Comp, Par, System};
+use crate::types::{Comp, FrequencyKernels, Par, System};
+use crate::utils::batch::KernelTable;
 
 
 const LAMBDA: f64 = 237.0;
@@ -18,25 +19,31 @@ fn f_complex(s: Comp, p: Par) -> Comp {
 }
 
 
-pub fn region_fraction_precalculated_numerator<'a>(
-    numerator: &'a [f64],
-    w_logspace: &'a [f64],
+/* Exponential factor of the denominator */
+fn frequency_kernels(w: f64, kernels: &mut [f64]) {
+    let helper = -X / (2.0 * SIGMA).sqrt();
+    kernels[0] = (helper * w.sqrt()).exp();
+}
+
+
+pub fn region_fraction_precalculated_numerator(
+    numerator: &[f64],
+    w_logspace: &[f64],
+    kernel_table: &KernelTable,
     origin: Par,
-    eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    eps: f64,
+    out: &mut [f64])
 {
     let k_max = origin.1 + eps;
     let k_powi = k_max.powi(2);
-    let helper = -X / (2.0 * SIGMA).sqrt();
-
-    let fraction_iter = numerator
-        .iter()
-        .zip(w_logspace.iter()).map(move |(num, w)| {
-            let a = (helper * w.sqrt()).exp();
-            let denom = a * (1.0 + k_powi * w.powi(2)).sqrt();
-            num / denom
-    });
 
-    Box::new(fraction_iter)
+    assert_eq!(kernel_table.rows().len(), w_logspace.len());
+    let fractions = out.iter_mut().zip(numerator).zip(w_logspace).zip(kernel_table.rows());
+    for (((out, num), w), kernels) in fractions {
+        let a = kernels[0];
+        let denom = a * (1.0 + k_powi * w.powi(2)).sqrt();
+        *out = num / denom;
+    }
 }
 
 
@@ -55,34 +62,53 @@ fn line_denominator(w: f64, p: Par, angle: f64, th_min: f64, th_max: f64) -> f64
 }
 
 
-pub fn region_fraction<'a>(
-    w_linspace: &'a [f64],
+pub fn line_fraction_precalculated_numerator(
+    numerator: &[f64],
+    w_logspace: &[f64],
+    origin: Par,
+    angle: f64,
+    th_min: f64,
+    th_max: f64,
+    out: &mut [f64])
+{
+    let c1 = f64::cos(angle);
+    let c2 = f64::sin(angle);
+    let k_max = f64::max(origin.1 + th_min * c2, origin.1 + th_max * c2);
+    let (c1_powi, c2_powi, k_max_powi) = (c1.powi(2), c2.powi(2), k_max.powi(2));
+
+    for ((out, num), w) in out.iter_mut().zip(numerator).zip(w_logspace) {
+        let term1 = (-X * f64::sqrt(w/(2.0*SIGMA))).exp();
+        let term2 = f64::sqrt(c2_powi + w.powi(2) * c1_powi * k_max_powi);
+        *out = num / (term1 * term2);
+    }
+}
+
+
+pub fn region_fraction(
+    w_linspace: &[f64],
     origin: Par,
-    eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    eps: f64,
+    out: &mut [f64])
 {
     let k_max = origin.1 + eps;
     let k_powi = k_max.powi(2);
     let helper = -X / (2.0 * SIGMA).sqrt();
 
-    let fraction_iter = w_linspace
-        .iter()
-        .map(move |w| {
-            let s = Comp::new(0.0, *w);
-            let tau = origin.0;
-            let k = origin.1;
-
-            let root = Comp::sqrt(s / SIGMA);
-            let term1 = LAMBDA * root;
-            let term2 = k * Comp::exp(-X * root - s*tau);
+    for (out, w) in out.iter_mut().zip(w_linspace) {
+        let s = Comp::new(0.0, *w);
+        let tau = origin.0;
+        let k = origin.1;
 
-            let num = (term1 + term2).norm();
+        let root = Comp::sqrt(s / SIGMA);
+        let term1 = LAMBDA * root;
+        let term2 = k * Comp::exp(-X * root - s*tau);
 
-            let a = (helper * w.sqrt()).exp();
-            let denom = a * (1.0 + k_powi * w.powi(2)).sqrt();
-            num / denom
-    });
+        let num = (term1 + term2).norm();
 
-    Box::new(fraction_iter)
+        let a = (helper * w.sqrt()).exp();
+        let denom = a * (1.0 + k_powi * w.powi(2)).sqrt();
+        *out = num / denom;
+    }
 }
 
 
@@ -90,8 +116,16 @@ pub const SYSTEM: System = System {
     name: "semi_infinite_rod",
     f_complex,
     parameters: (r"\tau", r"k"),
+    singular_origin: true,
     line_denominator: Option::Some(line_denominator),
+    line_fraction_precalculated_numerator: Option::Some(line_fraction_precalculated_numerator),
     region_denominator: Option::None,
     region_fraction_precalculated_numerator: Option::Some(region_fraction_precalculated_numerator),
     region_fraction: Option::Some(region_fraction),
+    frequency_kernels: Option::Some(FrequencyKernels { count: 1, evaluate: frequency_kernels }),
+    region_fraction_lipschitz: Option::None,
+    line_fraction_lipschitz: Option::None,
+    region_fraction_tails: Option::None,
+    line_fraction_tails: Option::None,
+    region_gradient: Option::None,
 };
\ No newline at end of file
@@ -1,4 +1,5 @@
-use crate::types::{Comp, Par, System};
+use crate::types::{Comp, FrequencyKernels, Par, System};
+use crate::utils::batch::KernelTable;
 
 const BETA: f64 = 2.0 / 3.0;
 const A: f64 = 4.160167646103808;
@@ -289,11 +290,28 @@ fn line_denominator(w: f64, p: Par, angle: f64, th_min: f64, th_max: f64) -> f64
 }
 
 
-pub fn region_fraction_precalculated_numerator<'a>(
-    numerator: &'a [f64],
-    w_logspace: &'a [f64],
+/* Real and imaginary parts of the logarithm of s and of the helper s^beta + A, along with
+ * the norm of the helper's inverse */
+fn frequency_kernels(w: f64, kernels: &mut [f64]) {
+    let s = Comp::new(0.0, w);
+    let helper = Comp::powf(s, BETA) + A;
+    let s_ln = s.ln();
+
+    kernels[0] = s_ln.re;
+    kernels[1] = s_ln.im;
+    kernels[2] = helper.re;
+    kernels[3] = helper.im;
+    kernels[4] = helper.powf(-1.0).norm();
+}
+
+
+pub fn region_fraction_precalculated_numerator(
+    numerator: &[f64],
+    w_logspace: &[f64],
+    kernel_table: &KernelTable,
     origin: Par,
-    eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    eps: f64,
+    out: &mut [f64])
 {
     let t0 = KX_HALF;
     let (alpha, gamma) = (origin.0, origin.1);
@@ -303,121 +321,119 @@ pub fn region_fraction_precalculated_numerator<'a>(
     let gamma_max = gamma + eps;
     let j1 = Comp::new(0.0, 1.0);
 
-    let fraction_iter = numerator
-        .iter()
-        .zip(w_logspace.iter()).map(move |(num, w)| {
-            let s = Comp::new(0.0, *w);
-            let s_ln = s.ln();
-            let helper = Comp::powf(s, BETA) + A;
-            let helper_arg = helper.arg();
-            let helper_arg_cos = helper_arg.cos();
-            let inverse_helper_norm = helper.powf(-1.0).norm();
-            let alpha_worst_max = if *w <= 1.0 { alpha_min } else { alpha_max };
-            let gamma_worst_max = if *w <= 1.0 { gamma_min } else { gamma_max };
-            let s_powf_alpha_min = s.powf(alpha_min);
-            let s_powf_gamma_min = s.powf(gamma_min);
-            let s_powf_alpha_max = s.powf(alpha_max);
-
-            let max_t1 = {
-                let min_rho = {
-                    let rho1 = inverse_helper_norm;
-                    let rho2 = {
-                        if (alpha_min - alpha_max).abs() > TOLERANCE {
-                            let result = if *w <= 1.0 {
-                                helper_arg_cos * B
-                            } else {
-                                let point1 = s_powf_alpha_min * helper + B;
-                                let point2 = s_powf_alpha_max * helper + B;
-                                let side_point1 = point1.norm();
-                                let side_point2 = point2.norm();
-                                let side_mutual = (point1 - point2).norm();
-
-                                let sh = (side_point1 + side_point2 + side_mutual) / 2.0; // Heron's formula
-                                let area = (sh * (sh - side_point1) * (sh - side_point2) * (sh - side_mutual)).sqrt();
-                                2.0 * area / side_mutual
-                            };
-                            result
-                        }
-                        else {
-                            let result = (s_powf_alpha_min * helper + B).norm();
-                            result
-                        }
-
-                    };
-                    let rho3 = {
-                        if *w <= 4.0 { 1.0 } else { (s_powf_gamma_min+1.0).norm()}
-                    };
-
-                    rho1 * rho2 * rho3
-                };
-                let max_phi = {
-                    let phi1 = -helper_arg;
-                    let phi2 = {
-                        if *w <= 1.0 {
-                            (j1.powf(alpha_max) * (j1.powf(BETA) + A) + B).arg()
-                        } else {
-                            (s_powf_alpha_max * ((s.powf(BETA)) + A) + B).arg()
-                        }
-                    };
-                    let phi3 = {
-                        if *w <= 1.0 {
-                            (j1.powf(gamma_max) + 1.0).arg()
+    assert_eq!(kernel_table.rows().len(), w_logspace.len());
+    let fractions = out.iter_mut().zip(numerator).zip(w_logspace).zip(kernel_table.rows());
+    for (((out, num), w), kernels) in fractions {
+        let s = Comp::new(0.0, *w);
+        let s_ln = Comp::new(kernels[0], kernels[1]);
+        let (helper, inverse_helper_norm) = (Comp::new(kernels[2], kernels[3]), kernels[4]);
+        let helper_arg = helper.arg();
+        let helper_arg_cos = helper_arg.cos();
+        let alpha_worst_max = if *w <= 1.0 { alpha_min } else { alpha_max };
+        let gamma_worst_max = if *w <= 1.0 { gamma_min } else { gamma_max };
+        let s_powf_alpha_min = s.powf(alpha_min);
+        let s_powf_gamma_min = s.powf(gamma_min);
+        let s_powf_alpha_max = s.powf(alpha_max);
+
+        let max_t1 = {
+            let min_rho = {
+                let rho1 = inverse_helper_norm;
+                let rho2 = {
+                    if (alpha_min - alpha_max).abs() > TOLERANCE {
+                        let result = if *w <= 1.0 {
+                            helper_arg_cos * B
                         } else {
-                            (s.powf(gamma_max) + 1.0).arg()
-                        }
-                    };
+                            let point1 = s_powf_alpha_min * helper + B;
+                            let point2 = s_powf_alpha_max * helper + B;
+                            let side_point1 = point1.norm();
+                            let side_point2 = point2.norm();
+                            let side_mutual = (point1 - point2).norm();
+
+                            let sh = (side_point1 + side_point2 + side_mutual) / 2.0; // Heron's formula
+                            let area = (sh * (sh - side_point1) * (sh - side_point2) * (sh - side_mutual)).sqrt();
+                            2.0 * area / side_mutual
+                        };
+                        result
+                    }
+                    else {
+                        let result = (s_powf_alpha_min * helper + B).norm();
+                        result
+                    }
 
-                    phi1 + phi2 + phi3
+                };
+                let rho3 = {
+                    if *w <= 4.0 { 1.0 } else { (s_powf_gamma_min+1.0).norm()}
                 };
 
-                (-X * min_rho.sqrt() * (max_phi/2.0).cos()).exp()
+                rho1 * rho2 * rho3
             };
-
-            let max_t2 = {
-                let p0 = inverse_helper_norm;
-                let p1 = if *w<=1.0 { B * helper_arg_cos } else {
-                    (s_powf_alpha_min * helper + B).norm()
+            let max_phi = {
+                let phi1 = -helper_arg;
+                let phi2 = {
+                    if *w <= 1.0 {
+                        (j1.powf(alpha_max) * (j1.powf(BETA) + A) + B).arg()
+                    } else {
+                        (s_powf_alpha_max * ((s.powf(BETA)) + A) + B).arg()
+                    }
+                };
+                let phi3 = {
+                    if *w <= 1.0 {
+                        (j1.powf(gamma_max) + 1.0).arg()
+                    } else {
+                        (s.powf(gamma_max) + 1.0).arg()
+                    }
                 };
 
-                let p2 =  if *w <= 4.0 {
-                    1.0
-                } else {
-                    (s_powf_gamma_min+1.0).norm()
-                }; // 4 is arbitrary
+                phi1 + phi2 + phi3
+            };
 
-                let min_psi = p0 * p1 * p2;
+            (-X * min_rho.sqrt() * (max_phi/2.0).cos()).exp()
+        };
 
-                min_psi.powf(-0.5)
+        let max_t2 = {
+            let p0 = inverse_helper_norm;
+            let p1 = if *w<=1.0 { B * helper_arg_cos } else {
+                (s_powf_alpha_min * helper + B).norm()
             };
 
-            let max_t3_alpha = {
-                s_ln.norm() * w.powf(alpha_worst_max) * (w.powf(gamma_worst_max) + 1.0)
-            };
+            let p2 =  if *w <= 4.0 {
+                1.0
+            } else {
+                (s_powf_gamma_min+1.0).norm()
+            }; // 4 is arbitrary
 
-            let max_t3_gamma = {
-                let p1 = (s_ln / helper).norm();
-                let p2 = w.powf(gamma_worst_max);
-                let p3 = w.powf(alpha_worst_max) * helper.norm() + B;
-                p1 * p2 * p3
-            };
+            let min_psi = p0 * p1 * p2;
 
-            let gradient_alpha = t0 * max_t1 * max_t2 * max_t3_alpha;
-            let gradient_gamma = t0 * max_t1 * max_t2 * max_t3_gamma;
+            min_psi.powf(-0.5)
+        };
 
-            let denominator = (gradient_alpha.powi(2) + gradient_gamma.powi(2)).sqrt();
-            let result = num / denominator;
+        let max_t3_alpha = {
+            s_ln.norm() * w.powf(alpha_worst_max) * (w.powf(gamma_worst_max) + 1.0)
+        };
+
+        let max_t3_gamma = {
+            let p1 = (s_ln / helper).norm();
+            let p2 = w.powf(gamma_worst_max);
+            let p3 = w.powf(alpha_worst_max) * helper.norm() + B;
+            p1 * p2 * p3
+        };
+
+        let gradient_alpha = t0 * max_t1 * max_t2 * max_t3_alpha;
+        let gradient_gamma = t0 * max_t1 * max_t2 * max_t3_gamma;
 
-            result
-    });
+        let denominator = (gradient_alpha.powi(2) + gradient_gamma.powi(2)).sqrt();
+        let result = num / denominator;
 
-    Box::new(fraction_iter)
+        *out = result;
+    }
 }
 
 
-pub fn region_fraction<'a>(
-    w_linspace: &'a [f64],
+pub fn region_fraction(
+    w_linspace: &[f64],
     origin: Par,
-    eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    eps: f64,
+    out: &mut [f64])
 {
     let t0 = KX_HALF;
     let (alpha, gamma) = (origin.0, origin.1);
@@ -427,112 +443,107 @@ pub fn region_fraction<'a>(
     let gamma_max = gamma + eps;
     let j1 = Comp::new(0.0, 1.0);
 
-    let fraction_iter = w_linspace
-        .iter()
-        .map(move |w| {
-            let num = f_complex(Comp::new(0.0, *w), origin).norm();
-            let s = Comp::new(0.0, *w);
-            let s_ln = s.ln();
-            let helper = Comp::powf(s, BETA) + A;
-            let helper_arg = helper.arg();
-            let helper_arg_cos = helper_arg.cos();
-            let inverse_helper_norm = helper.powf(-1.0).norm();
-            let alpha_worst_max = if *w <= 1.0 { alpha_min } else { alpha_max };
-            let gamma_worst_max = if *w <= 1.0 { gamma_min } else { gamma_max };
-            let s_powf_alpha_min = s.powf(alpha_min);
-            let s_powf_gamma_min = s.powf(gamma_min);
-            let s_powf_alpha_max = s.powf(alpha_max);
-
-            let max_t1 = {
-                let min_rho = {
-                    let rho1 = inverse_helper_norm;
-                    let rho2 = {
-                        if (alpha_min - alpha_max).abs() > TOLERANCE {
-                            let rho_2_small = helper_arg_cos * B;
+    for (out, w) in out.iter_mut().zip(w_linspace) {
+        let num = f_complex(Comp::new(0.0, *w), origin).norm();
+        let s = Comp::new(0.0, *w);
+        let s_ln = s.ln();
+        let helper = Comp::powf(s, BETA) + A;
+        let helper_arg = helper.arg();
+        let helper_arg_cos = helper_arg.cos();
+        let inverse_helper_norm = helper.powf(-1.0).norm();
+        let alpha_worst_max = if *w <= 1.0 { alpha_min } else { alpha_max };
+        let gamma_worst_max = if *w <= 1.0 { gamma_min } else { gamma_max };
+        let s_powf_alpha_min = s.powf(alpha_min);
+        let s_powf_gamma_min = s.powf(gamma_min);
+        let s_powf_alpha_max = s.powf(alpha_max);
+
+        let max_t1 = {
+            let min_rho = {
+                let rho1 = inverse_helper_norm;
+                let rho2 = {
+                    if (alpha_min - alpha_max).abs() > TOLERANCE {
+                        let rho_2_small = helper_arg_cos * B;
+
+                        let point1 = s_powf_alpha_min * helper + B;
+                        let point2 = s_powf_alpha_max * helper + B;
+                        let side_point1 = point1.norm();
+                        let side_point2 = point2.norm();
+                        let side_mutual = (point1 - point2).norm();
+
+                        let sh = (side_point1 + side_point2 + side_mutual) / 2.0; // Heron's formula
+                        let area = (sh * (sh - side_point1) * (sh - side_point2) * (sh - side_mutual)).sqrt();
+                        let rho_2_big = 2.0 * area / side_mutual;
+                        if *w <= 1.0 { rho_2_small } else { rho_2_big }
+                    }
+                    else {
+                        (s_powf_alpha_min * helper + B).norm()
+                    }
 
-                            let point1 = s_powf_alpha_min * helper + B;
-                            let point2 = s_powf_alpha_max * helper + B;
-                            let side_point1 = point1.norm();
-                            let side_point2 = point2.norm();
-                            let side_mutual = (point1 - point2).norm();
-
-                            let sh = (side_point1 + side_point2 + side_mutual) / 2.0; // Heron's formula
-                            let area = (sh * (sh - side_point1) * (sh - side_point2) * (sh - side_mutual)).sqrt();
-                            let rho_2_big = 2.0 * area / side_mutual;
-                            if *w <= 1.0 { rho_2_small } else { rho_2_big }
-                        }
-                        else {
-                            (s_powf_alpha_min * helper + B).norm()
-                        }
-
-                    };
-                    let rho3 = {
-                        let result_small = 1.0 + 0.0*w; // Not sure why
-                        let result_big = (s_powf_gamma_min+1.0).norm();
-                        if *w <= 4.0 { result_small } else { result_big } // 4 is arbitrary
-                    };
-
-                    rho1 * rho2 * rho3
                 };
-                let max_phi = {
-                    let phi1 = -helper_arg;
-                    let phi2 = {
-                        let result_big = (s_powf_alpha_max * ((s.powf(BETA)) + A) + B).arg();
-                        let result_w1 = (j1.powf(alpha_max) * (j1.powf(BETA) + A) + B).arg();
-                        let result_small = result_w1;
-                        if *w <= 1.0 { result_small } else { result_big }
-                    };
-                    let phi3 = {
-                        let result_big = (s.powf(gamma_max) + 1.0).arg();
-                        let result_w1 = (j1.powf(gamma_max) + 1.0).arg();
-                        let result_small = 0.0*w + result_w1; // Not sure why
-
-                        if *w <= 1.0 { result_small } else { result_big }
-                    };
-
-                    phi1 + phi2 + phi3
+                let rho3 = {
+                    let result_small = 1.0 + 0.0*w; // Not sure why
+                    let result_big = (s_powf_gamma_min+1.0).norm();
+                    if *w <= 4.0 { result_small } else { result_big } // 4 is arbitrary
                 };
 
-                (-X * min_rho.sqrt() * (max_phi/2.0).cos()).exp()
+                rho1 * rho2 * rho3
             };
+            let max_phi = {
+                let phi1 = -helper_arg;
+                let phi2 = {
+                    let result_big = (s_powf_alpha_max * ((s.powf(BETA)) + A) + B).arg();
+                    let result_w1 = (j1.powf(alpha_max) * (j1.powf(BETA) + A) + B).arg();
+                    let result_small = result_w1;
+                    if *w <= 1.0 { result_small } else { result_big }
+                };
+                let phi3 = {
+                    let result_big = (s.powf(gamma_max) + 1.0).arg();
+                    let result_w1 = (j1.powf(gamma_max) + 1.0).arg();
+                    let result_small = 0.0*w + result_w1; // Not sure why
 
-            let max_t2 = {
-                let p0 = inverse_helper_norm;
+                    if *w <= 1.0 { result_small } else { result_big }
+                };
 
-                let result_small_w = B * helper_arg_cos;
-                let result_big_w = (s_powf_alpha_min * helper + B).norm();
-                let p1 = if *w<=1.0 { result_small_w } else { result_big_w };
+                phi1 + phi2 + phi3
+            };
 
-                let p2_small = 1.0 + 0.0*w;
-                let p2_big = (s_powf_gamma_min+1.0).norm();
-                let p2 =  if *w <= 4.0 { p2_small } else { p2_big }; // 4 is arbitrary
+            (-X * min_rho.sqrt() * (max_phi/2.0).cos()).exp()
+        };
 
-                let min_psi = p0 * p1 * p2;
+        let max_t2 = {
+            let p0 = inverse_helper_norm;
 
-                min_psi.powf(-0.5)
-            };
+            let result_small_w = B * helper_arg_cos;
+            let result_big_w = (s_powf_alpha_min * helper + B).norm();
+            let p1 = if *w<=1.0 { result_small_w } else { result_big_w };
 
-            let max_t3_alpha = {
-                s_ln.norm() * w.powf(alpha_worst_max) * (w.powf(gamma_worst_max) + 1.0)
-            };
+            let p2_small = 1.0 + 0.0*w;
+            let p2_big = (s_powf_gamma_min+1.0).norm();
+            let p2 =  if *w <= 4.0 { p2_small } else { p2_big }; // 4 is arbitrary
 
-            let max_t3_gamma = {
-                let p1 = (s_ln / helper).norm();
-                let p2 = w.powf(gamma_worst_max);
-                let p3 = w.powf(alpha_worst_max) * helper.norm() + B;
-                p1 * p2 * p3
-            };
+            let min_psi = p0 * p1 * p2;
+
+            min_psi.powf(-0.5)
+        };
 
-            let gradient_alpha = t0 * max_t1 * max_t2 * max_t3_alpha;
-            let gradient_gamma = t0 * max_t1 * max_t2 * max_t3_gamma;
-            let denominator = (gradient_alpha.powi(2) + gradient_gamma.powi(2)).sqrt();
-            let result = num / denominator;
+        let max_t3_alpha = {
+            s_ln.norm() * w.powf(alpha_worst_max) * (w.powf(gamma_worst_max) + 1.0)
+        };
 
-            result
+        let max_t3_gamma = {
+            let p1 = (s_ln / helper).norm();
+            let p2 = w.powf(gamma_worst_max);
+            let p3 = w.powf(alpha_worst_max) * helper.norm() + B;
+            p1 * p2 * p3
+        };
 
-    });
+        let gradient_alpha = t0 * max_t1 * max_t2 * max_t3_alpha;
+        let gradient_gamma = t0 * max_t1 * max_t2 * max_t3_gamma;
+        let denominator = (gradient_alpha.powi(2) + gradient_gamma.powi(2)).sqrt();
+        let result = num / denominator;
 
-    Box::new(fraction_iter)
+        *out = result;
+    }
 }
 
 
@@ -540,8 +551,16 @@ pub const SYSTEM: System = System {
     name: "telegrapher_alpha_gamma",
     f_complex,
     parameters: (r"\alpha", r"\gamma"),
+    singular_origin: true,
     line_denominator: Option::Some(line_denominator),
+    line_fraction_precalculated_numerator: Option::None,
     region_denominator: Option::None,
     region_fraction_precalculated_numerator: Option::Some(region_fraction_precalculated_numerator),
     region_fraction: Option::Some(region_fraction),
+    frequency_kernels: Option::Some(FrequencyKernels { count: 5, evaluate: frequency_kernels }),
+    region_fraction_lipschitz: Option::None,
+    line_fraction_lipschitz: Option::None,
+    region_fraction_tails: Option::None,
+    line_fraction_tails: Option::None,
+    region_gradient: Option::None,
 };
\ No newline at end of file
@@ -1,4 +1,5 @@
-use crate::types::{Comp, Par, System};
+use crate::types::{Comp, FrequencyKernels, Par, System};
+use crate::utils::batch::KernelTable;
 
 const R: f64 = 172.24 * 1e-3;
 const C: f64 = 51.57 * 1e-12;
@@ -33,50 +34,77 @@ fn line_denominator(w: f64, p: Par, angle: f64, th_min: f64, th_max: f64) -> f64
 }
 
 
-pub fn region_fraction_precalculated_numerator<'a>(
-    numerator: &'a [f64],
-    w_logspace: &'a [f64],
+pub fn line_fraction_precalculated_numerator(
+    numerator: &[f64],
+    w_logspace: &[f64],
     origin: Par,
-    eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    angle: f64,
+    th_min: f64,
+    th_max: f64,
+    out: &mut [f64])
+{
+    let (c1, c2) = (f64::cos(angle), f64::sin(angle));
+    let k0 = origin.1;
+    let k_max = f64::max(k0 + c2*th_min, k0 + c2*th_max);
+    let (c1_powi, c2_powi, k_max_powi) = (c1.powi(2), c2.powi(2), k_max.powi(2));
+
+    for ((out, num), w) in out.iter_mut().zip(numerator).zip(w_logspace) {
+        let s = Comp::new(0.0, *w);
+        let sqrt_term = Comp::sqrt((C*s + G) * (L*s + R));
+        let t1 = Comp::norm(Comp::exp(-X0 * sqrt_term));
+        let t2 = f64::sqrt(c2_powi + c1_powi * w.powi(2) * k_max_powi);
+        *out = num / (t1 * t2);
+    }
+}
+
+
+/* Norm of the exponential prefix of the denominator */
+fn frequency_kernels(w: f64, kernels: &mut [f64]) {
+    let s = Comp::new(0.0, w);
+    let sqrt_term = Comp::sqrt((C*s + G) * (L*s + R));
+    let exponent = -X0 * sqrt_term;
+    kernels[0] = Comp::norm(Comp::exp(exponent));
+}
+
+
+pub fn region_fraction_precalculated_numerator(
+    numerator: &[f64],
+    w_logspace: &[f64],
+    kernel_table: &KernelTable,
+    origin: Par,
+    eps: f64,
+    out: &mut [f64])
 {
     let k_max = origin.1 + eps;
     let kmax_powi = k_max.powi(2);
-    let fraction_iter = numerator
-        .iter()
-        .zip(w_logspace.iter()).map(move |(num, w)| {
-            let s = Comp::new(0.0, *w);
-            let sqrt_term = Comp::sqrt((C*s + G) * (L*s + R));
-            let exponent = -X0 * sqrt_term;
-            let prefix = Comp::norm(Comp::exp(exponent));
-            let denominator = prefix * f64::sqrt(1.0 + w.powi(2) * kmax_powi);
-            num / denominator
-    });
-
-    Box::new(fraction_iter)
+    assert_eq!(kernel_table.rows().len(), w_logspace.len());
+    let fractions = out.iter_mut().zip(numerator).zip(w_logspace).zip(kernel_table.rows());
+    for (((out, num), w), kernels) in fractions {
+        let prefix = kernels[0];
+        let denominator = prefix * f64::sqrt(1.0 + w.powi(2) * kmax_powi);
+        *out = num / denominator;
+    }
 }
 
 
-pub fn region_fraction<'a>(
-    w_linspace: &'a [f64],
+pub fn region_fraction(
+    w_linspace: &[f64],
     origin: Par,
-    eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    eps: f64,
+    out: &mut [f64])
 {
     let k_max = origin.1 + eps;
     let kmax_powi = k_max.powi(2);
 
-    let fraction_iter = w_linspace
-        .iter()
-        .map(move |w| {
-            let num = f_complex(Comp::new(0.0, *w), origin).norm();
-            let s = Comp::new(0.0, *w);
-            let sqrt_term = Comp::sqrt((C*s + G) * (L*s + R));
-            let exponent = -X0 * sqrt_term;
-            let prefix = Comp::norm(Comp::exp(exponent));
-            let denominator = prefix * f64::sqrt(1.0 + w.powi(2) * kmax_powi);
-            num / denominator
-    });
-
-    Box::new(fraction_iter)
+    for (out, w) in out.iter_mut().zip(w_linspace) {
+        let num = f_complex(Comp::new(0.0, *w), origin).norm();
+        let s = Comp::new(0.0, *w);
+        let sqrt_term = Comp::sqrt((C*s + G) * (L*s + R));
+        let exponent = -X0 * sqrt_term;
+        let prefix = Comp::norm(Comp::exp(exponent));
+        let denominator = prefix * f64::sqrt(1.0 + w.powi(2) * kmax_powi);
+        *out = num / denominator;
+    }
 }
 
 
@@ -84,8 +112,16 @@ pub const SYSTEM: System = System {
     name: "telegrapher_standard",
     f_complex,
     parameters: (r"\tau", r"k"),
+    singular_origin: false,
     line_denominator: Option::Some(line_denominator),
+    line_fraction_precalculated_numerator: Option::Some(line_fraction_precalculated_numerator),
     region_denominator: Option::None,
     region_fraction_precalculated_numerator: Option::Some(region_fraction_precalculated_numerator),
     region_fraction: Option::Some(region_fraction),
+    frequency_kernels: Option::Some(FrequencyKernels { count: 1, evaluate: frequency_kernels }),
+    region_fraction_lipschitz: Option::None,
+    line_fraction_lipschitz: Option::None,
+    region_fraction_tails: Option::None,
+    line_fraction_tails: Option::None,
+    region_gradient: Option::None,
 };
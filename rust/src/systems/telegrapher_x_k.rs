@@ -1,4 +1,5 @@
-use crate::types::{Comp, Par, System};
+use crate::types::{Comp, FrequencyKernels, Par, System};
+use crate::utils::batch::KernelTable;
 
 const ALPHA: f64 = 5.0 / 6.0;
 const BETA: f64 = 2.0 / 3.0;
@@ -41,63 +42,67 @@ fn line_denominator(w: f64, p: Par, angle: f64, th_min: f64, th_max: f64) -> f64
 }
 
 
-pub fn region_fraction_precalculated_numerator<'a>(
-    numerator: &'a [f64],
-    w_logspace: &'a [f64],
+/* Norm of psi and the real part of its square root */
+fn frequency_kernels(w: f64, kernels: &mut [f64]) {
+    let s = Comp::new(0.0, w);
+    let s_alpha = Comp::powf(s, ALPHA);
+    let s_beta = Comp::powf(s, BETA);
+    let num1 = s_alpha * s_beta + A * s_alpha + B;
+    let num2 = Comp::powf(s, GAMMA) + 1.0;
+    let numerator = num1 * num2;
+    let denominator = s_beta + A;
+    let psi = numerator / denominator;
+    kernels[0] = psi.norm();
+    kernels[1] = psi.sqrt().re;
+}
+
+
+pub fn region_fraction_precalculated_numerator(
+    numerator: &[f64],
+    w_logspace: &[f64],
+    kernel_table: &KernelTable,
     origin: Par,
-    eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    eps: f64,
+    out: &mut [f64])
 {
     let x_min = origin.0 - eps;
     let k_max = origin.1 + eps;
     let kmax_powi = k_max.powi(2);
-    let fraction_iter = numerator
-        .iter()
-        .zip(w_logspace.iter()).map(move |(num, w)| {
-            let s = Comp::new(0.0, *w);
-            let s_alpha = Comp::powf(s, ALPHA);
-            let s_beta = Comp::powf(s, BETA);
-            let num1 = s_alpha * s_beta + A * s_alpha + B;
-            let num2 = Comp::powf(s, GAMMA) + 1.0;
-            let numerator = num1 * num2;
-            let denominator = s_beta + A;
-            let psi = numerator / denominator;
-            let r = psi.sqrt();
-            let t = (-x_min * r.re).exp();
-            let denominator = t * (1.0 + kmax_powi * psi.norm()).sqrt();
-            num / denominator
-    });
-
-    Box::new(fraction_iter)
+    assert_eq!(kernel_table.rows().len(), w_logspace.len());
+    let fractions = out.iter_mut().zip(numerator).zip(kernel_table.rows());
+    for ((out, num), kernels) in fractions {
+        let (psi_norm, r_re) = (kernels[0], kernels[1]);
+        let t = (-x_min * r_re).exp();
+        let denominator = t * (1.0 + kmax_powi * psi_norm).sqrt();
+        *out = num / denominator;
+    }
 }
 
 
-pub fn region_fraction<'a>(
-    w_linspace: &'a [f64],
+pub fn region_fraction(
+    w_linspace: &[f64],
     origin: Par,
-    eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    eps: f64,
+    out: &mut [f64])
 {
     let x_min = origin.0 - eps;
     let k_max = origin.1 + eps;
     let kmax_powi = k_max.powi(2);
-    let fraction_iter = w_linspace
-        .iter()
-        .map(move |w| {
-            let num = f_complex(Comp::new(0.0, *w), origin).norm();
-            let s = Comp::new(0.0, *w);
-            let s_alpha = Comp::powf(s, ALPHA);
-            let s_beta = Comp::powf(s, BETA);
-            let num1 = s_alpha * s_beta + A * s_alpha + B;
-            let num2 = Comp::powf(s, GAMMA) + 1.0;
-            let numerator = num1 * num2;
-            let denominator = s_beta + A;
-            let psi = numerator / denominator;
-            let r = psi.sqrt();
-            let t = (-x_min * r.re).exp();
-            let denominator = t * (1.0 + kmax_powi * psi.norm()).sqrt();
-            num / denominator
-    });
-
-    Box::new(fraction_iter)
+    for (out, w) in out.iter_mut().zip(w_linspace) {
+        let num = f_complex(Comp::new(0.0, *w), origin).norm();
+        let s = Comp::new(0.0, *w);
+        let s_alpha = Comp::powf(s, ALPHA);
+        let s_beta = Comp::powf(s, BETA);
+        let num1 = s_alpha * s_beta + A * s_alpha + B;
+        let num2 = Comp::powf(s, GAMMA) + 1.0;
+        let numerator = num1 * num2;
+        let denominator = s_beta + A;
+        let psi = numerator / denominator;
+        let r = psi.sqrt();
+        let t = (-x_min * r.re).exp();
+        let denominator = t * (1.0 + kmax_powi * psi.norm()).sqrt();
+        *out = num / denominator;
+    }
 }
 
 
@@ -105,8 +110,16 @@ pub const SYSTEM: System = System {
     name: "telegrapher_x_k",
     f_complex,
     parameters: (r"x", r"k"),
+    singular_origin: true,
     line_denominator: Option::Some(line_denominator),
+    line_fraction_precalculated_numerator: Option::None,
     region_denominator: Option::None,
     region_fraction_precalculated_numerator: Option::Some(region_fraction_precalculated_numerator),
     region_fraction: Option::Some(region_fraction),
+    frequency_kernels: Option::Some(FrequencyKernels { count: 2, evaluate: frequency_kernels }),
+    region_fraction_lipschitz: Option::None,
+    line_fraction_lipschitz: Option::None,
+    region_fraction_tails: Option::None,
+    line_fraction_tails: Option::None,
+    region_gradient: Option::None,
 };
\ No newline at end of file
@@ -1,4 +1,5 @@
 use crate::types::{Comp, Par, System};
+use crate::utils::batch::KernelTable;
 
 
 fn f_complex(s: Comp, p: Par) -> Comp {
@@ -19,46 +20,124 @@ fn line_denominator(w: f64, p: Par, angle: f64, th_min: f64, th_max: f64) -> f64
 }
 
 
-pub fn region_fraction_precalculated_numerator<'a>(
-    numerator: &'a [f64],
-    w_logspace: &'a [f64],
+/* Bounds on |f(jw, p)| and |df(jw, p)/dw| valid for every frequency below w_max */
+fn numerator_bounds(w_max: f64, p: Par) -> (f64, f64) {
+    let (a, b) = (p.0, p.1);
+
+    let norm_bound = w_max.powi(2) + a.abs()*w_max + (a.powi(2) + b - 1.0).abs();
+    let derivative_bound = 2.0*w_max + a.abs();
+
+    (norm_bound, derivative_bound)
+}
+
+
+fn line_fraction_lipschitz(w_min: f64, w_max: f64, p: Par, angle: f64, th_min: f64, th_max: f64) -> f64 {
+    let c1 = f64::cos(angle);
+    let p0 = (p.0 + th_min*c1, p.1 + th_min*f64::sin(angle));
+    let (norm_bound, derivative_bound) = numerator_bounds(w_max, p0);
+
+    /* The denominator is increasing, with a derivative bounded by |c1| */
+    let denom_min = line_denominator(w_min, p, angle, th_min, th_max);
+    let denom_derivative_max = c1.abs();
+
+    derivative_bound / denom_min + norm_bound * denom_derivative_max / denom_min.powi(2)
+}
+
+
+fn region_fraction_lipschitz(w_min: f64, w_max: f64, p: Par, eps: f64) -> f64 {
+    let (norm_bound, derivative_bound) = numerator_bounds(w_max, p);
+    let a_max = p.0 + eps;
+
+    /* The denominator is increasing, with a derivative bounded by 1 */
+    let denom_min = (w_min.powi(2) + 4.0 * a_max.powi(2) + 1.0).sqrt();
+    let denom_derivative_max = 1.0;
+
+    derivative_bound / denom_min + norm_bound * denom_derivative_max / denom_min.powi(2)
+}
+
+
+/* Lower bounds of |f(jw, p)| for all frequencies below w_min and above w_max, respectively */
+fn numerator_tails(w_min: f64, w_max: f64, p: Par) -> (f64, f64) {
+    let (a, b) = (p.0, p.1);
+    let constant_term = (a.powi(2) + b - 1.0).abs();
+
+    let low_tail = f64::max(0.0, constant_term - w_min.powi(2));
+    let high_tail = f64::max(0.0, w_max.powi(2) - constant_term);
+
+    (low_tail, high_tail)
+}
+
+
+fn line_fraction_tails(w_min: f64, w_max: f64, p: Par, angle: f64, th_min: f64, th_max: f64) -> (f64, f64) {
+    let p0 = (p.0 + th_min*f64::cos(angle), p.1 + th_min*f64::sin(angle));
+    let (num_low, num_high) = numerator_tails(w_min, w_max, p0);
+
+    /* The denominator is increasing, and the high tail fraction grows once it is positive */
+    let low_tail = num_low / line_denominator(w_min, p, angle, th_min, th_max);
+    let high_tail = num_high / line_denominator(w_max, p, angle, th_min, th_max);
+
+    (low_tail, high_tail)
+}
+
+
+fn region_fraction_tails(w_min: f64, w_max: f64, p: Par, eps: f64) -> (f64, f64) {
+    let (num_low, num_high) = numerator_tails(w_min, w_max, p);
+    let a_max = p.0 + eps;
+    let denominator = |w: f64| (w.powi(2) + 4.0 * a_max.powi(2) + 1.0).sqrt();
+
+    /* The denominator is increasing, and the high tail fraction grows once it is positive */
+    let low_tail = num_low / denominator(w_min);
+    let high_tail = num_high / denominator(w_max);
+
+    (low_tail, high_tail)
+}
+
+
+pub fn region_fraction_precalculated_numerator(
+    numerator: &[f64],
+    w_logspace: &[f64],
+    _kernels: &KernelTable,
     origin: Par,
-    eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    eps: f64,
+    out: &mut [f64])
 {
     let a_max = origin.0 + eps;
     let amax_powi = a_max.powi(2);
     let amax_powi_times_4 = amax_powi * 4.0;
-    let fraction_iter = numerator
-        .iter()
-        .zip(w_logspace.iter()).map(move |(num, w)| {
-            let derivative_a = (w.powi(2) + amax_powi_times_4).sqrt();
-            let denominator = (derivative_a.powi(2) + 1.0).sqrt();
-            num / denominator
-    });
-
-    Box::new(fraction_iter)
+    for ((out, num), w) in out.iter_mut().zip(numerator).zip(w_logspace) {
+        let derivative_a = (w.powi(2) + amax_powi_times_4).sqrt();
+        let denominator = (derivative_a.powi(2) + 1.0).sqrt();
+        *out = num / denominator;
+    }
 }
 
 
-pub fn region_fraction<'a>(
-    w_linspace: &'a [f64],
+pub fn region_fraction(
+    w_linspace: &[f64],
     origin: Par,
-    eps: f64) -> Box<dyn Iterator<Item=f64> + 'a>
+    eps: f64,
+    out: &mut [f64])
 {
     let a_max = origin.0 + eps;
     let amax_powi = a_max.powi(2);
     let amax_powi_times_4 = amax_powi * 4.0;
 
-    let fraction_iter = w_linspace
-        .iter()
-        .map(move |w| {
-            let num = f_complex(Comp::new(0.0, *w), origin).norm();
-            let derivative_a = (w.powi(2) + amax_powi_times_4).sqrt();
-            let denominator = (derivative_a.powi(2) + 1.0).sqrt();
-            num / denominator
-    });
+    for (out, w) in out.iter_mut().zip(w_linspace) {
+        let num = f_complex(Comp::new(0.0, *w), origin).norm();
+        let derivative_a = (w.powi(2) + amax_powi_times_4).sqrt();
+        let denominator = (derivative_a.powi(2) + 1.0).sqrt();
+        *out = num / denominator;
+    }
+}
+
+
+/* Bounds of |df/dp1| and |df/dp2| at any s (e.g. off the imaginary axis) over |q_i - p_i| <= eps_i */
+fn region_gradient(s: Comp, p: Par, eps: Par) -> (f64, f64) {
+    let a_max = p.0.abs() + eps.0;
+    let gradient_p1 = s.norm() + 2.0*a_max;
+    let gradient_p2 = 1.0;
 
-    Box::new(fraction_iter)
+    (gradient_p1, gradient_p2)
 }
 
 
@@ -66,8 +145,16 @@ pub const SYSTEM: System = System {
     name: "test_system",
     f_complex,
     parameters: ("a", "b"),
+    singular_origin: false,
     line_denominator: Option::Some(line_denominator),
+    line_fraction_precalculated_numerator: Option::None,
     region_denominator: Option::None,
     region_fraction_precalculated_numerator: Option::Some(region_fraction_precalculated_numerator),
     region_fraction: Option::Some(region_fraction),
+    frequency_kernels: Option::None,
+    region_fraction_lipschitz: Option::Some(region_fraction_lipschitz),
+    line_fraction_lipschitz: Option::Some(line_fraction_lipschitz),
+    region_fraction_tails: Option::Some(region_fraction_tails),
+    line_fraction_tails: Option::Some(line_fraction_tails),
+    region_gradient: Option::Some(region_gradient),
 };
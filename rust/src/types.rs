@@ -1,20 +1,30 @@
 use std::iter::{Map, Zip};
 use core::slice::Iter;
 use serde::Serialize;
+use crate::utils::batch::KernelTable;
 
 
 /* Convenience aliases */
 pub type Par = (f64, f64);
 pub type Comp = num::complex::Complex64;
 pub type LineDenomFunc = fn(w: f64, p: Par, angle: f64, th_min: f64, th_max: f64) -> f64;
+pub type LineFractionPrecalculatedNumerator =
+    fn(numerator: &[f64], w: &[f64], p: Par, angle: f64, th_min: f64, th_max: f64, out: &mut [f64]);
 pub type RegionDenomFunc = fn(w: f64, p: Par, eps: f64) -> f64;
 pub type RegionFractionPrecalculatedNumerator =
-    for<'a> fn(&'a[f64], &'a[f64], Par, f64) -> Box<dyn Iterator<Item=f64> + 'a>;
-pub type RegionFraction =
-    for<'a> fn(&'a[f64], Par, f64) -> Box<dyn Iterator<Item=f64> + 'a>;
+    fn(numerator: &[f64], w: &[f64], kernels: &KernelTable, p: Par, eps: f64, out: &mut [f64]);
+pub type RegionFraction = fn(w: &[f64], p: Par, eps: f64, out: &mut [f64]);
+pub type RegionFractionLipschitz = fn(w_min: f64, w_max: f64, p: Par, eps: f64) -> f64;
+pub type LineFractionLipschitz =
+    fn(w_min: f64, w_max: f64, p: Par, angle: f64, th_min: f64, th_max: f64) -> f64;
+pub type RegionFractionTails = fn(w_min: f64, w_max: f64, p: Par, eps: f64) -> (f64, f64);
+pub type LineFractionTails =
+    fn(w_min: f64, w_max: f64, p: Par, angle: f64, th_min: f64, th_max: f64) -> (f64, f64);
+pub type RegionGradientFunc = fn(s: Comp, p: Par, eps: Par) -> (f64, f64);
+pub type FrequencyKernelFunc = fn(w: f64, kernels: &mut [f64]);
 
 /* Rectangular 2D limits */
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Limits {
     pub p1_min: f64,
     pub p1_max: f64,
@@ -23,13 +33,31 @@ pub struct Limits {
 }
 
 
+/* Parameter-independent values which fractions of a system need at each frequency; they are
+ * calculated once per frequency grid instead of once per fraction evaluation */
+#[derive(Clone, Copy)]
+pub struct FrequencyKernels {
+    pub count: usize,
+    pub evaluate: FrequencyKernelFunc,
+}
+
+
 /* A LTI system represented by its characteristic function in Laplace domain */
+#[derive(Clone, Copy)]
 pub struct System {
     pub name: &'static str,
     pub f_complex: fn(Comp, Par) -> Comp,
     pub parameters: (&'static str, &'static str),
+    pub singular_origin: bool, // Branch point of f at s = 0, e.g. from ln s or fractional powers
     pub line_denominator: Option<LineDenomFunc>,
+    pub line_fraction_precalculated_numerator: Option<LineFractionPrecalculatedNumerator>,
     pub region_denominator: Option<RegionDenomFunc>,
     pub region_fraction_precalculated_numerator: Option<RegionFractionPrecalculatedNumerator>,
     pub region_fraction: Option<RegionFraction>,
+    pub frequency_kernels: Option<FrequencyKernels>,
+    pub region_fraction_lipschitz: Option<RegionFractionLipschitz>,
+    pub line_fraction_lipschitz: Option<LineFractionLipschitz>,
+    pub region_fraction_tails: Option<RegionFractionTails>,
+    pub line_fraction_tails: Option<LineFractionTails>,
+    pub region_gradient: Option<RegionGradientFunc>,
 }
\ No newline at end of file
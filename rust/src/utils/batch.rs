@@ -0,0 +1,91 @@
+use crate::types::{Comp, FrequencyKernels};
+
+
+/* Complex values in structure-of-arrays layout; keeping real and imaginary parts in separate
+ * buffers lets element-wise loops over them be vectorized */
+pub struct CompBatch {
+    pub re: Vec<f64>,
+    pub im: Vec<f64>,
+}
+
+
+impl CompBatch {
+    /* Evaluate a complex function at every given frequency */
+    pub fn evaluate<F>(w: &[f64], f: F) -> CompBatch
+    where F: Fn(f64) -> Comp
+    {
+        let mut batch = CompBatch { re: Vec::with_capacity(w.len()), im: Vec::with_capacity(w.len()) };
+        for value in w.iter().map(|w| f(*w)) {
+            batch.re.push(value.re);
+            batch.im.push(value.im);
+        }
+        batch
+    }
+
+    /* Absolute values of all elements; parts are scaled by the larger one in order to avoid
+     * overflow, as in hypot, but without a branch which would prevent vectorization */
+    pub fn norms(&self, out: &mut [f64]) {
+        for ((out, re), im) in out.iter_mut().zip(&self.re).zip(&self.im) {
+            let scale = f64::max(re.abs(), im.abs());
+            let (re, im) = (re / scale, im / scale);
+            let norm = scale * (re * re + im * im).sqrt();
+            *out = if scale > 0.0 && scale.is_finite() { norm } else { scale };
+        }
+    }
+}
+
+
+/* Frequency kernels of a system, evaluated at every frequency of a grid; kernels are real, so
+ * a complex one takes two consecutive entries for its real and imaginary part */
+pub struct KernelTable {
+    count: usize,
+    values: Vec<f64>,
+}
+
+
+impl KernelTable {
+    pub fn new(kernels: Option<FrequencyKernels>, w: &[f64]) -> KernelTable {
+        let Some(kernels) = kernels else {
+            return KernelTable { count: 0, values: Vec::new() };
+        };
+
+        let mut values = vec![0.0; kernels.count * w.len()];
+        for (w, row) in w.iter().zip(values.chunks_exact_mut(kernels.count)) {
+            (kernels.evaluate)(*w, row);
+        }
+        KernelTable { count: kernels.count, values }
+    }
+
+    /* Kernels at each frequency of the grid, in order */
+    pub fn rows(&self) -> std::slice::ChunksExact<'_, f64> {
+        if self.count == 0 {
+            panic!("System declares no frequency kernels");
+        }
+        self.values.chunks_exact(self.count)
+    }
+}
+
+
+/* Frequency grid along with the kernels of a system precalculated on it */
+pub struct FrequencyGrid {
+    pub w: Vec<f64>,
+    pub kernels: KernelTable,
+}
+
+
+impl FrequencyGrid {
+    pub fn new(w: Vec<f64>, kernels: Option<FrequencyKernels>) -> FrequencyGrid {
+        let kernels = KernelTable::new(kernels, &w);
+        FrequencyGrid { w, kernels }
+    }
+}
+
+
+/* Evaluate a batch fraction function at a single frequency */
+pub fn evaluate_single<F>(fraction: F, w: f64) -> f64
+where F: Fn(&[f64], &mut [f64])
+{
+    let mut value = [0.0];
+    fraction(&[w], &mut value);
+    value[0]
+}
@@ -1,7 +1,9 @@
+use std::f64::consts::PI;
 use crate::types::{Par, Limits};
 use crate::utils::geometry;
 
 
+#[derive(Clone)]
 pub enum Delta {
     Abs(f64),
     Rel(f64),
@@ -18,3 +20,121 @@ pub fn is_point_in_limits(point: Par, limits: &Limits) -> bool {
     }
 }
 
+
+
+/* Hölder q-norm of a 2D vector, for q in [1, inf] */
+pub fn q_norm(v: Par, q: f64) -> f64 {
+    let (x, y) = (v.0.abs(), v.1.abs());
+
+    if q.is_infinite() {
+        f64::max(x, y)
+    } else if q == 1.0 {
+        x + y
+    } else {
+        (x.powf(q) + y.powf(q)).powf(1.0 / q)
+    }
+}
+
+
+/* Exponent q* of the dual norm, satisfying 1/q + 1/q* = 1 */
+pub fn dual_exponent(q: f64) -> f64 {
+    if q == 1.0 {
+        f64::INFINITY
+    } else if q.is_infinite() {
+        1.0
+    } else {
+        q / (q - 1.0)
+    }
+}
+
+
+/* Is the polygon convex, with at least 3 vertices given in either orientation; turning the
+ * same way at every vertex is not enough, since the vertices must also go around only once */
+pub fn is_convex_polygon(polygon: &[Par]) -> bool {
+    if polygon.len() < 3 {
+        return false;
+    }
+
+    let edges: Vec<Par> = polygon.iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(a, b)| (b.0 - a.0, b.1 - a.1))
+        .collect();
+    let turns: Vec<f64> = edges.iter()
+        .zip(edges.iter().cycle().skip(1))
+        .map(|(u, v)| (u.0 * v.1 - u.1 * v.0).atan2(u.0 * v.0 + u.1 * v.1))
+        .collect();
+
+    let same_direction = turns.iter().all(|t| *t > 0.0) || turns.iter().all(|t| *t < 0.0);
+    let total_turn: f64 = turns.iter().sum();
+
+    same_direction && (total_turn.abs() - 2.0 * PI).abs() < 1e-6
+}
+
+
+/* Does the axis-aligned rectangle intersect the convex polygon (separating axis theorem) */
+pub fn does_rectangle_intersect_polygon(p_min: Par, p_max: Par, polygon: &[Par]) -> bool {
+    let rectangle = [p_min, (p_max.0, p_min.1), p_max, (p_min.0, p_max.1)];
+    let edges = polygon.iter().zip(polygon.iter().cycle().skip(1));
+    let mut axes: Vec<Par> = edges.map(|(a, b)| (a.1 - b.1, b.0 - a.0)).collect();
+    axes.push((1.0, 0.0));
+    axes.push((0.0, 1.0));
+
+    let project = |points: &[Par], axis: Par| {
+        points.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), p| {
+            let projection = p.0 * axis.0 + p.1 * axis.1;
+            (f64::min(min, projection), f64::max(max, projection))
+        })
+    };
+
+    axes.iter().all(|axis| {
+        let (rectangle_min, rectangle_max) = project(&rectangle, *axis);
+        let (polygon_min, polygon_max) = project(polygon, *axis);
+        rectangle_min <= polygon_max && polygon_min <= rectangle_max
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRIANGLE: [Par; 3] = [(0.0, 0.0), (4.0, 0.0), (0.0, 4.0)];
+
+    #[test]
+    fn test_does_rectangle_intersect_polygon() {
+        /* Rectangle inside, containing, overlapping and touching the triangle */
+        assert!(does_rectangle_intersect_polygon((0.5, 0.5), (1.0, 1.0), &TRIANGLE));
+        assert!(does_rectangle_intersect_polygon((-1.0, -1.0), (5.0, 5.0), &TRIANGLE));
+        assert!(does_rectangle_intersect_polygon((3.0, -1.0), (5.0, 0.5), &TRIANGLE));
+        assert!(does_rectangle_intersect_polygon((2.0, 2.0), (3.0, 3.0), &TRIANGLE));
+
+        /* Bounding boxes overlap, but the hypotenuse separates them */
+        assert!(!does_rectangle_intersect_polygon((2.5, 2.5), (3.0, 3.0), &TRIANGLE));
+
+        /* Separated along one of the coordinate axes */
+        assert!(!does_rectangle_intersect_polygon((5.0, 0.0), (6.0, 1.0), &TRIANGLE));
+        assert!(!does_rectangle_intersect_polygon((0.0, -2.0), (1.0, -1.0), &TRIANGLE));
+
+        /* Orientation of the polygon does not matter */
+        let reversed: Vec<Par> = TRIANGLE.iter().rev().cloned().collect();
+        assert!(does_rectangle_intersect_polygon((0.5, 0.5), (1.0, 1.0), &reversed));
+        assert!(!does_rectangle_intersect_polygon((2.5, 2.5), (3.0, 3.0), &reversed));
+    }
+
+    #[test]
+    fn test_is_convex_polygon() {
+        let square = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+        let reversed: Vec<Par> = square.iter().rev().cloned().collect();
+        let concave = [(0.0, 0.0), (2.0, 0.0), (1.0, 0.5), (2.0, 2.0), (0.0, 2.0)];
+        let crossed = [(0.0, 0.0), (1.0, 1.0), (1.0, 0.0), (0.0, 1.0)];
+        let pentagram = [(0.0, 1.0), (0.588, -0.809), (-0.951, 0.309), (0.951, 0.309), (-0.588, -0.809)];
+
+        assert!(is_convex_polygon(&TRIANGLE));
+        assert!(is_convex_polygon(&square));
+        assert!(is_convex_polygon(&reversed));
+        assert!(!is_convex_polygon(&concave));
+        assert!(!is_convex_polygon(&crossed));
+        assert!(!is_convex_polygon(&pentagram));
+        assert!(!is_convex_polygon(&TRIANGLE[..2]));
+    }
+}
@@ -1,3 +1,4 @@
+pub mod batch;
 pub mod geometry;
 pub mod optimization;
-pub mod storage;
\ No newline at end of file
+pub mod storage;
@@ -1,12 +1,9 @@
 use lazy_static::lazy_static;
 use log::{debug, info, warn};
 
-use iter_num_tools::{log_space, lin_space};
-use rayon::iter::Update;
-use crate::types::Par;
-use crate::systems::distributed_delay1;
-
-use crate::types;
+use crate::utils::batch;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
 use std::sync::Mutex;
 
 
@@ -71,28 +68,173 @@ where F: Fn(f64) -> bool
     x
 }
 
-pub struct MinimizationProblemSlow<'b, F1, F2>
+/* Largest x up to limit for which x < minimum(x), where minimum is nonincreasing. The solution
+ * is the fixed point of minimum, i.e. the root of the decreasing gap minimum(x) - x, which is
+ * approached by secant steps through the last two evaluations; the first step, lacking a slope,
+ * jumps straight to the minimum. Steps that leave the bracket or stop shrinking it are replaced
+ * by bisection. Besides the value, minimum tells whether it is exact or merely a lower bound */
+pub fn get_maximum_fixed_point<F>(minimum: F, initial: f64, tolerance: f64, limit: f64) -> f64
+where F: Fn(f64) -> (f64, bool)
+{
+    const GROWTH_FACTOR: f64 = 2.0;
+    let mut lower: f64 = 0.0; // Largest x known to fulfill the condition
+    let mut upper: f64 = f64::INFINITY; // Smallest x known not to fulfill the condition
+    let mut previous: Option<(f64, f64)> = Option::None;
+    let mut previous_width = f64::INFINITY;
+    let mut x = initial.max(tolerance).min(limit);
+    let mut evaluations: u32 = 0;
+    debug!("Searching for fixed point with: limit={}, initial={}, tolerance={}", limit, initial, tolerance);
+
+    loop {
+        if !x.is_finite() {
+            panic!("Condition seems to be fulfilled for arbitrarily large values!");
+        }
+
+        let (value, exact) = minimum(x);
+        let gap = value - x;
+        evaluations += 1;
+        let fulfilled = gap > 0.0;
+        debug!("Trying {}: minimum {}, exact {}, fulfilled {}", x, value, exact, fulfilled);
+
+        /* Being nonincreasing, minimum cannot be exceeded by the fixed point once x is valid;
+         * a lower bound on the minimum tells nothing about the fixed point though */
+        match (fulfilled, exact) {
+            (true, true) => (lower, upper) = (x, upper.min(value)),
+            (true, false) => lower = x,
+            (false, _) => upper = x,
+        }
+
+        if fulfilled && x >= limit {
+            debug!("Limit {} reached; aborting", limit);
+            break;
+        }
+
+        let width = upper - lower;
+        if width <= tolerance {
+            break;
+        }
+
+        /* Aim slightly below the estimated fixed point, so that the guess is likely to be valid */
+        let fixed_point = match previous {
+            Some((x_previous, gap_previous)) if gap != gap_previous =>
+                x - gap * (x - x_previous) / (gap - gap_previous),
+            _ => value,
+        };
+        let guess = f64::min(fixed_point - tolerance / 2.0, limit);
+        let shrinking = width < previous_width / 2.0;
+        previous = Option::Some((x, gap));
+        previous_width = width;
+
+        /* Guesses too close to the bracket ends would hardly narrow it, so they are pushed inwards */
+        let use_guess = lower < guess && guess < upper && shrinking;
+        x = match (use_guess, upper.is_finite()) {
+            (true, true) => guess.max(lower + tolerance / 2.0).min(upper - tolerance / 2.0),
+            (true, false) => guess.max(lower + tolerance / 2.0),
+            (false, true) => (lower + upper) / 2.0,
+            (false, false) => (lower * GROWTH_FACTOR).max(tolerance).min(limit),
+        };
+    }
+    debug!("Found maximum condition fulfilled by {} using {} evaluations", lower, evaluations);
+
+    lower
+}
+
+
+/* Fraction to be minimized over frequencies; it is evaluated in batches, each evaluation filling
+ * the output slice with the values of the fraction at the given frequencies */
+pub trait FractionProblem {
+    /* Frequencies at which the fraction is sampled before the search is refined */
+    fn log_space(&self) -> &[f64];
+
+    fn evaluate(&self, w: &[f64], out: &mut [f64]);
+
+    /* Fraction at the logspace frequencies, which may be calculated from values precalculated on it */
+    fn evaluate_log_space(&self, out: &mut [f64]) {
+        self.evaluate(self.log_space(), out)
+    }
+
+    fn evaluate_single(&self, w: f64) -> f64 {
+        batch::evaluate_single(|w, out| self.evaluate(w, out), w)
+    }
+}
+
+
+/* Fraction given by batch functions, the first one using a precalculated logspace numerator */
+pub struct BatchFraction<'b, F1, F2>
 where
-    F1: Fn(f64) -> f64,
-    F2: Fn(f64) -> f64,
+    F1: Fn(&[f64], &mut [f64]),
+    F2: Fn(&[f64], &mut [f64]),
 {
     pub log_space: &'b[f64],
-    pub lin_steps: usize,
-    pub precalculated_numerator: &'b[f64],
-    pub denominator_function: &'b F1,
+    pub logspace_fraction_function: &'b F1,
     pub fraction_function: &'b F2,
 }
 
 
-pub struct MinimizationProblemFast<'b>
+impl<F1, F2> FractionProblem for BatchFraction<'_, F1, F2>
+where
+    F1: Fn(&[f64], &mut [f64]),
+    F2: Fn(&[f64], &mut [f64]),
 {
-    pub log_space: &'b[f64],
+    fn log_space(&self) -> &[f64] {
+        self.log_space
+    }
+
+    fn evaluate(&self, w: &[f64], out: &mut [f64]) {
+        (self.fraction_function)(w, out)
+    }
+
+    fn evaluate_log_space(&self, out: &mut [f64]) {
+        (self.logspace_fraction_function)(self.log_space, out)
+    }
+}
+
+
+/* Way of searching for the minimum of a fraction problem */
+pub trait MinimizationStrategy {
+    fn minimize<P: FractionProblem>(&self, problem: &P) -> MinimizationResult;
+}
+
+
+/* Minimum of the fraction sampled on the logspace, without any refinement */
+pub struct GridSearch;
+
+
+/* Deepest logspace local minima refined by Brent's method, with the resolution given by the
+ * number of linear steps between neighbouring logspace frequencies */
+pub struct BrentSearch {
     pub lin_steps: usize,
-    pub logspace_fraction_iterator: Box<dyn Iterator<Item=f64> + 'b>,
-    pub linspace_fraction_generator: Box<dyn Fn(&[f64]) -> Box<dyn Iterator<Item=f64> + '_> + 'b>,
 }
 
 
+/* Branch and bound over the logspace intervals using Lipschitz bounds of the fraction; the found
+ * minimum is a guaranteed lower bound, and the search stops early once it is decided whether the
+ * minimum exceeds the threshold */
+pub struct CertifiedSearch<'b, F>
+where F: Fn(f64, f64) -> f64
+{
+    pub lipschitz_function: &'b F,
+    pub threshold: Option<f64>,
+}
+
+
+/* Outcome of a single fraction minimization */
+#[derive(Debug, Clone, Copy)]
+pub struct MinimizationResult {
+    pub min: f64,
+    pub argmin: f64,
+    pub evaluations: usize,
+    pub out_of_bounds: bool,
+}
+
+
+/* Number of logspace local minima which get refined by Brent's method */
+const REFINEMENT_CANDIDATES: usize = 5;
+
+/* Upper bound on fraction evaluations spent refining a single candidate */
+const BRENT_MAX_EVALUATIONS: usize = 100;
+
+
 pub fn get_linsearch_interval(
     index_of_logmin: usize,
     log_space: &[f64],
@@ -117,9 +259,126 @@ pub fn get_linsearch_interval(
 }
 
 
+pub fn get_log_candidates(log_values: &[f64], count: usize) -> Vec<usize> {
+    /* Collect local minima of the sampled values, the deepest ones coming first */
+    let last_index = log_values.len() - 1;
+    let mut candidates: Vec<usize> = (0..=last_index)
+        .filter(|&i| {
+            let left_ok = i == 0 || log_values[i] <= log_values[i-1];
+            let right_ok = i == last_index || log_values[i] <= log_values[i+1];
+            left_ok && right_ok
+        })
+        .collect();
+
+    candidates.sort_by(|a, b| log_values[*a]
+        .partial_cmp(&log_values[*b])
+        .expect("Invalid value found"));
+    candidates.truncate(count);
+
+    candidates
+}
+
+
+pub fn brent_minimize<F>(
+    mut function: F,
+    a: f64,
+    b: f64,
+    x_tolerance: f64,
+    max_evaluations: usize,
+) -> MinimizationResult
+where F: FnMut(f64) -> f64
+{
+    /* Bounded Brent search (golden section with parabolic interpolation) on [a, b] */
+    let golden_ratio = 0.5 * (3.0 - f64::sqrt(5.0));
+    let sqrt_eps = f64::sqrt(f64::EPSILON);
+    let (mut a, mut b) = (a, b);
+
+    let mut fulc = a + golden_ratio * (b - a);
+    let mut nfc = fulc;
+    let mut xf = fulc;
+    let mut rat: f64 = 0.0;
+    let mut e: f64 = 0.0;
+    let mut fx = function(xf);
+    let mut evaluations = 1;
+    let mut ffulc = fx;
+    let mut fnfc = fx;
+    let mut xm = 0.5 * (a + b);
+    let mut tol1 = sqrt_eps * xf.abs() + x_tolerance / 3.0;
+    let mut tol2 = 2.0 * tol1;
+
+    while (xf - xm).abs() > tol2 - 0.5 * (b - a) && evaluations < max_evaluations {
+        let mut golden = true;
+
+        /* Attempt a parabolic step first */
+        if e.abs() > tol1 {
+            golden = false;
+            let mut r = (xf - nfc) * (fx - ffulc);
+            let mut q = (xf - fulc) * (fx - fnfc);
+            let mut p = (xf - fulc) * q - (xf - nfc) * r;
+            q = 2.0 * (q - r);
+            if q > 0.0 {
+                p = -p;
+            }
+            q = q.abs();
+            r = e;
+            e = rat;
+
+            if p.abs() < (0.5 * q * r).abs() && p > q * (a - xf) && p < q * (b - xf) {
+                rat = p / q;
+                let x = xf + rat;
+                if (x - a) < tol2 || (b - x) < tol2 {
+                    rat = tol1 * if xm >= xf { 1.0 } else { -1.0 };
+                }
+            } else {
+                golden = true;
+            }
+        }
+
+        /* Fall back to a golden section step */
+        if golden {
+            e = if xf >= xm { a - xf } else { b - xf };
+            rat = golden_ratio * e;
+        }
+
+        let direction = if rat >= 0.0 { 1.0 } else { -1.0 };
+        let x = xf + direction * f64::max(rat.abs(), tol1);
+        let fu = function(x);
+        evaluations += 1;
+
+        if fu <= fx {
+            if x >= xf { a = xf } else { b = xf }
+            fulc = nfc;
+            ffulc = fnfc;
+            nfc = xf;
+            fnfc = fx;
+            xf = x;
+            fx = fu;
+        } else {
+            if x < xf { a = x } else { b = x }
+            if fu <= fnfc || nfc == xf {
+                fulc = nfc;
+                ffulc = fnfc;
+                nfc = x;
+                fnfc = fu;
+            } else if fu <= ffulc || fulc == xf || fulc == nfc {
+                fulc = x;
+                ffulc = fu;
+            }
+        }
+
+        xm = 0.5 * (a + b);
+        tol1 = sqrt_eps * xf.abs() + x_tolerance / 3.0;
+        tol2 = 2.0 * tol1;
+    }
+
+    MinimizationResult { min: fx, argmin: xf, evaluations, out_of_bounds: false }
+}
+
+
 lazy_static!(
     static ref MIN_W: Mutex<f64> = Mutex::new(f64::INFINITY);
     static ref MAX_W: Mutex<f64> = Mutex::new(0.0);
+    static ref EVALUATIONS: Mutex<(usize, usize)> = Mutex::new((0, 0));
 );
 
 
@@ -141,87 +400,422 @@ pub fn update_statistics(w: f64) {
 }
 
 
-pub fn find_minimum_fraction_slow<F1, F2>(problem: &MinimizationProblemSlow<F1, F2>) -> f64
-where
-    F1: Fn(f64) -> f64,
-    F2: Fn(f64) -> f64,
+pub fn update_evaluation_statistics(evaluations: usize) {
+    /* Track the total number of minimizations and fraction evaluations they needed */
+    let mut evaluations_unlocked = EVALUATIONS.lock().unwrap();
+    evaluations_unlocked.0 += 1;
+    evaluations_unlocked.1 += evaluations;
+}
+
+
+fn refine_log_minimum<F>(
+    log_values: &[f64],
+    log_space: &[f64],
+    lin_steps: usize,
+    mut fraction: F,
+) -> Option<MinimizationResult>
+where F: FnMut(f64) -> f64
 {
-    let log_denominator_iterator = problem.log_space
-        .iter()
-        .map(|w| (problem.denominator_function)(*w));
-
-    let log_fraction_iterator = problem.precalculated_numerator
-        .iter()
-        .zip(log_denominator_iterator)
-        .map(|(num, denom)| num / denom);
-
-    /* Perform search on logspace */
-    let minind = log_fraction_iterator
-        .enumerate()
-        .min_by(|(_, a), (_, b)| a.partial_cmp(b).expect("Invalid value found"))
-        .map(|(index, _)| index)
-        .expect("Error while searching for log min");
-
-    /* Perform search on linspace */
-    let (w_min, w_max) = match get_linsearch_interval(minind, problem.log_space) {
-        Some(val) => val,
-        None => return 0.0,
+    let mut evaluations = log_values.len();
+    let candidates = get_log_candidates(log_values, REFINEMENT_CANDIDATES);
+    let global_index = *candidates.first().expect("Error while searching for log min");
+
+    /* A minimum on the upper edge of the logspace cannot be refined */
+    get_linsearch_interval(global_index, log_space)?;
+
+    let mut best = MinimizationResult {
+        min: log_values[global_index],
+        argmin: log_space[global_index],
+        evaluations: 0,
+        out_of_bounds: false,
     };
 
-    debug!("Starting linsearch on [{}, {}]", w_min, w_max);
-    let w_linspace = iter_num_tools::lin_space(w_min..=w_max, problem.lin_steps);
-    let function_vals = iter_num_tools::lin_space(w_min..=w_max, problem.lin_steps)
-        .map(|w| (problem.fraction_function)(w));
+    for index in candidates {
+        let (w_min, w_max) = match index == log_space.len() - 1 {
+            true => continue,
+            false => get_linsearch_interval(index, log_space).unwrap(),
+        };
+        debug!("Starting Brent search on [{}, {}]", w_min, w_max);
+
+        /* Linear step count determines the resolution of the refinement */
+        let x_tolerance = (w_max - w_min) / lin_steps as f64;
+        let refined = brent_minimize(&mut fraction, w_min, w_max, x_tolerance, BRENT_MAX_EVALUATIONS);
+        evaluations += refined.evaluations;
+
+        if refined.min < best.min {
+            best.min = refined.min;
+            best.argmin = refined.argmin;
+        }
+    }
+
+    best.evaluations = evaluations;
+    Some(best)
+}
+
+
+/* Fraction of the problem sampled on its logspace */
+fn sample_log_space<P: FractionProblem>(problem: &P) -> Vec<f64> {
+    let mut log_values = vec![0.0; problem.log_space().len()];
+    problem.evaluate_log_space(&mut log_values);
+    log_values
+}
+
+
+impl MinimizationStrategy for GridSearch {
+    fn minimize<P: FractionProblem>(&self, problem: &P) -> MinimizationResult {
+        let log_space = problem.log_space();
+        let log_values = sample_log_space(problem);
+        let candidates = get_log_candidates(&log_values, 1);
+        let index = *candidates.first().expect("Error while searching for log min");
+
+        /* A minimum on the upper edge of the logspace is out of bounds, as with refinement */
+        let result = get_linsearch_interval(index, log_space).map(|_| MinimizationResult {
+            min: log_values[index],
+            argmin: log_space[index],
+            evaluations: log_values.len(),
+            out_of_bounds: false,
+        });
 
-    let (argmin, min) = w_linspace.zip(function_vals)
-        .min_by(|(_, a), (_, b)| a.partial_cmp(b).expect("Invalid value found"))
-        .expect("Error while searching for lin min");
-    update_statistics(argmin);
+        finalize_minimization(result, log_values.len())
+    }
+}
 
-    min
+
+impl MinimizationStrategy for BrentSearch {
+    fn minimize<P: FractionProblem>(&self, problem: &P) -> MinimizationResult {
+        let log_values = sample_log_space(problem);
+
+        let result = refine_log_minimum(
+            &log_values,
+            problem.log_space(),
+            self.lin_steps,
+            |w| problem.evaluate_single(w));
+
+        finalize_minimization(result, log_values.len())
+    }
 }
 
 
 pub fn print_minmax_statistics() {
     let min = MIN_W.lock().unwrap();
     let max = MAX_W.lock().unwrap();
+    let (calls, evaluations) = *EVALUATIONS.lock().unwrap();
 
     info!("Roots for found for w in ({}, {})", min, max);
+    if calls > 0 {
+        info!("Performed {} minimizations with {} fraction evaluations on average",
+            calls, evaluations / calls);
+    }
+}
+
+
+fn finalize_minimization(result: Option<MinimizationResult>, log_evaluations: usize) -> MinimizationResult {
+    let result = match result {
+        Some(result) => result,
+        None => MinimizationResult {
+            min: 0.0,
+            argmin: f64::INFINITY,
+            evaluations: log_evaluations,
+            out_of_bounds: true,
+        },
+    };
+
+    debug!("Found minimum f({}) = {} using {} fraction evaluations",
+        result.argmin, result.min, result.evaluations);
+    if result.argmin.is_finite() {
+        update_statistics(result.argmin);
+    }
+    update_evaluation_statistics(result.evaluations);
+
+    result
+}
+
+/* Relative gap between the sampled minimum and its lower bound at which the search stops */
+const CERTIFIED_RELATIVE_GAP: f64 = 1e-3;
+
+/* Upper bound on fraction evaluations spent by a single certified minimization */
+const CERTIFIED_MAX_EVALUATIONS: usize = 20_000;
+
+
+/* Frequency interval with a guaranteed lower bound of the fraction on it */
+struct BoundedInterval {
+    lower_bound: f64,
+    w_min: f64,
+    w_max: f64,
+    f_min: f64,
+    f_max: f64,
+}
+
+
+impl BoundedInterval {
+    fn new<F>(w_min: f64, w_max: f64, f_min: f64, f_max: f64, lipschitz_function: &F) -> Self
+    where F: Fn(f64, f64) -> f64
+    {
+        /* Lipschitz cone spanned from both endpoints, never above the endpoints themselves */
+        let lipschitz = lipschitz_function(w_min, w_max);
+        let cone_bound = 0.5 * (f_min + f_max) - 0.5 * lipschitz * (w_max - w_min);
+        let lower_bound = f64::min(cone_bound, f64::min(f_min, f_max));
+
+        if lower_bound.is_nan() {
+            panic!("NaN lower bound on [{}, {}]", w_min, w_max);
+        }
+
+        BoundedInterval { lower_bound, w_min, w_max, f_min, f_max }
+    }
+}
+
+
+impl PartialEq for BoundedInterval {
+    fn eq(&self, other: &Self) -> bool {
+        self.lower_bound == other.lower_bound
+    }
+}
+
+
+impl Eq for BoundedInterval {}
+
+
+impl PartialOrd for BoundedInterval {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+
+impl Ord for BoundedInterval {
+    /* Reversed, so that the binary heap pops the lowest bound first */
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.lower_bound
+            .partial_cmp(&self.lower_bound)
+            .expect("Invalid value found")
+    }
+}
+
+
+impl<F> MinimizationStrategy for CertifiedSearch<'_, F>
+where F: Fn(f64, f64) -> f64
+{
+    fn minimize<P: FractionProblem>(&self, problem: &P) -> MinimizationResult {
+        let log_space = problem.log_space();
+        let log_values = sample_log_space(problem);
+        let mut evaluations = log_values.len();
+        let mut upper_bound = f64::INFINITY;
+        let mut argmin = log_space[0];
+
+        for (w, value) in log_space.iter().zip(log_values.iter()) {
+            if *value < upper_bound {
+                upper_bound = *value;
+                argmin = *w;
+            }
+        }
+
+        let mut heap: BinaryHeap<BoundedInterval> = log_space
+            .windows(2)
+            .zip(log_values.windows(2))
+            .map(|(w, f)| BoundedInterval::new(w[0], w[1], f[0], f[1], self.lipschitz_function))
+            .collect();
+
+        let lower_bound = loop {
+            let interval = match heap.pop() {
+                Some(interval) => interval,
+                None => break upper_bound,
+            };
+
+            /* Either the threshold is certified, refuted, or the bound is tight enough */
+            let threshold_decided = match self.threshold {
+                Some(threshold) => interval.lower_bound > threshold || upper_bound <= threshold,
+                None => false,
+            };
+            let gap = upper_bound - interval.lower_bound;
+            if threshold_decided
+                || gap <= CERTIFIED_RELATIVE_GAP * upper_bound.abs()
+                || evaluations >= CERTIFIED_MAX_EVALUATIONS
+            {
+                break interval.lower_bound;
+            }
+
+            let w_mid = 0.5 * (interval.w_min + interval.w_max);
+            if w_mid <= interval.w_min || w_mid >= interval.w_max {
+                warn!("Interval [{}, {}] cannot be split further", interval.w_min, interval.w_max);
+                break interval.lower_bound;
+            }
+
+            let f_mid = problem.evaluate_single(w_mid);
+            evaluations += 1;
+            if f_mid < upper_bound {
+                upper_bound = f_mid;
+                argmin = w_mid;
+            }
+
+            heap.push(BoundedInterval::new(
+                interval.w_min, w_mid, interval.f_min, f_mid, self.lipschitz_function));
+            heap.push(BoundedInterval::new(
+                w_mid, interval.w_max, f_mid, interval.f_max, self.lipschitz_function));
+        };
+
+        debug!("Certified minimum in [{}, {}] near w={} using {} fraction evaluations",
+            lower_bound, upper_bound, argmin, evaluations);
+        update_statistics(argmin);
+        update_evaluation_statistics(evaluations);
+
+        MinimizationResult { min: lower_bound, argmin, evaluations, out_of_bounds: false }
+    }
 }
 
 
-pub fn find_minimum_fraction_fast<'b>(problem: MinimizationProblemFast<'b>) -> f64 {
-    /* Perform search on logspace */
-    let minind = problem.logspace_fraction_iterator
-        .enumerate()
-        .min_by(|(_, a), (_, b)| a.partial_cmp(b).expect("Invalid value found"))
-        .map(|(index, _)| index)
-        .expect("Error while searching for log min");
+pub struct TailProblem<'b, P, S, F>
+where
+    P: FractionProblem,
+    S: MinimizationStrategy,
+    F: Fn(f64, f64) -> (f64, f64),
+{
+    pub problem: &'b P,
+    pub strategy: &'b S,
+    pub window_result: MinimizationResult,
+    pub steps_per_decade: usize,
+    pub tails_function: &'b F,
+    pub threshold: f64,
+}
 
-    /* Perform search on linspace */
-    let (w_min, w_max) = match get_linsearch_interval(minind, problem.log_space) {
-        Some(val) => val,
-        None => return 0.0,
+
+/* Factor by which an insufficient window edge is pushed outwards */
+const TAIL_EXTENSION_FACTOR: f64 = 100.0;
+
+/* Maximal number of window extensions before the tails are accepted as they are */
+const TAIL_MAX_EXTENSIONS: usize = 8;
+
+
+/* Fraction of another problem, sampled on a logspace beyond the original one */
+struct ExtensionFraction<'b, P: FractionProblem> {
+    problem: &'b P,
+    log_space: Vec<f64>,
+}
+
+
+impl<P: FractionProblem> FractionProblem for ExtensionFraction<'_, P> {
+    fn log_space(&self) -> &[f64] {
+        &self.log_space
+    }
+
+    fn evaluate(&self, w: &[f64], out: &mut [f64]) {
+        self.problem.evaluate(w, out)
+    }
+}
+
+
+fn minimize_extension<P, S>(
+    problem: &P,
+    strategy: &S,
+    w_min: f64,
+    w_max: f64,
+    steps: usize,
+) -> f64
+where
+    P: FractionProblem,
+    S: MinimizationStrategy,
+{
+    let extension = ExtensionFraction {
+        problem,
+        log_space: iter_num_tools::log_space(w_min..=w_max, steps).collect(),
     };
-    debug!("Starting linsearch on [{}, {}]", w_min, w_max);
+    let result = strategy.minimize(&extension);
 
-    let w_linspace: Vec<f64> =
-        iter_num_tools::lin_space(w_min..=w_max, problem.lin_steps).collect();
-    let linspace_iter = (problem.linspace_fraction_generator)(&w_linspace);
+    /* An edge minimum is covered by the next extension or by the tail bound */
+    match result.out_of_bounds {
+        true => problem.evaluate_single(w_max),
+        false => result.min,
+    }
+}
 
-    let w_linspace: Vec<f64> =
-        iter_num_tools::lin_space(w_min..=w_max, problem.lin_steps).collect();
 
-    let (min, argmin) = linspace_iter.zip(w_linspace)
-        .min_by(|(a, _), (b, _)| a.partial_cmp(b).expect("Invalid value found"))
-        .expect("Error while searching for lin min");
+pub fn combine_with_tails<P, S, F>(problem: &TailProblem<P, S, F>) -> f64
+where
+    P: FractionProblem,
+    S: MinimizationStrategy,
+    F: Fn(f64, f64) -> (f64, f64),
+{
+    let log_space = problem.problem.log_space();
+    let mut w_min = log_space[0];
+    let mut w_max = log_space[log_space.len() - 1];
+
+    /* A minimum on the window edge is sampled there, frequencies beyond belong to the tail */
+    let mut min = match problem.window_result.out_of_bounds {
+        true => problem.problem.evaluate_single(w_max),
+        false => problem.window_result.min,
+    };
+    let steps = usize::max(2, problem.steps_per_decade * TAIL_EXTENSION_FACTOR.log10() as usize);
+
+    for _ in 0..TAIL_MAX_EXTENSIONS {
+        if min <= problem.threshold {
+            return min;
+        }
+
+        let (low_tail, high_tail) = (problem.tails_function)(w_min, w_max);
+        if low_tail > problem.threshold && high_tail > problem.threshold {
+            return f64::min(min, f64::min(low_tail, high_tail));
+        }
 
-    debug!("Found lin minimum f({}) = {}", argmin, min);
-    update_statistics(argmin);
+        if low_tail <= problem.threshold {
+            let w_new = w_min / TAIL_EXTENSION_FACTOR;
+            debug!("Low tail bound {} insufficient; extending window to {}", low_tail, w_new);
+            let extension_min = minimize_extension(
+                problem.problem, problem.strategy, w_new, w_min, steps);
+            min = f64::min(min, extension_min);
+            w_min = w_new;
+        }
+
+        if high_tail <= problem.threshold {
+            let w_new = w_max * TAIL_EXTENSION_FACTOR;
+            debug!("High tail bound {} insufficient; extending window to {}", high_tail, w_new);
+            let extension_min = minimize_extension(
+                problem.problem, problem.strategy, w_max, w_new, steps);
+            min = f64::min(min, extension_min);
+            w_max = w_new;
+        }
+    }
 
-    min
+    let (low_tail, high_tail) = (problem.tails_function)(w_min, w_max);
+    warn!("Tail bounds ({}, {}) still insufficient on [{}, {}]", low_tail, high_tail, w_min, w_max);
+
+    f64::min(min, f64::min(low_tail, high_tail))
 }
 
+
+/* Minimum of the fraction found by the strategy; frequencies beyond the logspace are covered by
+ * the tail bounds, if given, with the window extended by the same strategy while they are insufficient */
+pub fn minimize_with_tails<P, S, F>(
+    problem: &P,
+    strategy: &S,
+    tails_function: Option<&F>,
+    threshold: f64,
+) -> f64
+where
+    P: FractionProblem,
+    S: MinimizationStrategy,
+    F: Fn(f64, f64) -> (f64, f64),
+{
+    let window_result = strategy.minimize(problem);
+
+    match tails_function {
+        Some(tails_function) => combine_with_tails(&TailProblem {
+            problem,
+            strategy,
+            window_result,
+            steps_per_decade: get_steps_per_decade(problem.log_space()),
+            tails_function,
+            threshold,
+        }),
+        None => window_result.min,
+    }
+}
+
+
+pub fn get_steps_per_decade(log_space: &[f64]) -> usize {
+    let decades = (log_space[log_space.len() - 1] / log_space[0]).log10();
+    usize::max(1, (log_space.len() as f64 / decades).ceil() as usize)
+}
+
+
 #[cfg(test)]
 mod tests {
     use log::LevelFilter;
@@ -261,33 +855,199 @@ mod tests {
     }
 
 
-    // #[test]
-    // fn test_find_minimum() {
-    //     /* Numerator is (x-x_offset)^3, denominator is (x-x_offset) */
-    //     let w_min = 1e-3;
-    //     let w_max = 1e5;
-    //     let x_offset = 2.0;
-    //     let steps = 10;
-    //     let log_steps = steps;
-    //     let lin_steps = steps;
-    //     let log_space: Vec<f64> = iter_num_tools::log_space(w_min..=w_max, log_steps).collect();
-    //     let precalculated_numerator: Vec<f64> = log_space.iter().map(|w| (w-x_offset).powi(3).abs()).collect();
-    //     let denominator_function = |w: f64| (w-x_offset).abs();
-    //     let fraction_function = |w: f64| (w-x_offset).powi(2);
-
-    //     let problem = MinimizationProblem {
-    //         log_space: &log_space,
-    //         lin_steps: lin_steps,
-    //         precalculated_numerator : &precalculated_numerator,
-    //         denominator_function: &denominator_function,
-    //         fraction_function: &fraction_function,
-
-    //     };
-
-    //     let obtained = find_minimum_fraction(&problem);
-    //     let expected = 0.0;
-    //     let eps = 1e-1;
-    //     println!("obtained vs expected: {} vs {}", obtained, expected);
-    //     assert_floats_eq(obtained, expected, eps);
-    // }
+    #[test]
+    fn test_get_maximum_fixed_point() {
+        /* Condition x < 10 / (1 + x) holds up to the fixed point 2.7015... */
+        let calls = std::cell::Cell::new(0);
+        let minimum = |x: f64| {
+            calls.set(calls.get() + 1);
+            (10.0 / (1.0 + x), true)
+        };
+        let fixed_point = (-1.0 + f64::sqrt(41.0)) / 2.0;
+        let tolerance = 1e-3;
+
+        let result = get_maximum_fixed_point(minimum, 0.5, tolerance, f64::INFINITY);
+        assert!(result < fixed_point && fixed_point - result < tolerance);
+        assert!(calls.get() < 20);
+
+        let result = get_maximum_fixed_point(minimum, 0.5, tolerance, 1.0);
+        assert_floats_eq(result, 1.0, 1e-12);
+
+        /* A guess above the fixed point only serves as the first upper bound */
+        let result = get_maximum_fixed_point(minimum, 100.0, tolerance, f64::INFINITY);
+        assert!(result < fixed_point && fixed_point - result < tolerance);
+
+        /* Lower bounds stopped just above the threshold, like a certified search does, must not
+         * be mistaken for an upper bound on the fixed point */
+        let loose_minimum = |x: f64| {
+            let minimum = 10.0 / (1.0 + x);
+            match minimum > x {
+                true => (f64::min(minimum, x + 1e-6), false),
+                false => (minimum, false),
+            }
+        };
+        let result = get_maximum_fixed_point(loose_minimum, 0.5, tolerance, f64::INFINITY);
+        assert!(result < fixed_point && fixed_point - result < tolerance);
+    }
+
+
+    #[test]
+    fn test_brent_minimize() {
+        let function = |x: f64| (x - 1.3).powi(2) + 0.5;
+        let result = brent_minimize(function, 0.0, 4.0, 1e-8, 100);
+        assert_floats_eq(result.argmin, 1.3, 1e-6);
+        assert_floats_eq(result.min, 0.5, 1e-10);
+        assert!(result.evaluations < 100);
+    }
+
+
+    #[test]
+    fn test_brent_search_secondary_candidate() {
+        /* Wide dip at w=2 dominates the logspace, but the narrow dip at w=30.3 is deeper */
+        let fraction = |w: f64| {
+            let shallow = 1.0 - 0.5 * f64::exp(-(w - 2.0).powi(2));
+            let deep = 0.9 * f64::exp(-((w - 30.3) / 0.3).powi(2));
+            shallow - deep
+        };
+        let log_space: Vec<f64> = iter_num_tools::log_space(1e-1..=1e3, 400).collect();
+        let batch_fraction = |w: &[f64], out: &mut [f64]| {
+            for (out, w) in out.iter_mut().zip(w) {
+                *out = fraction(*w);
+            }
+        };
+
+        let problem = BatchFraction {
+            log_space: &log_space,
+            logspace_fraction_function: &batch_fraction,
+            fraction_function: &batch_fraction,
+        };
+
+        let result = BrentSearch { lin_steps: 1000 }.minimize(&problem);
+        assert_floats_eq(result.argmin, 30.3, 1e-3);
+        assert_floats_eq(result.min, 0.1, 1e-6);
+        assert!(result.evaluations < 400 + REFINEMENT_CANDIDATES * BRENT_MAX_EVALUATIONS);
+
+        /* Sampling alone only bounds the minimum from above */
+        let sampled = GridSearch.minimize(&problem);
+        assert!(sampled.min >= result.min);
+        assert_eq!(sampled.evaluations, 400);
+    }
+
+
+    #[test]
+    fn test_certified_search() {
+        /* |sin(w)| + 0.1 has Lipschitz constant 1 and minimum 0.1 at multiples of pi */
+        let fraction = |w: &[f64], out: &mut [f64]| {
+            for (out, w) in out.iter_mut().zip(w) {
+                *out = w.sin().abs() + 0.1;
+            }
+        };
+        let lipschitz = |_w_min: f64, _w_max: f64| 1.0;
+        let log_space: Vec<f64> = iter_num_tools::log_space(1.0..=10.0, 7).collect();
+
+        let problem = BatchFraction {
+            log_space: &log_space,
+            logspace_fraction_function: &fraction,
+            fraction_function: &fraction,
+        };
+        let mut strategy = CertifiedSearch {
+            lipschitz_function: &lipschitz,
+            threshold: Option::None,
+        };
+        let result = strategy.minimize(&problem);
+        assert!(result.min <= 0.1);
+        assert_floats_eq(result.min, 0.1, 1e-3);
+
+        /* A threshold below the minimum is certified without reaching full precision */
+        strategy.threshold = Option::Some(0.05);
+        let result = strategy.minimize(&problem);
+        assert!(result.min > 0.05 && result.min <= 0.1);
+    }
+
+
+    #[test]
+    fn test_combine_with_tails() {
+        /* Fraction dips to 0.2 at w=1e3, far beyond the sampled window */
+        let fraction = |w: f64| 1.0 - 0.8 * f64::exp(-(w.log10() - 3.0).powi(2));
+        let tails = |w_min: f64, w_max: f64| {
+            let low_tail = if w_min <= 1e3 { fraction(w_min) } else { 0.2 };
+            let high_tail = if w_max >= 1e3 { fraction(w_max) } else { 0.2 };
+            (low_tail, high_tail)
+        };
+        let log_space: Vec<f64> = iter_num_tools::log_space(1.0..=10.0, 100).collect();
+        let batch_fraction = |w: &[f64], out: &mut [f64]| {
+            for (out, w) in out.iter_mut().zip(w) {
+                *out = fraction(*w);
+            }
+        };
+        let fraction_problem = BatchFraction {
+            log_space: &log_space,
+            logspace_fraction_function: &batch_fraction,
+            fraction_function: &batch_fraction,
+        };
+        let window_result = MinimizationResult {
+            min: 0.0,
+            argmin: f64::INFINITY,
+            evaluations: log_space.len(),
+            out_of_bounds: true,
+        };
+
+        /* The window edge minimum is replaced by the edge sample and the extended tail */
+        let strategy = BrentSearch { lin_steps: 1000 };
+        let mut problem = TailProblem {
+            problem: &fraction_problem,
+            strategy: &strategy,
+            window_result,
+            steps_per_decade: get_steps_per_decade(&log_space),
+            tails_function: &tails,
+            threshold: 0.1,
+        };
+        let min = combine_with_tails(&problem);
+        assert_floats_eq(min, 0.2, 1e-6);
+
+        /* A threshold above the tail bound forces the window to extend over the dip */
+        problem.threshold = 0.5;
+        let min = combine_with_tails(&problem);
+        assert_floats_eq(min, 0.2, 1e-3);
+
+        /* Certified extensions bound the dip from below, |f'(w)| <= 0.3 / w */
+        let lipschitz = |w_min: f64, _w_max: f64| 0.3 / w_min;
+        let strategy = CertifiedSearch { lipschitz_function: &lipschitz, threshold: Option::None };
+        let min = minimize_with_tails(&fraction_problem, &strategy, Some(&tails), 0.5);
+        assert!(min <= 0.2, "Certified minimum {} above the true minimum", min);
+        assert_floats_eq(min, 0.2, 1e-2);
+    }
+
+
+    #[test]
+    fn test_find_minimum() {
+        /* Numerator is |x-x_offset|^3, denominator is |x-x_offset| */
+        let w_min = 1e-3;
+        let w_max = 1e5;
+        let x_offset = 2.0;
+        let steps = 10;
+        let log_space: Vec<f64> = iter_num_tools::log_space(w_min..=w_max, steps).collect();
+        let precalculated_numerator: Vec<f64> = log_space.iter().map(|w| (w-x_offset).powi(3).abs()).collect();
+        let logspace_fraction = |w: &[f64], out: &mut [f64]| {
+            for ((out, num), w) in out.iter_mut().zip(&precalculated_numerator).zip(w) {
+                *out = num / (w-x_offset).abs();
+            }
+        };
+        let fraction = |w: &[f64], out: &mut [f64]| {
+            for (out, w) in out.iter_mut().zip(w) {
+                *out = (w-x_offset).powi(2);
+            }
+        };
+
+        let problem = BatchFraction {
+            log_space: &log_space,
+            logspace_fraction_function: &logspace_fraction,
+            fraction_function: &fraction,
+        };
+
+        let obtained = BrentSearch { lin_steps: steps }.minimize(&problem).min;
+        let expected = 0.0;
+        let eps = 1e-1;
+        assert_floats_eq(obtained, expected, eps);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,261 @@
+/* Golden-output regression tests: scaled-down variants of every nu, line and region configuration
+ * are run, and their results are compared against the files committed in tests/golden.
+ * Running the tests with UPDATE_GOLDEN=1 regenerates the files instead of comparing them. */
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use fsals::data::line::{self, LineConfiguration};
+use fsals::data::origins::Origins;
+use fsals::data::region::{self, CoverageConfiguration, RegionConfiguration};
+use fsals::nu::{self, NuConfiguration};
+use fsals::types::{Limits, Par};
+use fsals::utils::geometry;
+
+
+const RELATIVE_TOLERANCE: f64 = 1e-6;
+const ABSOLUTE_TOLERANCE: f64 = 1e-9;
+
+/* Granularity of the scaled-down variants */
+const NU_GRID_STEP: usize = 3;
+const AUTO_ORIGINS_GRID_STEP: usize = 5;
+const RAY_COUNT: usize = 8;
+const SPAWN_COUNT: usize = 8;
+const MAX_ITER: u32 = 3;
+const MAX_LOG_SPACE_STEPS: usize = 200;
+const MAX_LIN_STEPS: usize = 200;
+const DELTA_FACTOR: f64 = 10.0;
+const LOCAL_FRACTION: f64 = 0.05;
+
+
+fn get_golden_path(kind: &str, name: &str) -> PathBuf {
+    let mut path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "golden", kind, name].iter().collect();
+    path.set_extension("json");
+    path
+}
+
+
+/* Integers (e.g. nu and depths) must match exactly, while floats are compared with tolerance */
+fn compare_values(expected: &Value, actual: &Value, location: &str, mismatches: &mut Vec<String>) {
+    match (expected, actual) {
+        (Value::Number(e), Value::Number(a)) if e.is_f64() || a.is_f64() => {
+            let (e, a) = (e.as_f64().unwrap(), a.as_f64().unwrap());
+            if (e - a).abs() > ABSOLUTE_TOLERANCE + RELATIVE_TOLERANCE * e.abs() {
+                mismatches.push(format!("{}: expected {}, got {}", location, e, a));
+            }
+        },
+        (Value::Array(e), Value::Array(a)) => {
+            if e.len() != a.len() {
+                mismatches.push(format!("{}: expected {} elements, got {}", location, e.len(), a.len()));
+                return;
+            }
+            for (i, (e, a)) in e.iter().zip(a.iter()).enumerate() {
+                compare_values(e, a, &format!("{}[{}]", location, i), mismatches);
+            }
+        },
+        (Value::Object(e), Value::Object(a)) => {
+            for (key, e) in e {
+                match a.get(key) {
+                    Some(a) => compare_values(e, a, &format!("{}.{}", location, key), mismatches),
+                    None => mismatches.push(format!("{}: missing field {}", location, key)),
+                }
+            }
+        },
+        (e, a) => if e != a {
+            mismatches.push(format!("{}: expected {}, got {}", location, e, a));
+        },
+    }
+}
+
+
+fn check_golden<T: Serialize>(kind: &str, name: &str, result: &T) -> Vec<String> {
+    let actual = serde_json::to_value(result).unwrap();
+    let path = get_golden_path(kind, name);
+
+    if env::var("UPDATE_GOLDEN").is_ok() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, serde_json::to_string_pretty(&actual).unwrap()).unwrap();
+        return Vec::new();
+    }
+
+    let expected: Value = match fs::read_to_string(&path) {
+        Ok(contents) => serde_json::from_str(&contents).unwrap(),
+        Err(_) => return vec![format!("{}/{}: missing golden file, run with UPDATE_GOLDEN=1", kind, name)],
+    };
+
+    let mut mismatches = Vec::new();
+    compare_values(&expected, &actual, &format!("{}/{}", kind, name), &mut mismatches);
+    mismatches
+}
+
+
+/* Parallel region search depends on scheduling, so results are reproducible only on one thread */
+fn run_single_threaded<R: Send>(f: impl FnOnce() -> R + Send) -> R {
+    rayon::ThreadPoolBuilder::new().num_threads(1).build().unwrap().install(f)
+}
+
+
+fn get_sorted_names<T>(configs: &std::collections::HashMap<&'static str, T>) -> Vec<&'static str> {
+    let mut names: Vec<&'static str> = configs.keys().copied().collect();
+    names.sort();
+    names
+}
+
+
+fn scale_down_origins(origins: &Origins) -> Origins {
+    match origins {
+        Origins::Manual(origins) => Origins::Manual(origins.clone()),
+        Origins::Auto(_) => Origins::Auto(AUTO_ORIGINS_GRID_STEP),
+    }
+}
+
+
+fn scale_down_line_delta(delta: &geometry::Delta) -> geometry::Delta {
+    match delta {
+        geometry::Delta::Abs(abs) => geometry::Delta::Abs(abs * DELTA_FACTOR),
+        geometry::Delta::Rel(rel) => geometry::Delta::Rel(rel * DELTA_FACTOR),
+    }
+}
+
+
+fn scale_down_region_delta(delta: &region::Delta) -> region::Delta {
+    match delta {
+        region::Delta::Abs(abs) => region::Delta::Abs(abs * DELTA_FACTOR),
+        region::Delta::Rel(rel) => region::Delta::Rel(rel * DELTA_FACTOR),
+    }
+}
+
+
+fn scale_down_nu(conf: &NuConfiguration) -> &'static NuConfiguration {
+    Box::leak(Box::new(NuConfiguration {
+        name: conf.name,
+        system: conf.system,
+        limits: conf.limits.clone(),
+        grid_step: NU_GRID_STEP,
+        adaptive_depth: conf.adaptive_depth.map(|_| 1),
+        contour_conf: conf.contour_conf.clone(),
+    }))
+}
+
+
+/* Rays of the line algorithm end at the limits, so shrinking them bounds the work per origin */
+fn get_local_limits(limits: &Limits, origin: Par) -> Limits {
+    let half_span1 = LOCAL_FRACTION * (limits.p1_max - limits.p1_min) / 2.0;
+    let half_span2 = LOCAL_FRACTION * (limits.p2_max - limits.p2_min) / 2.0;
+    Limits {
+        p1_min: limits.p1_min.max(origin.0 - half_span1),
+        p1_max: limits.p1_max.min(origin.0 + half_span1),
+        p2_min: limits.p2_min.max(origin.1 - half_span2),
+        p2_max: limits.p2_max.min(origin.1 + half_span2),
+    }
+}
+
+
+fn scale_down_line(conf: &LineConfiguration, origin: Par) -> LineConfiguration {
+    LineConfiguration {
+        name: conf.name,
+        system: conf.system,
+        limits: get_local_limits(&conf.limits, origin),
+        origins: Origins::Manual(vec![origin]),
+        ray_count: RAY_COUNT,
+        contour_conf: conf.contour_conf.clone(),
+        delta: scale_down_line_delta(&conf.delta),
+        safeguard: conf.safeguard,
+        w_steps_linear: conf.w_steps_linear.min(MAX_LIN_STEPS),
+        log_space_minw: conf.log_space_minw,
+        log_space_maxw: conf.log_space_maxw,
+        log_space_steps: conf.log_space_steps.min(MAX_LOG_SPACE_STEPS),
+        certified_minimum: conf.certified_minimum,
+        corrective_ratio: conf.corrective_ratio,
+    }
+}
+
+
+fn scale_down_region(conf: &RegionConfiguration) -> RegionConfiguration {
+    RegionConfiguration {
+        name: conf.name,
+        system: conf.system,
+        limits: conf.limits.clone(),
+        origins: scale_down_origins(&conf.origins),
+        contour_conf: conf.contour_conf.clone(),
+        delta: scale_down_region_delta(&conf.delta),
+        safeguard: conf.safeguard,
+        spawn_count: SPAWN_COUNT,
+        enforce_limits: conf.enforce_limits,
+        lin_steps: conf.lin_steps.min(MAX_LIN_STEPS),
+        log_space_minw: conf.log_space_minw,
+        log_space_maxw: conf.log_space_maxw,
+        log_space_steps: conf.log_space_steps.min(MAX_LOG_SPACE_STEPS),
+        certified_minimum: conf.certified_minimum,
+        max_iter: Some(conf.max_iter.unwrap_or(MAX_ITER).min(MAX_ITER)),
+        check_obsoletion: conf.check_obsoletion,
+        nu_modifier: conf.nu_modifier,
+        q: conf.q,
+        scaling: conf.scaling,
+        coverage: conf.coverage.as_ref().map(|coverage| CoverageConfiguration {
+            target: coverage.target,
+            grid_step: 10,
+            max_rounds: 1,
+        }),
+    }
+}
+
+
+fn assert_no_mismatches(mismatches: Vec<String>) {
+    assert!(mismatches.is_empty(), "Results differ from golden files:\n{}", mismatches.join("\n"));
+}
+
+
+#[test]
+fn test_golden_nu() {
+    let mut mismatches = Vec::new();
+    for name in get_sorted_names(&nu::CONFIGURATONS) {
+        let conf = scale_down_nu(&nu::CONFIGURATONS[name]);
+        let result = run_single_threaded(|| nu::calculate_nu(conf, false));
+        mismatches.extend(check_golden("nu", name, &result));
+    }
+    assert_no_mismatches(mismatches);
+}
+
+
+#[test]
+fn test_golden_line() {
+    let mut mismatches = Vec::new();
+    for name in get_sorted_names(&line::CONFIGURATONS) {
+        let conf = &line::CONFIGURATONS[name];
+        let rayfans = run_single_threaded(|| {
+            scale_down_origins(&conf.origins)
+                .get_origins(&conf.system, &conf.limits, &conf.contour_conf)
+                .into_iter()
+                .map(|origin| {
+                    let local_conf = scale_down_line(conf, origin);
+                    line::get_rayfan(&local_conf, origin, &local_conf.get_log_space(), false)
+                })
+                .collect::<Vec<_>>()
+        });
+        mismatches.extend(check_golden("line", name, &rayfans));
+    }
+    assert_no_mismatches(mismatches);
+}
+
+
+#[test]
+fn test_golden_region() {
+    let mut mismatches = Vec::new();
+    for name in get_sorted_names(&region::CONFIGURATONS) {
+        let conf = scale_down_region(&region::CONFIGURATONS[name]);
+        let regions = run_single_threaded(|| {
+            let origins = conf.origins.get_origins(&conf.system, &conf.limits, &conf.contour_conf);
+            let mut regions = region::get_regions(&conf, &origins);
+            if let Some(coverage) = &conf.coverage {
+                region::extend_coverage(&conf, coverage, &mut regions);
+            }
+            regions
+        });
+        mismatches.extend(check_golden("region", name, &regions));
+    }
+    assert_no_mismatches(mismatches);
+}